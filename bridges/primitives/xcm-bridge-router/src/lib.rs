@@ -21,7 +21,7 @@
 use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::sp_std::fmt::Debug;
-use sp_runtime::{FixedU128, RuntimeDebug};
+use sp_runtime::{FixedPointNumber, FixedU128, RuntimeDebug, Saturating};
 use xcm::latest::prelude::{InteriorLocation, Location, NetworkId};
 
 /// Minimal delivery fee factor.
@@ -42,6 +42,45 @@ impl Default for BridgeState {
 	}
 }
 
+impl BridgeState {
+	/// Message size factor used to scale the fee by the size (in bytes, relative to `threshold`)
+	/// of the message being sent.
+	pub const MESSAGE_SIZE_FACTOR: FixedU128 = FixedU128::from_rational(1, 1000);
+
+	/// Extra exponential boost applied to the fee factor on every congested message.
+	pub const EXPONENTIAL_FEE_BOOST: FixedU128 = FixedU128::from_rational(5, 100);
+
+	/// Rate at which the fee factor relaxes back towards `MINIMAL_DELIVERY_FEE_FACTOR` on every
+	/// call to [`Self::decay`].
+	pub const DECAY_RATE: FixedU128 = FixedU128::from_rational(2, 100);
+
+	/// Record that a message of `message_size` bytes has been sent.
+	///
+	/// If the bridge is congested, the delivery fee factor is bumped multiplicatively, scaled by
+	/// `message_size` relative to `threshold`. Does nothing while the bridge is not congested.
+	pub fn on_message_sent(&mut self, message_size: u32, threshold: u32) {
+		if !self.is_congested {
+			return;
+		}
+
+		let message_size_factor = FixedU128::from_u32(message_size.saturating_div(threshold.max(1)))
+			.saturating_mul(Self::MESSAGE_SIZE_FACTOR);
+		let total_factor = FixedU128::one()
+			.saturating_add(message_size_factor)
+			.saturating_add(Self::EXPONENTIAL_FEE_BOOST);
+
+		self.delivery_fee_factor = self.delivery_fee_factor.saturating_mul(total_factor);
+	}
+
+	/// Relax the delivery fee factor towards its floor. Meant to be called once per block.
+	pub fn decay(&mut self) {
+		self.delivery_fee_factor = self
+			.delivery_fee_factor
+			.saturating_mul(FixedU128::one().saturating_sub(Self::DECAY_RATE))
+			.max(MINIMAL_DELIVERY_FEE_FACTOR);
+	}
+}
+
 /// Trait that resolves a specific `BridgeId` for `dest`.
 pub trait ResolveBridgeId {
 	/// Bridge identifier.
@@ -76,8 +115,46 @@ impl ResolveBridgeId for () {
 /// A minimized version of `pallet-xcm-bridge-router::Call` that can be used without a runtime.
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, TypeInfo)]
 #[allow(non_camel_case_types)]
-pub enum XcmBridgeHubCall<BridgeId> {
+pub enum XcmBridgeHubCall<BridgeId, Balance = u128> {
 	/// `pallet-xcm-bridge-router::Call::update_bridge_status`
+	///
+	/// Kept for backward compatibility - this unsigned report carries no accountability for a
+	/// misreported congestion status.
 	#[codec(index = 0)]
 	update_bridge_status { bridge_id: BridgeId, is_congested: bool },
+	/// `pallet-xcm-bridge-router::Call::report_bridge_status_signed`
+	///
+	/// Like `update_bridge_status`, but bonds `bond` behind the report. If the relay-chain
+	/// observed congestion status diverges from `is_congested` within the challenge window, the
+	/// bond can be slashed - see [`SlashableReport`] and [`ResolveReporter`].
+	#[codec(index = 1)]
+	report_bridge_status_signed { bridge_id: BridgeId, is_congested: bool, bond: Balance },
+}
+
+/// A bonded report of a bridge's congestion status, recorded so that a runtime can challenge and
+/// slash it if it turns out to be misreported.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct SlashableReport<AccountId, BlockNumber> {
+	/// The account that submitted the report and bonded behind it.
+	pub reporter: AccountId,
+	/// The congestion state claimed by the reporter.
+	pub is_congested: bool,
+	/// The block at which the report was submitted, i.e. the start of the challenge window.
+	pub at: BlockNumber,
+}
+
+/// Trait that resolves the account to be slashed for a misreported [`SlashableReport`].
+pub trait ResolveReporter<AccountId, BlockNumber> {
+	/// Resolves the account that should be slashed if the relay-chain-observed congestion status
+	/// diverges from the one claimed by `report`. Returns `None` if the report can no longer be
+	/// (or never could be) slashed - e.g. because its challenge window already elapsed.
+	fn resolve_reporter(report: &SlashableReport<AccountId, BlockNumber>) -> Option<AccountId>;
+}
+
+/// The default implementation of `ResolveReporter` just holds the reporter accountable for their
+/// own report.
+impl<AccountId: Clone, BlockNumber> ResolveReporter<AccountId, BlockNumber> for () {
+	fn resolve_reporter(report: &SlashableReport<AccountId, BlockNumber>) -> Option<AccountId> {
+		Some(report.reporter.clone())
+	}
 }
@@ -65,6 +65,10 @@ impl ChainWithTransactions for BridgeHubRococo {
 	type AccountKeyPair = sp_core::sr25519::Pair;
 	type SignedTransaction = runtime::UncheckedExtrinsic;
 
+	fn account_id_from_signer(signer: &Self::AccountKeyPair) -> Self::AccountId {
+		sp_runtime::MultiSigner::from(signer.public()).into_account()
+	}
+
 	fn sign_transaction(
 		param: SignParam<Self>,
 		unsigned: UnsignedTransaction<Self>,
@@ -82,12 +86,12 @@ impl ChainWithTransactions for BridgeHubRococo {
 		)?;
 
 		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
-		let signer: sp_runtime::MultiSigner = param.signer.public().into();
+		let signer_account_id = Self::account_id_from_signer(&param.signer);
 		let (call, extra, _) = raw_payload.deconstruct();
 
 		Ok(runtime::UncheckedExtrinsic::new_signed(
 			call,
-			signer.into_account().into(),
+			signer_account_id.into(),
 			signature.into(),
 			extra,
 		))
@@ -101,7 +105,7 @@ impl ChainWithTransactions for BridgeHubRococo {
 		tx.signature
 			.as_ref()
 			.map(|(address, _, _)| {
-				*address == bp_bridge_hub_rococo::Address::Id(signer.public().into())
+				*address == bp_bridge_hub_rococo::Address::Id(Self::account_id_from_signer(signer))
 			})
 			.unwrap_or(false)
 	}
@@ -71,6 +71,10 @@ impl ChainWithTransactions for BridgeHubWococo {
 	type AccountKeyPair = sp_core::sr25519::Pair;
 	type SignedTransaction = UncheckedExtrinsic;
 
+	fn account_id_from_signer(signer: &Self::AccountKeyPair) -> Self::AccountId {
+		sp_runtime::MultiSigner::from(signer.public()).into_account()
+	}
+
 	fn sign_transaction(
 		param: SignParam<Self>,
 		unsigned: UnsignedTransaction<Self>,
@@ -88,12 +92,12 @@ impl ChainWithTransactions for BridgeHubWococo {
 		)?;
 
 		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
-		let signer: sp_runtime::MultiSigner = param.signer.public().into();
+		let signer_account_id = Self::account_id_from_signer(&param.signer);
 		let (call, extra, _) = raw_payload.deconstruct();
 
 		Ok(UncheckedExtrinsic::new_signed(
 			call,
-			signer.into_account().into(),
+			signer_account_id.into(),
 			signature.into(),
 			extra,
 		))
@@ -107,7 +111,7 @@ impl ChainWithTransactions for BridgeHubWococo {
 		tx.signature
 			.as_ref()
 			.map(|(address, _, _)| {
-				*address == bp_bridge_hub_wococo::Address::Id(signer.public().into())
+				*address == bp_bridge_hub_wococo::Address::Id(Self::account_id_from_signer(signer))
 			})
 			.unwrap_or(false)
 	}
@@ -84,6 +84,10 @@ impl ChainWithTransactions for Millau {
 	type AccountKeyPair = sp_core::sr25519::Pair;
 	type SignedTransaction = millau_runtime::UncheckedExtrinsic;
 
+	fn account_id_from_signer(signer: &Self::AccountKeyPair) -> Self::AccountId {
+		sp_runtime::MultiSigner::from(signer.public()).into_account()
+	}
+
 	fn sign_transaction(
 		param: SignParam<Self>,
 		unsigned: UnsignedTransaction<Self>,
@@ -116,12 +120,12 @@ impl ChainWithTransactions for Millau {
 			),
 		);
 		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
-		let signer: sp_runtime::MultiSigner = param.signer.public().into();
+		let signer_account_id = Self::account_id_from_signer(&param.signer);
 		let (call, extra, _) = raw_payload.deconstruct();
 
 		Ok(millau_runtime::UncheckedExtrinsic::new_signed(
 			call.into_decoded()?,
-			signer.into_account(),
+			signer_account_id,
 			signature.into(),
 			extra,
 		))
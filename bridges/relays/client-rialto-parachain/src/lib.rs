@@ -85,6 +85,10 @@ impl ChainWithTransactions for RialtoParachain {
 	type SignedTransaction =
 		bp_polkadot_core::UncheckedExtrinsic<Self::Call, bp_rialto_parachain::SignedExtension>;
 
+	fn account_id_from_signer(signer: &Self::AccountKeyPair) -> Self::AccountId {
+		sp_runtime::MultiSigner::from(signer.public()).into_account()
+	}
+
 	fn sign_transaction(
 		param: SignParam<Self>,
 		unsigned: UnsignedTransaction<Self>,
@@ -102,12 +106,12 @@ impl ChainWithTransactions for RialtoParachain {
 		)?;
 
 		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
-		let signer: sp_runtime::MultiSigner = param.signer.public().into();
+		let signer_account_id = Self::account_id_from_signer(&param.signer);
 		let (call, extra, _) = raw_payload.deconstruct();
 
 		Ok(Self::SignedTransaction::new_signed(
 			call,
-			signer.into_account().into(),
+			signer_account_id.into(),
 			signature.into(),
 			extra,
 		))
@@ -120,7 +124,7 @@ impl ChainWithTransactions for RialtoParachain {
 	fn is_signed_by(signer: &Self::AccountKeyPair, tx: &Self::SignedTransaction) -> bool {
 		tx.signature
 			.as_ref()
-			.map(|(address, _, _)| *address == Address::Id(signer.public().into()))
+			.map(|(address, _, _)| *address == Address::Id(Self::account_id_from_signer(signer)))
 			.unwrap_or(false)
 	}
 
@@ -67,6 +67,10 @@ impl ChainWithTransactions for Statemine {
 	type AccountKeyPair = sp_core::sr25519::Pair;
 	type SignedTransaction = runtime::UncheckedExtrinsic;
 
+	fn account_id_from_signer(signer: &Self::AccountKeyPair) -> Self::AccountId {
+		sp_runtime::MultiSigner::from(signer.public()).into_account()
+	}
+
 	fn sign_transaction(
 		param: SignParam<Self>,
 		unsigned: UnsignedTransaction<Self>,
@@ -84,12 +88,12 @@ impl ChainWithTransactions for Statemine {
 		)
 		.expect("SignedExtension never fails.");
 		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
-		let signer: sp_runtime::MultiSigner = param.signer.public().into();
+		let signer_account_id = Self::account_id_from_signer(&param.signer);
 		let (call, extra, _) = raw_payload.deconstruct();
 
 		Ok(runtime::UncheckedExtrinsic::new_signed(
 			call,
-			signer.into_account().into(),
+			signer_account_id.into(),
 			signature.into(),
 			extra,
 		))
@@ -102,7 +106,9 @@ impl ChainWithTransactions for Statemine {
 	fn is_signed_by(signer: &Self::AccountKeyPair, tx: &Self::SignedTransaction) -> bool {
 		tx.signature
 			.as_ref()
-			.map(|(address, _, _)| *address == bp_statemine::Address::Id(signer.public().into()))
+			.map(|(address, _, _)| {
+				*address == bp_statemine::Address::Id(Self::account_id_from_signer(signer))
+			})
 			.unwrap_or(false)
 	}
 
@@ -33,6 +33,20 @@ use sp_runtime::{
 };
 use std::{fmt::Debug, time::Duration};
 
+/// Derive an Ethereum-style 20-byte account id from an uncompressed ECDSA public key, as used by
+/// EVM-compatible Substrate chains (e.g. Moonbeam-style runtimes built on the `account` crate):
+/// `keccak256(uncompressed_pubkey[1..])[12..]`.
+pub fn ecdsa_public_to_eth_account_id(public: &sp_core::ecdsa::Public) -> [u8; 20] {
+	let uncompressed = secp256k1::PublicKey::from_slice(public.as_ref())
+		.expect("`sp_core::ecdsa::Public` is a valid compressed secp256k1 public key; qed")
+		.serialize_uncompressed();
+
+	let hash = sp_io::hashing::keccak_256(&uncompressed[1..]);
+	let mut account_id = [0u8; 20];
+	account_id.copy_from_slice(&hash[12..]);
+	account_id
+}
+
 /// Substrate-based chain from minimal relay-client point of view.
 pub trait Chain: ChainBase + Clone {
 	/// Chain id.
@@ -204,6 +218,16 @@ pub trait ChainWithTransactions: Chain {
 	/// Signed transaction.
 	type SignedTransaction: Clone + Debug + Codec + Send + 'static;
 
+	/// Derive this chain's `AccountId` from the public key of `Self::AccountKeyPair`.
+	///
+	/// sr25519/ed25519-based chains typically go through [`sp_runtime::MultiSigner`], which
+	/// hashes the public key into a 32-byte `AccountId32`. EVM-compatible chains built on the
+	/// `account` crate (e.g. Moonbeam-style runtimes) instead use `ecdsa::Pair` and derive a
+	/// 20-byte `H160` account id - see [`ecdsa_public_to_eth_account_id`]. Implementing this hook
+	/// lets `sign_transaction`, `is_signed_by` and `parse_transaction` stay generic over both
+	/// account schemes instead of assuming sr25519 + `AccountId32`.
+	fn account_id_from_signer(signer: &Self::AccountKeyPair) -> Self::AccountId;
+
 	/// Create transaction for given runtime call, signed by given account.
 	fn sign_transaction(
 		param: SignParam<Self>,
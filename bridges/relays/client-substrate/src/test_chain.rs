@@ -130,6 +130,10 @@ impl ChainWithTransactions for TestChain {
 		)>,
 	>;
 
+	fn account_id_from_signer(_signer: &Self::AccountKeyPair) -> Self::AccountId {
+		unreachable!()
+	}
+
 	fn sign_transaction(
 		_param: SignParam<Self>,
 		_unsigned: UnsignedTransaction<Self>,
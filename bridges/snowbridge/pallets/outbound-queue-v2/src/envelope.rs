@@ -28,6 +28,12 @@ pub struct DeliveryProofEnvelope {
 pub enum EnvelopeDecodeError {
 	DecodeLogFailed,
 	DecodeAccountFailed,
+	/// The logs did not all originate from the same gateway address.
+	GatewayMismatch,
+	/// The decoded nonces were not contiguous, i.e. a delivery was skipped.
+	NonceGap,
+	/// The same nonce was delivered more than once in the batch.
+	DuplicateNonce,
 }
 
 impl TryFrom<&Log> for DeliveryProofEnvelope {
@@ -49,3 +55,35 @@ impl TryFrom<&Log> for DeliveryProofEnvelope {
 		})
 	}
 }
+
+/// Decode a receipt containing multiple `InboundMessageDispatched` event logs, one per
+/// delivered nonce, into a contiguous, nonce-ordered batch of [`DeliveryProofEnvelope`]s.
+///
+/// Logs that don't decode as `InboundMessageDispatched` are skipped. All decoded envelopes must
+/// share the same `gateway` and their `nonce`s must form a contiguous range with no gaps or
+/// duplicates, so the bridge can acknowledge the whole range in one relayer submission.
+pub fn decode_batch(logs: &[Log]) -> Result<Vec<DeliveryProofEnvelope>, EnvelopeDecodeError> {
+	let mut envelopes: Vec<DeliveryProofEnvelope> =
+		logs.iter().filter_map(|log| DeliveryProofEnvelope::try_from(log).ok()).collect();
+
+	if envelopes.is_empty() {
+		return Ok(envelopes);
+	}
+
+	envelopes.sort_by_key(|envelope| envelope.nonce);
+
+	let gateway = envelopes[0].gateway;
+	if envelopes.iter().any(|envelope| envelope.gateway != gateway) {
+		return Err(EnvelopeDecodeError::GatewayMismatch);
+	}
+
+	for window in envelopes.windows(2) {
+		match window[1].nonce.checked_sub(window[0].nonce) {
+			Some(0) => return Err(EnvelopeDecodeError::DuplicateNonce),
+			Some(1) => (),
+			_ => return Err(EnvelopeDecodeError::NonceGap),
+		}
+	}
+
+	Ok(envelopes)
+}
@@ -6,6 +6,7 @@ use frame_support::traits::tokens::Balance as BalanceT;
 use snowbridge_core::PricingParameters;
 use snowbridge_merkle_tree::MerkleProof;
 use snowbridge_outbound_queue_primitives::v1::{Command, Fee};
+use sp_core::H256;
 
 sp_api::decl_runtime_apis! {
 	pub trait OutboundQueueApi<Balance> where Balance: BalanceT
@@ -15,6 +16,10 @@ sp_api::decl_runtime_apis! {
 		/// `sp_runtime::generic::DigestItem::Other`
 		fn prove_message(leaf_index: u64) -> Option<MerkleProof>;
 
+		/// The root of the merkle tree of messages committed so far this block, matching the
+		/// root that `prove_message`'s proof currently verifies against.
+		fn messages_root() -> H256;
+
 		/// Calculate the delivery fee for `command`
 		fn calculate_fee(command: Command, parameters: Option<PricingParameters<Balance>>) -> Fee<Balance>;
 	}
@@ -5,9 +5,9 @@
 use crate::{Config, MessageLeaves};
 use frame_support::storage::StorageStreamIter;
 use snowbridge_core::PricingParameters;
-use snowbridge_merkle_tree::{merkle_proof, MerkleProof};
+use snowbridge_merkle_tree::{merkle_proof, merkle_root, MerkleProof};
 use snowbridge_outbound_queue_primitives::v1::{Command, Fee, GasMeter};
-use sp_core::Get;
+use sp_core::{Get, H256};
 
 pub fn prove_message<T>(leaf_index: u64) -> Option<MerkleProof>
 where
@@ -21,6 +21,19 @@ where
 	Some(proof)
 }
 
+/// The root of the merkle tree of messages committed so far this block, i.e. the root that
+/// `prove_message`'s proof would currently verify against. Returns the zero hash if no messages
+/// have been committed yet this block.
+pub fn messages_root<T>() -> H256
+where
+	T: Config,
+{
+	if !MessageLeaves::<T>::exists() {
+		return H256::default()
+	}
+	merkle_root::<<T as Config>::Hashing, _>(MessageLeaves::<T>::stream_iter())
+}
+
 pub fn calculate_fee<T>(
 	command: Command,
 	parameters: Option<PricingParameters<T::Balance>>,
@@ -6,6 +6,7 @@ use frame_support::{
 	parameter_types,
 	traits::{Everything, Hooks},
 	weights::IdentityFee,
+	BoundedVec,
 };
 
 use snowbridge_core::{
@@ -179,11 +180,12 @@ pub fn mock_message(sibling_para_id: u32) -> Message {
 		channel_id: ParaId::from(sibling_para_id).into(),
 		command: Command::AgentExecute {
 			agent_id: Default::default(),
-			command: AgentExecuteCommand::TransferToken {
+			command: BoundedVec::try_from(vec![AgentExecuteCommand::TransferToken {
 				token: Default::default(),
 				recipient: Default::default(),
 				amount: 0,
-			},
+			}])
+			.expect("a single command always fits within the bound"),
 		},
 	}
 }
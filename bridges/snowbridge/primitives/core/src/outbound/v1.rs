@@ -9,12 +9,16 @@ use crate::{
 };
 use codec::{Decode, Encode};
 use ethabi::Token;
-use frame_support::PalletError;
+use frame_support::{pallet_prelude::ConstU32, BoundedVec, PalletError};
 use scale_info::TypeInfo;
 use sp_arithmetic::traits::{BaseArithmetic, Unsigned};
 use sp_core::{RuntimeDebug, H160, H256, U256};
 use sp_std::{borrow::ToOwned, vec, vec::Vec};
 
+/// The maximum number of sub-commands that can be executed within a single agent, e.g. as part
+/// of a batched token unlock.
+pub const MAX_AGENT_EXECUTE_COMMANDS: u32 = 8;
+
 /// Enqueued outbound messages need to be versioned to prevent data corruption
 /// or loss after forkless runtime upgrades
 #[derive(Encode, Decode, TypeInfo, Clone, RuntimeDebug)]
@@ -64,8 +68,8 @@ pub enum Command {
 	AgentExecute {
 		/// The ID of the agent
 		agent_id: H256,
-		/// The sub-command to be executed
-		command: AgentExecuteCommand,
+		/// The ordered list of sub-commands to execute within the agent
+		command: BoundedVec<AgentExecuteCommand, ConstU32<MAX_AGENT_EXECUTE_COMMANDS>>,
 	},
 	/// Upgrade the Gateway contract
 	Upgrade {
@@ -187,7 +191,7 @@ impl Command {
 		match self {
 			Command::AgentExecute { agent_id, command } => ethabi::encode(&[Token::Tuple(vec![
 				Token::FixedBytes(agent_id.as_bytes().to_owned()),
-				Token::Bytes(command.abi_encode()),
+				Token::Array(command.iter().map(|c| Token::Bytes(c.abi_encode())).collect()),
 			])]),
 			Command::Upgrade { impl_address, impl_code_hash, initializer, .. } =>
 				ethabi::encode(&[Token::Tuple(vec![
@@ -414,15 +418,20 @@ impl GasMeter for ConstantGasMeter {
 			Command::UpdateChannel { .. } => 50_000,
 			Command::TransferNativeFromAgent { .. } => 60_000,
 			Command::SetOperatingMode { .. } => 40_000,
-			Command::AgentExecute { command, .. } => match command {
-				// Execute IERC20.transferFrom
-				//
-				// Worst-case assumptions are important:
-				// * No gas refund for clearing storage slot of source account in ERC20 contract
-				// * Assume dest account in ERC20 contract does not yet have a storage slot
-				// * ERC20.transferFrom possibly does other business logic besides updating balances
-				AgentExecuteCommand::TransferToken { .. } => 100_000,
-			},
+			Command::AgentExecute { command, .. } => command
+				.iter()
+				.map(|sub_command| match sub_command {
+					// Execute IERC20.transferFrom
+					//
+					// Worst-case assumptions are important:
+					// * No gas refund for clearing storage slot of source account in ERC20
+					//   contract
+					// * Assume dest account in ERC20 contract does not yet have a storage slot
+					// * ERC20.transferFrom possibly does other business logic besides updating
+					//   balances
+					AgentExecuteCommand::TransferToken { .. } => 100_000,
+				})
+				.sum(),
 			Command::Upgrade { initializer, .. } => {
 				let initializer_max_gas = match *initializer {
 					Some(Initializer { maximum_required_gas, .. }) => maximum_required_gas,
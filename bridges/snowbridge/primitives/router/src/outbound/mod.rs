@@ -9,30 +9,58 @@ use core::slice::Iter;
 
 use codec::{Decode, Encode};
 
-use frame_support::{ensure, traits::Get};
+use frame_support::{ensure, pallet_prelude::ConstU32, traits::Get, BoundedVec};
 use snowbridge_core::{
-	outbound::{AgentExecuteCommand, Command, Message, SendMessage},
-	ChannelId, ParaId,
+	outbound::{AgentExecuteCommand, Command, Message, SendMessage, MAX_AGENT_EXECUTE_COMMANDS},
+	ChannelId, ParaId, TokenIdOf,
 };
-use sp_core::{H160, H256};
+use sp_core::{RuntimeDebug, H160, H256};
 use sp_std::{iter::Peekable, marker::PhantomData, prelude::*};
 use xcm::prelude::*;
 use xcm_executor::traits::{ConvertLocation, ExportXcm};
 
+/// Fixed per-command-kind fee surcharges added on top of `OutboundQueue`'s dynamically computed
+/// fee, so operators can pin a predictable minimum delivery cost regardless of queue pricing
+/// fluctuations. Set every field to zero to preserve the previous, queue-only pricing behaviour.
+#[derive(Clone, Copy, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct CommandFees {
+	/// Surcharge for a `Command::AgentExecute` (token transfer) message.
+	pub transfer: u128,
+	/// Surcharge for a `Command::RegisterForeignToken` message.
+	pub register: u128,
+}
+
 pub struct EthereumBlobExporter<
 	UniversalLocation,
 	EthereumNetwork,
 	OutboundQueue,
 	AgentHashedDescription,
->(PhantomData<(UniversalLocation, EthereumNetwork, OutboundQueue, AgentHashedDescription)>);
-
-impl<UniversalLocation, EthereumNetwork, OutboundQueue, AgentHashedDescription> ExportXcm
-	for EthereumBlobExporter<UniversalLocation, EthereumNetwork, OutboundQueue, AgentHashedDescription>
+	CommandFee,
+>(
+	PhantomData<(
+		UniversalLocation,
+		EthereumNetwork,
+		OutboundQueue,
+		AgentHashedDescription,
+		CommandFee,
+	)>,
+);
+
+impl<UniversalLocation, EthereumNetwork, OutboundQueue, AgentHashedDescription, CommandFee>
+	ExportXcm
+	for EthereumBlobExporter<
+		UniversalLocation,
+		EthereumNetwork,
+		OutboundQueue,
+		AgentHashedDescription,
+		CommandFee,
+	>
 where
 	UniversalLocation: Get<InteriorLocation>,
 	EthereumNetwork: Get<NetworkId>,
 	OutboundQueue: SendMessage<Balance = u128>,
 	AgentHashedDescription: ConvertLocation<H256>,
+	CommandFee: Get<CommandFees>,
 {
 	type Ticket = (Vec<u8>, XcmHash);
 
@@ -87,12 +115,6 @@ where
 			SendError::MissingArgument
 		})?;
 
-		let mut converter = XcmConverter::new(&message, &expected_network);
-		let (agent_execute_command, message_id) = converter.convert().map_err(|err|{
-			log::error!(target: "xcm::ethereum_blob_exporter", "unroutable due to pattern matching error '{err:?}'.");
-			SendError::Unroutable
-		})?;
-
 		let source_location = Location::new(1, local_sub.clone());
 		let agent_id = match AgentHashedDescription::convert_location(&source_location) {
 			Some(id) => id,
@@ -102,22 +124,32 @@ where
 			},
 		};
 
+		let mut converter = XcmConverter::new(&message, &expected_network, agent_id);
+		let (command, message_id) = converter.convert().map_err(|err|{
+			log::error!(target: "xcm::ethereum_blob_exporter", "unroutable due to pattern matching error '{err:?}'.");
+			SendError::Unroutable
+		})?;
+
 		let channel_id: ChannelId = ParaId::from(para_id).into();
 
-		let outbound_message = Message {
-			id: Some(message_id.into()),
-			channel_id,
-			command: Command::AgentExecute { agent_id, command: agent_execute_command },
+		// the fixed surcharge to add on top of the queue's dynamic fee, keyed by command kind
+		let command_surcharge = match command {
+			Command::AgentExecute { .. } => CommandFee::get().transfer,
+			Command::RegisterForeignToken { .. } => CommandFee::get().register,
+			_ => 0,
 		};
 
+		let outbound_message = Message { id: Some(message_id.into()), channel_id, command };
+
 		// validate the message
 		let (ticket, fee) = OutboundQueue::validate(&outbound_message).map_err(|err| {
 			log::error!(target: "xcm::ethereum_blob_exporter", "OutboundQueue validation of message failed. {err:?}");
 			SendError::Unroutable
 		})?;
 
-		// convert fee to Asset
-		let fee = Asset::from((Location::parent(), fee.total())).into();
+		// convert fee to Asset, adding the fixed per-command surcharge
+		let fee = Asset::from((Location::parent(), fee.total().saturating_add(command_surcharge)))
+			.into();
 
 		Ok(((ticket.encode(), message_id), fee))
 	}
@@ -154,8 +186,14 @@ enum XcmConverterError {
 	AssetResolutionFailed,
 	InvalidFeeAsset,
 	SetTopicExpected,
+	ReserveAssetDepositedExpected,
+	InvalidRegistrationMetadata,
+	UnexpectedInstruction,
 }
 
+/// An ordered batch of transfer commands to execute within a single agent.
+type AgentExecuteCommands = BoundedVec<AgentExecuteCommand, ConstU32<MAX_AGENT_EXECUTE_COMMANDS>>;
+
 macro_rules! match_expression {
 	($expression:expr, $(|)? $( $pattern:pat_param )|+ $( if $guard: expr )?, $value:expr $(,)?) => {
 		match $expression {
@@ -168,15 +206,22 @@ macro_rules! match_expression {
 struct XcmConverter<'a, Call> {
 	iter: Peekable<Iter<'a, Instruction<Call>>>,
 	ethereum_network: &'a NetworkId,
+	agent_id: H256,
 }
 impl<'a, Call> XcmConverter<'a, Call> {
-	fn new(message: &'a Xcm<Call>, ethereum_network: &'a NetworkId) -> Self {
-		Self { iter: message.inner().iter().peekable(), ethereum_network }
+	fn new(message: &'a Xcm<Call>, ethereum_network: &'a NetworkId, agent_id: H256) -> Self {
+		Self { iter: message.inner().iter().peekable(), ethereum_network, agent_id }
 	}
 
-	fn convert(&mut self) -> Result<(AgentExecuteCommand, [u8; 32]), XcmConverterError> {
-		// Get withdraw/deposit and make native tokens create message.
-		let result = self.native_tokens_unlock_message()?;
+	fn convert(&mut self) -> Result<(Command, [u8; 32]), XcmConverterError> {
+		let result = match self.peek() {
+			// A Polkadot-native asset being registered as a mirrored ERC-20 on the Gateway.
+			Ok(ReserveAssetDeposited { .. }) => self.register_token_message(),
+			// Get withdraw/deposit and make native tokens create message.
+			Ok(WithdrawAsset { .. }) => self.native_tokens_unlock_message(),
+			Err(e) => Err(e),
+			_ => Err(XcmConverterError::UnexpectedInstruction),
+		}?;
 
 		// All xcm instructions must be consumed before exit.
 		if self.next().is_ok() {
@@ -188,7 +233,7 @@ impl<'a, Call> XcmConverter<'a, Call> {
 
 	fn native_tokens_unlock_message(
 		&mut self,
-	) -> Result<(AgentExecuteCommand, [u8; 32]), XcmConverterError> {
+	) -> Result<(Command, [u8; 32]), XcmConverterError> {
 		use XcmConverterError::*;
 
 		// Get the reserve assets from WithdrawAsset.
@@ -233,36 +278,105 @@ impl<'a, Call> XcmConverter<'a, Call> {
 			return Err(FilterDoesNotConsumeAllAssets)
 		}
 
-		// We only support a single asset at a time.
-		ensure!(reserve_assets.len() == 1, TooManyAssets);
-		let reserve_asset = reserve_assets.get(0).ok_or(AssetResolutionFailed)?;
-
-		// If there was a fee specified verify it.
+		// If there was a fee specified, verify that it is covered by one of the reserve assets.
 		if let Some(fee_asset) = fee_asset {
-			// The fee asset must be the same as the reserve asset.
-			if fee_asset.id != reserve_asset.id || fee_asset.fun > reserve_asset.fun {
+			let covered = reserve_assets
+				.inner()
+				.iter()
+				.any(|asset| fee_asset.id == asset.id && fee_asset.fun <= asset.fun);
+			if !covered {
 				return Err(InvalidFeeAsset)
 			}
 		}
 
-		let (token, amount) = match reserve_asset {
-			Asset { id: AssetId(inner_location), fun: Fungible(amount) } =>
-				match inner_location.unpack() {
-					(0, [AccountKey20 { network, key }]) if self.network_matches(network) =>
-						Some((H160(*key), *amount)),
-					_ => None,
-				},
-			_ => None,
-		}
-		.ok_or(AssetResolutionFailed)?;
+		// Resolve every reserve asset into a transfer command, in order.
+		let mut commands = Vec::with_capacity(reserve_assets.len());
+		for reserve_asset in reserve_assets.inner() {
+			let (token, amount) = match reserve_asset {
+				Asset { id: AssetId(inner_location), fun: Fungible(amount) } =>
+					match inner_location.unpack() {
+						(0, [AccountKey20 { network, key }]) if self.network_matches(network) =>
+							Some((H160(*key), *amount)),
+						_ => None,
+					},
+				_ => None,
+			}
+			.ok_or(AssetResolutionFailed)?;
 
-		// transfer amount must be greater than 0.
-		ensure!(amount > 0, ZeroAssetTransfer);
+			// transfer amount must be greater than 0.
+			ensure!(amount > 0, ZeroAssetTransfer);
+
+			commands.push(AgentExecuteCommand::TransferToken { token, recipient, amount });
+		}
 
 		// Check if there is a SetTopic and skip over it if found.
 		let topic_id = match_expression!(self.next()?, SetTopic(id), id).ok_or(SetTopicExpected)?;
 
-		Ok((AgentExecuteCommand::TransferToken { token, recipient, amount }, *topic_id))
+		let commands: AgentExecuteCommands =
+			BoundedVec::try_from(commands).map_err(|_| TooManyAssets)?;
+
+		Ok((Command::AgentExecute { agent_id: self.agent_id, command: commands }, *topic_id))
+	}
+
+	/// Convert the xcm for registering a Polkadot-native asset as a mirrored ERC-20 on the
+	/// Gateway. To match this, we expect an input of the form:
+	/// # ReserveAssetDeposited
+	/// # ClearOrigin (optional)
+	/// # SetTopic
+	///
+	/// The token key is derived deterministically from the asset's `Location` in the same way
+	/// an agent ID is derived from a consensus system's location. Name/symbol/decimals are
+	/// optional and, when present, are SCALE-encoded as `(name, symbol, decimals)` inside a
+	/// trailing `GeneralKey` junction of that `Location`; that junction is not part of the
+	/// location the token key is derived from.
+	fn register_token_message(&mut self) -> Result<(Command, [u8; 32]), XcmConverterError> {
+		use XcmConverterError::*;
+
+		// Get the reserve asset identifying the token to register.
+		let reserve_assets =
+			match_expression!(self.next()?, ReserveAssetDeposited(reserve_assets), reserve_assets)
+				.ok_or(ReserveAssetDepositedExpected)?;
+
+		// Check if clear origin exists and skip over it.
+		if match_expression!(self.peek(), Ok(ClearOrigin), ()).is_some() {
+			let _ = self.next();
+		}
+
+		// We only support registering a single asset at a time.
+		if reserve_assets.len() == 0 {
+			return Err(NoReserveAssets)
+		}
+		ensure!(reserve_assets.len() == 1, TooManyAssets);
+		let reserve_asset = reserve_assets.get(0).ok_or(AssetResolutionFailed)?;
+
+		let location = match reserve_asset {
+			Asset { id: AssetId(location), fun: Fungible(_) } => location,
+			_ => return Err(AssetResolutionFailed),
+		};
+
+		// Split off the trailing metadata junction, if any, before deriving the token key.
+		let mut token_location = location.clone();
+		let metadata_key = match token_location.last() {
+			Some(GeneralKey { .. }) => token_location.take_last(),
+			_ => None,
+		};
+
+		let token_id =
+			TokenIdOf::convert_location(&token_location).ok_or(AssetResolutionFailed)?;
+
+		let metadata = match metadata_key {
+			Some(GeneralKey { length, data }) =>
+				Some(<(Vec<u8>, Vec<u8>, u8)>::decode(&mut &data[..length as usize]))
+					.transpose()
+					.map_err(|_| InvalidRegistrationMetadata)?,
+			_ => None,
+		};
+		let (name, symbol, decimals) = metadata.unwrap_or_default();
+
+		// Get the message ID from the SetTopic, which is required.
+		let topic_id = match_expression!(self.next()?, SetTopic(id), id).ok_or(SetTopicExpected)?;
+
+		Ok((Command::RegisterForeignToken { token_id, name, symbol, decimals }, *topic_id))
 	}
 
 	fn next(&mut self) -> Result<&'a Instruction<Call>, XcmConverterError> {
@@ -14,6 +14,8 @@ parameter_types! {
 	UniversalLocation: InteriorLocation = [GlobalConsensus(RelayNetwork::get()), Parachain(1013)].into();
 	const BridgedNetwork: NetworkId =  Ethereum{ chain_id: 1 };
 	const NonBridgedNetwork: NetworkId =  Ethereum{ chain_id: 2 };
+	const NoCommandFees: CommandFees = CommandFees { transfer: 0, register: 0 };
+	const CommandFeesWithSurcharge: CommandFees = CommandFees { transfer: 10, register: 20 };
 }
 
 struct MockOkOutboundQueue;
@@ -70,6 +72,7 @@ fn exporter_validate_with_unknown_network_yields_not_applicable() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -89,6 +92,7 @@ fn exporter_validate_with_invalid_destination_yields_missing_argument() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -111,6 +115,7 @@ fn exporter_validate_with_x8_destination_yields_not_applicable() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -130,6 +135,7 @@ fn exporter_validate_without_universal_source_yields_missing_argument() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -149,6 +155,7 @@ fn exporter_validate_without_global_universal_location_yields_unroutable() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -168,6 +175,7 @@ fn exporter_validate_without_global_bridge_location_yields_not_applicable() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -188,6 +196,7 @@ fn exporter_validate_with_remote_universal_source_yields_not_applicable() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -207,6 +216,7 @@ fn exporter_validate_without_para_id_in_source_yields_missing_argument() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -227,6 +237,7 @@ fn exporter_validate_complex_para_id_in_source_yields_missing_argument() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -247,6 +258,7 @@ fn exporter_validate_without_xcm_message_yields_missing_argument() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -294,6 +306,7 @@ fn exporter_validate_with_max_target_fee_yields_unroutable() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -321,6 +334,7 @@ fn exporter_validate_with_unparsable_xcm_yields_unroutable() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -367,6 +381,7 @@ fn exporter_validate_xcm_success_case_1() {
 		BridgedNetwork,
 		MockOkOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::validate(
 		network, channel, &mut universal_source, &mut destination, &mut message
 	);
@@ -374,6 +389,54 @@ fn exporter_validate_xcm_success_case_1() {
 	assert!(result.is_ok());
 }
 
+#[test]
+fn exporter_validate_adds_command_fee_surcharge_for_transfer() {
+	let network = BridgedNetwork::get();
+	let mut destination: Option<InteriorLocation> = Here.into();
+
+	let mut universal_source: Option<InteriorLocation> =
+		Some([GlobalConsensus(Polkadot), Parachain(1000)].into());
+
+	let token_address: [u8; 20] = hex!("1000000000000000000000000000000000000000");
+	let beneficiary_address: [u8; 20] = hex!("2000000000000000000000000000000000000000");
+
+	let channel: u32 = 0;
+	let assets: Assets = vec![Asset {
+		id: AssetId([AccountKey20 { network: None, key: token_address }].into()),
+		fun: Fungible(1000),
+	}]
+	.into();
+	let fee = assets.clone().get(0).unwrap().clone();
+	let filter: AssetFilter = assets.clone().into();
+
+	let mut message: Option<Xcm<()>> = Some(
+		vec![
+			WithdrawAsset(assets.clone()),
+			ClearOrigin,
+			BuyExecution { fees: fee, weight_limit: Unlimited },
+			DepositAsset {
+				assets: filter,
+				beneficiary: AccountKey20 { network: None, key: beneficiary_address }.into(),
+			},
+			SetTopic([0; 32]),
+		]
+		.into(),
+	);
+
+	let (_, fee) = EthereumBlobExporter::<
+		UniversalLocation,
+		BridgedNetwork,
+		MockOkOutboundQueue,
+		AgentIdOf,
+		CommandFeesWithSurcharge,
+	>::validate(network, channel, &mut universal_source, &mut destination, &mut message)
+	.unwrap();
+
+	// MockOkOutboundQueue's dynamic fee (2) plus the transfer surcharge (10).
+	let expected_fee: Assets = Asset::from((Location::parent(), 12u128)).into();
+	assert_eq!(fee, expected_fee);
+}
+
 #[test]
 fn exporter_deliver_with_submit_failure_yields_unroutable() {
 	let result = EthereumBlobExporter::<
@@ -381,6 +444,7 @@ fn exporter_deliver_with_submit_failure_yields_unroutable() {
 		BridgedNetwork,
 		MockErrOutboundQueue,
 		AgentIdOf,
+		NoCommandFees,
 	>::deliver((hex!("deadbeef").to_vec(), XcmHash::default()));
 	assert_eq!(result, Err(XcmSendError::Transport("other transport error")))
 }
@@ -410,12 +474,15 @@ fn xcm_converter_convert_success() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
-	let expected_payload = AgentExecuteCommand::TransferToken {
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
+	let expected_command = BoundedVec::try_from(vec![AgentExecuteCommand::TransferToken {
 		token: token_address.into(),
 		recipient: beneficiary_address.into(),
 		amount: 1000,
-	};
+	}])
+	.unwrap();
+	let expected_payload =
+		Command::AgentExecute { agent_id: H256::default(), command: expected_command };
 	let result = converter.convert();
 	assert_eq!(result, Ok((expected_payload, [0; 32])));
 }
@@ -443,12 +510,15 @@ fn xcm_converter_convert_without_buy_execution_yields_success() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
-	let expected_payload = AgentExecuteCommand::TransferToken {
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
+	let expected_command = BoundedVec::try_from(vec![AgentExecuteCommand::TransferToken {
 		token: token_address.into(),
 		recipient: beneficiary_address.into(),
 		amount: 1000,
-	};
+	}])
+	.unwrap();
+	let expected_payload =
+		Command::AgentExecute { agent_id: H256::default(), command: expected_command };
 	let result = converter.convert();
 	assert_eq!(result, Ok((expected_payload, [0; 32])));
 }
@@ -478,12 +548,15 @@ fn xcm_converter_convert_with_wildcard_all_asset_filter_succeeds() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
-	let expected_payload = AgentExecuteCommand::TransferToken {
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
+	let expected_command = BoundedVec::try_from(vec![AgentExecuteCommand::TransferToken {
 		token: token_address.into(),
 		recipient: beneficiary_address.into(),
 		amount: 1000,
-	};
+	}])
+	.unwrap();
+	let expected_payload =
+		Command::AgentExecute { agent_id: H256::default(), command: expected_command };
 	let result = converter.convert();
 	assert_eq!(result, Ok((expected_payload, [0; 32])));
 }
@@ -513,12 +586,15 @@ fn xcm_converter_convert_with_fees_less_than_reserve_yields_success() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
-	let expected_payload = AgentExecuteCommand::TransferToken {
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
+	let expected_command = BoundedVec::try_from(vec![AgentExecuteCommand::TransferToken {
 		token: token_address.into(),
 		recipient: beneficiary_address.into(),
 		amount: 1000,
-	};
+	}])
+	.unwrap();
+	let expected_payload =
+		Command::AgentExecute { agent_id: H256::default(), command: expected_command };
 	let result = converter.convert();
 	assert_eq!(result, Ok((expected_payload, [0; 32])));
 }
@@ -547,7 +623,7 @@ fn xcm_converter_convert_without_set_topic_yields_set_topic_expected() {
 		ClearTopic,
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::SetTopicExpected));
 }
@@ -564,7 +640,7 @@ fn xcm_converter_convert_with_partial_message_yields_unexpected_end_of_xcm() {
 	.into();
 	let message: Xcm<()> = vec![WithdrawAsset(assets)].into();
 
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::UnexpectedEndOfXcm));
 }
@@ -595,7 +671,7 @@ fn xcm_converter_with_different_fee_asset_fails() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::InvalidFeeAsset));
 }
@@ -625,7 +701,7 @@ fn xcm_converter_with_fees_greater_than_reserve_fails() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::InvalidFeeAsset));
 }
@@ -636,7 +712,7 @@ fn xcm_converter_convert_with_empty_xcm_yields_unexpected_end_of_xcm() {
 
 	let message: Xcm<()> = vec![].into();
 
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::UnexpectedEndOfXcm));
@@ -668,7 +744,7 @@ fn xcm_converter_convert_with_extra_instructions_yields_end_of_xcm_message_expec
 		ClearError,
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::EndOfXcmMessageExpected));
@@ -698,7 +774,7 @@ fn xcm_converter_convert_without_withdraw_asset_yields_withdraw_expected() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::WithdrawAssetExpected));
@@ -723,7 +799,7 @@ fn xcm_converter_convert_without_withdraw_asset_yields_deposit_expected() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::DepositAssetExpected));
@@ -756,14 +832,14 @@ fn xcm_converter_convert_without_assets_yields_no_reserve_assets() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::NoReserveAssets));
 }
 
 #[test]
-fn xcm_converter_convert_with_two_assets_yields_too_many_assets() {
+fn xcm_converter_convert_with_two_assets_yields_batched_transfer_commands() {
 	let network = BridgedNetwork::get();
 
 	let token_address_1: [u8; 20] = hex!("1000000000000000000000000000000000000000");
@@ -794,12 +870,152 @@ fn xcm_converter_convert_with_two_assets_yields_too_many_assets() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
+
+	let expected_command = BoundedVec::try_from(vec![
+		AgentExecuteCommand::TransferToken {
+			token: token_address_1.into(),
+			recipient: beneficiary_address.into(),
+			amount: 1000,
+		},
+		AgentExecuteCommand::TransferToken {
+			token: token_address_2.into(),
+			recipient: beneficiary_address.into(),
+			amount: 500,
+		},
+	])
+	.unwrap();
+	let expected_payload =
+		Command::AgentExecute { agent_id: H256::default(), command: expected_command };
+	let result = converter.convert();
+	assert_eq!(result, Ok((expected_payload, [0; 32])));
+}
+
+#[test]
+fn xcm_converter_convert_with_too_many_assets_yields_too_many_assets() {
+	let network = BridgedNetwork::get();
+
+	let beneficiary_address: [u8; 20] = hex!("2000000000000000000000000000000000000000");
+
+	let assets: Assets = (0..=MAX_AGENT_EXECUTE_COMMANDS as u8)
+		.map(|i| Asset {
+			id: AssetId(AccountKey20 { network: None, key: [i; 20] }.into()),
+			fun: Fungible(1000),
+		})
+		.collect::<Vec<_>>()
+		.into();
+	let filter: AssetFilter = assets.clone().into();
+
+	let message: Xcm<()> = vec![
+		WithdrawAsset(assets.clone()),
+		DepositAsset {
+			assets: filter,
+			beneficiary: AccountKey20 { network: None, key: beneficiary_address }.into(),
+		},
+		SetTopic([0; 32]),
+	]
+	.into();
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::TooManyAssets));
 }
 
+#[test]
+fn xcm_converter_convert_register_token_without_metadata_succeeds() {
+	let network = BridgedNetwork::get();
+
+	let asset_location: Location =
+		[GlobalConsensus(Polkadot), Parachain(1000), GeneralIndex(0)].into();
+	let assets: Assets = vec![Asset { id: AssetId(asset_location.clone()), fun: Fungible(1) }].into();
+
+	let message: Xcm<()> =
+		vec![ReserveAssetDeposited(assets), ClearOrigin, SetTopic([0; 32])].into();
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
+
+	let token_id = TokenIdOf::convert_location(&asset_location).unwrap();
+	let expected_payload = Command::RegisterForeignToken {
+		token_id,
+		name: Vec::new(),
+		symbol: Vec::new(),
+		decimals: 0,
+	};
+	let result = converter.convert();
+	assert_eq!(result, Ok((expected_payload, [0; 32])));
+}
+
+#[test]
+fn xcm_converter_convert_register_token_with_metadata_succeeds() {
+	let network = BridgedNetwork::get();
+
+	let name = b"wrapped ether".to_vec();
+	let symbol = b"wETH".to_vec();
+	let decimals = 18u8;
+	let encoded = (name.clone(), symbol.clone(), decimals).encode();
+
+	let asset_location: Location = [
+		GlobalConsensus(Polkadot),
+		Parachain(1000),
+		GeneralIndex(0),
+		GeneralKey { length: encoded.len() as u8, data: zero_padded_32(&encoded) },
+	]
+	.into();
+	let assets: Assets = vec![Asset { id: AssetId(asset_location.clone()), fun: Fungible(1) }].into();
+
+	let message: Xcm<()> =
+		vec![ReserveAssetDeposited(assets), ClearOrigin, SetTopic([0; 32])].into();
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
+
+	// The trailing metadata junction is not part of the location the token key is derived from.
+	let token_location: Location =
+		[GlobalConsensus(Polkadot), Parachain(1000), GeneralIndex(0)].into();
+	let token_id = TokenIdOf::convert_location(&token_location).unwrap();
+	let expected_payload = Command::RegisterForeignToken { token_id, name, symbol, decimals };
+	let result = converter.convert();
+	assert_eq!(result, Ok((expected_payload, [0; 32])));
+}
+
+#[test]
+fn xcm_converter_convert_register_token_with_malformed_metadata_fails() {
+	let network = BridgedNetwork::get();
+
+	let asset_location: Location = [
+		GlobalConsensus(Polkadot),
+		Parachain(1000),
+		GeneralIndex(0),
+		GeneralKey { length: 1, data: zero_padded_32(&[0xff]) },
+	]
+	.into();
+	let assets: Assets = vec![Asset { id: AssetId(asset_location), fun: Fungible(1) }].into();
+
+	let message: Xcm<()> =
+		vec![ReserveAssetDeposited(assets), ClearOrigin, SetTopic([0; 32])].into();
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
+
+	let result = converter.convert();
+	assert_eq!(result.err(), Some(XcmConverterError::InvalidRegistrationMetadata));
+}
+
+#[test]
+fn xcm_converter_convert_register_token_without_reserve_assets_fails() {
+	let network = BridgedNetwork::get();
+
+	let assets: Assets = vec![].into();
+
+	let message: Xcm<()> =
+		vec![ReserveAssetDeposited(assets), ClearOrigin, SetTopic([0; 32])].into();
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
+
+	let result = converter.convert();
+	assert_eq!(result.err(), Some(XcmConverterError::NoReserveAssets));
+}
+
+fn zero_padded_32(data: &[u8]) -> [u8; 32] {
+	let mut padded = [0u8; 32];
+	padded[..data.len()].copy_from_slice(data);
+	padded
+}
+
 #[test]
 fn xcm_converter_convert_without_consuming_filter_yields_filter_does_not_consume_all_assets() {
 	let network = BridgedNetwork::get();
@@ -825,7 +1041,7 @@ fn xcm_converter_convert_without_consuming_filter_yields_filter_does_not_consume
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::FilterDoesNotConsumeAllAssets));
@@ -856,7 +1072,7 @@ fn xcm_converter_convert_with_zero_amount_asset_yields_zero_asset_transfer() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::ZeroAssetTransfer));
@@ -886,7 +1102,7 @@ fn xcm_converter_convert_non_ethereum_asset_yields_asset_resolution_failed() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::AssetResolutionFailed));
@@ -919,7 +1135,7 @@ fn xcm_converter_convert_non_ethereum_chain_asset_yields_asset_resolution_failed
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::AssetResolutionFailed));
@@ -952,7 +1168,7 @@ fn xcm_converter_convert_non_ethereum_chain_yields_asset_resolution_failed() {
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::AssetResolutionFailed));
@@ -989,7 +1205,7 @@ fn xcm_converter_convert_with_non_ethereum_beneficiary_yields_beneficiary_resolu
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::BeneficiaryResolutionFailed));
@@ -1025,7 +1241,7 @@ fn xcm_converter_convert_with_non_ethereum_chain_beneficiary_yields_beneficiary_
 		SetTopic([0; 32]),
 	]
 	.into();
-	let mut converter = XcmConverter::new(&message, &network);
+	let mut converter = XcmConverter::new(&message, &network, H256::default());
 
 	let result = converter.convert();
 	assert_eq!(result.err(), Some(XcmConverterError::BeneficiaryResolutionFailed));
@@ -6,7 +6,7 @@ use core::slice::Iter;
 
 use codec::{Decode, Encode};
 
-use frame_support::{ensure, traits::Get};
+use frame_support::{ensure, traits::Get, BoundedVec};
 use snowbridge_core::{
 	outbound::v1::{AgentExecuteCommand, Command, Message, SendMessage},
 	AgentId, ChannelId, ParaId, TokenId, TokenIdOf,
@@ -296,7 +296,12 @@ where
 		Ok((
 			Command::AgentExecute {
 				agent_id: self.agent_id,
-				command: AgentExecuteCommand::TransferToken { token, recipient, amount },
+				command: BoundedVec::try_from(vec![AgentExecuteCommand::TransferToken {
+					token,
+					recipient,
+					amount,
+				}])
+				.expect("a single command always fits within the bound"),
 			},
 			*topic_id,
 		))
@@ -842,11 +847,12 @@ mod tests {
 			XcmConverter::<MockTokenIdConvert, ()>::new(&message, network, Default::default());
 		let expected_payload = Command::AgentExecute {
 			agent_id: Default::default(),
-			command: AgentExecuteCommand::TransferToken {
+			command: BoundedVec::try_from(vec![AgentExecuteCommand::TransferToken {
 				token: token_address.into(),
 				recipient: beneficiary_address.into(),
 				amount: 1000,
-			},
+			}])
+			.expect("a single command always fits within the bound"),
 		};
 		let result = converter.convert();
 		assert_eq!(result, Ok((expected_payload, [0; 32])));
@@ -879,11 +885,12 @@ mod tests {
 			XcmConverter::<MockTokenIdConvert, ()>::new(&message, network, Default::default());
 		let expected_payload = Command::AgentExecute {
 			agent_id: Default::default(),
-			command: AgentExecuteCommand::TransferToken {
+			command: BoundedVec::try_from(vec![AgentExecuteCommand::TransferToken {
 				token: token_address.into(),
 				recipient: beneficiary_address.into(),
 				amount: 1000,
-			},
+			}])
+			.expect("a single command always fits within the bound"),
 		};
 		let result = converter.convert();
 		assert_eq!(result, Ok((expected_payload, [0; 32])));
@@ -918,11 +925,12 @@ mod tests {
 			XcmConverter::<MockTokenIdConvert, ()>::new(&message, network, Default::default());
 		let expected_payload = Command::AgentExecute {
 			agent_id: Default::default(),
-			command: AgentExecuteCommand::TransferToken {
+			command: BoundedVec::try_from(vec![AgentExecuteCommand::TransferToken {
 				token: token_address.into(),
 				recipient: beneficiary_address.into(),
 				amount: 1000,
-			},
+			}])
+			.expect("a single command always fits within the bound"),
 		};
 		let result = converter.convert();
 		assert_eq!(result, Ok((expected_payload, [0; 32])));
@@ -958,11 +966,12 @@ mod tests {
 			XcmConverter::<MockTokenIdConvert, ()>::new(&message, network, Default::default());
 		let expected_payload = Command::AgentExecute {
 			agent_id: Default::default(),
-			command: AgentExecuteCommand::TransferToken {
+			command: BoundedVec::try_from(vec![AgentExecuteCommand::TransferToken {
 				token: token_address.into(),
 				recipient: beneficiary_address.into(),
 				amount: 1000,
-			},
+			}])
+			.expect("a single command always fits within the bound"),
 		};
 		let result = converter.convert();
 		assert_eq!(result, Ok((expected_payload, [0; 32])));
@@ -21,6 +21,8 @@ use crate::{
 	collators::{
 		check_validation_code_or_log,
 		slot_based::{
+			collation_cache::{CachedCollation, CollationCache},
+			inclusion_emulator::{self, ConstraintViolation},
 			relay_chain_data_cache::RelayChainDataCache,
 			slot_timer::{SlotInfo, SlotTimer},
 		},
@@ -40,8 +42,8 @@ use cumulus_primitives_core::{
 use cumulus_relay_chain_interface::RelayChainInterface;
 use futures::prelude::*;
 use polkadot_primitives::{
-	Block as RelayBlock, CoreIndex, Hash as RelayHash, Header as RelayHeader, Id as ParaId,
-	DEFAULT_CLAIM_QUEUE_OFFSET,
+	Block as RelayBlock, CoreIndex, CoreState, Hash as RelayHash, Header as RelayHeader,
+	Id as ParaId, DEFAULT_CLAIM_QUEUE_OFFSET,
 };
 use sc_client_api::{backend::AuxStore, BlockBackend, BlockOf, UsageProvider};
 use sc_consensus::BlockImport;
@@ -119,6 +121,10 @@ pub struct BuilderTaskParams<
 	/// The maximum percentage of the maximum PoV size that the collator can use.
 	/// It will be removed once https://github.com/paritytech/polkadot-sdk/issues/6020 is fixed.
 	pub max_pov_percentage: Option<u32>,
+	/// Upper bound on the number of sibling blocks this collator keeps around at the same
+	/// height before pruning the least useful one, to stop a restarting or misbehaving
+	/// collator from piling up competing forks in the backend.
+	pub level_limit: consensus_common::LevelLimit,
 }
 
 /// Run block-builder.
@@ -171,8 +177,21 @@ where
 			para_backend,
 			slot_offset,
 			max_pov_percentage,
+			level_limit,
 		} = params;
 
+		let mut level_monitor = match level_limit {
+			consensus_common::LevelLimit::None => None,
+			consensus_common::LevelLimit::Some(limit) =>
+				Some(consensus_common::LevelMonitor::new(limit, para_backend.clone())),
+			consensus_common::LevelLimit::Default => Some(consensus_common::LevelMonitor::new(
+				consensus_common::MAX_LEAVES_PER_LEVEL_SENSIBLE_DEFAULT,
+				para_backend.clone(),
+			)),
+		};
+
+		let mut collation_cache = CollationCache::<Block>::new();
+
 		let mut slot_timer = SlotTimer::new_with_offset(slot_offset, relay_chain_slot_duration);
 
 		let mut collator = {
@@ -286,6 +305,15 @@ where
 
 			let included_header_hash = included_header.hash();
 
+			// The included block is no longer "uninjected", and relay parents keep rolling
+			// forward, so trim the cache of collations we no longer have any use for before
+			// consulting it below.
+			collation_cache.evict_included(included_header_hash);
+			collation_cache.evict_outside_ancestry(
+				*relay_parent_header.number(),
+				crate::collators::PARENT_SEARCH_DEPTH as u32,
+			);
+
 			if let Ok(authorities) = para_client.runtime_api().authorities(initial_parent.hash) {
 				connection_helper.update::<P>(slot_info.slot, &authorities).await;
 			}
@@ -408,6 +436,8 @@ where
 						slot_time.is_parachain_slot_ending(para_slot_duration.as_duration()),
 					collator_peer_id,
 					rp_data.clone(),
+					&mut level_monitor,
+					&mut collation_cache,
 				)
 				.await
 				{
@@ -431,7 +461,7 @@ where
 /// Build a collation for one core.
 ///
 /// One collation can be composed of multiple blocks.
-async fn build_collation_for_core<Block: BlockT, P, RelayClient, BI, CIDP, Proposer, CS>(
+async fn build_collation_for_core<Block: BlockT, P, RelayClient, BI, CIDP, Proposer, CS, BE>(
 	pov_parent_header: Block::Header,
 	pov_parent_hash: Block::Hash,
 	relay_parent_header: &RelayHeader,
@@ -452,6 +482,8 @@ async fn build_collation_for_core<Block: BlockT, P, RelayClient, BI, CIDP, Propo
 	is_last_core_in_parachain_slot: bool,
 	collator_peer_id: PeerId,
 	relay_parent_data: RelayParentData,
+	level_monitor: &mut Option<consensus_common::LevelMonitor<Block, BE>>,
+	collation_cache: &mut CollationCache<Block>,
 ) -> Result<Option<Block::Header>, ()>
 where
 	RelayClient: RelayChainInterface + 'static,
@@ -463,9 +495,48 @@ where
 	BI: BlockImport<Block> + ParachainBlockImportMarker + Send + Sync + 'static,
 	Proposer: Environment<Block> + Send + Sync + 'static,
 	CS: CollatorServiceInterface<Block> + Send + Sync + 'static,
+	BE: sc_client_api::Backend<Block> + 'static,
 {
 	let core_start = Instant::now();
 
+	if let Some(cached) = collation_cache.get(pov_parent_hash, relay_parent_hash, slot_claim.slot())
+	{
+		tracing::debug!(
+			target: crate::LOG_TARGET,
+			?pov_parent_hash,
+			?relay_parent_hash,
+			slot = ?slot_claim.slot(),
+			?core_index,
+			"Reusing a previously built collation for this core instead of re-running the proposer.",
+		);
+
+		let CachedCollation { blocks, proof, validation_code_hash, parent_header, .. } =
+			cached.clone();
+
+		return if let Err(err) = collator_sender.unbounded_send(CollatorMessage {
+			relay_parent: relay_parent_hash,
+			parent_header: pov_parent_header.clone(),
+			blocks,
+			proof,
+			validation_code_hash,
+			core_index,
+			max_pov_size,
+		}) {
+			tracing::error!(
+				target: crate::LOG_TARGET,
+				?err,
+				"Unable to send cached block to collation task."
+			);
+			Err(())
+		} else {
+			if let Some(sleep) = slot_time_for_core.checked_sub(core_start.elapsed()) {
+				tokio::time::sleep(sleep).await;
+			}
+
+			Ok(Some(parent_header))
+		};
+	}
+
 	let validation_data = PersistedValidationData {
 		parent_head: pov_parent_header.encode().into(),
 		relay_parent_number: *relay_parent_header.number(),
@@ -493,6 +564,25 @@ where
 	let mut parent_hash = pov_parent_hash;
 	let mut parent_header = pov_parent_header.clone();
 
+	// Best-effort: if the relay chain can't tell us the backing constraints for this
+	// parachain (e.g. it doesn't implement `para_backing_state` yet), we simply skip the
+	// pre-build checks below and fall back to discovering violations at the relay chain as
+	// before.
+	let mut working_constraints =
+		match inclusion_emulator::fetch_constraints(relay_client, relay_parent_hash, para_id).await
+		{
+			Ok(constraints) => constraints,
+			Err(error) => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					?error,
+					"Could not fetch backing constraints from the relay chain, skipping \
+					 local inclusion-emulator checks for this core.",
+				);
+				None
+			},
+		};
+
 	for block_index in 0..blocks_per_core {
 		//TODO: Remove when transaction streaming is implemented
 		// We require that the next node has imported our last block before it can start building
@@ -513,6 +603,27 @@ where
 			break;
 		}
 
+		if let Some(constraints) = &working_constraints {
+			// Head-data size isn't known until the block is actually built, so this only
+			// checks whether the relay parent we're about to build on is still within the
+			// backing window. The code-upgrade cooldown is not checked, here or after the
+			// block is built.
+			if let Err(violation) = constraints.check_modifications(
+				*relay_parent_header.number(),
+				0,
+				false,
+			) {
+				tracing::error!(
+					target: crate::LOG_TARGET,
+					?violation,
+					?core_index,
+					"Next candidate would violate backing constraints, stopping block \
+					 production for core",
+				);
+				break
+			}
+		}
+
 		let block_start = Instant::now();
 		let slot_time_for_block = slot_time_for_core.saturating_sub(core_start.elapsed()) /
 			(blocks_per_core - block_index) as u32;
@@ -611,11 +722,34 @@ where
 			);
 		}
 
+		let new_block_number = *parent_header.number();
+
+		if let Some(monitor) = level_monitor.as_mut() {
+			// Make room for the candidate we're about to import by pruning the lowest-priority
+			// sibling at this height, if there is one.
+			monitor.enforce_limit(new_block_number);
+
+			if monitor.level_count(new_block_number) >= monitor.level_limit() {
+				tracing::warn!(
+					target: crate::LOG_TARGET,
+					number = ?new_block_number,
+					?core_index,
+					"Too many competing blocks at this height and none could be pruned, \
+					 stopping block production for core",
+				);
+				break
+			}
+		}
+
 		if let Err(error) = collator.import_block(import_block).await {
 			tracing::error!(target: crate::LOG_TARGET, ?error, "Failed to import built block.");
 			return Ok(None);
 		}
 
+		if let Some(monitor) = level_monitor.as_mut() {
+			monitor.block_imported(new_block_number, parent_hash);
+		}
+
 		// Announce the newly built block to our peers.
 		collator.collator_service().announce_block(parent_hash, None);
 
@@ -641,6 +775,27 @@ where
 			break
 		}
 
+		if let Some(constraints) = &mut working_constraints {
+			let head_data_size = parent_header.encode().len() as u32;
+			if head_data_size > constraints.max_head_data_size {
+				tracing::error!(
+					target: crate::LOG_TARGET,
+					violation = ?ConstraintViolation::HeadDataTooBig,
+					%head_data_size,
+					?core_index,
+					"Built candidate violates backing constraints, stopping block production \
+					 for core",
+				);
+				break
+			}
+
+			let modifications = inclusion_emulator::modifications_for_built_block::<Block>(
+				&parent_header,
+				runtime_upgrade_digest,
+			);
+			constraints.apply_modifications(&modifications);
+		}
+
 		ignored_nodes.extend(IgnoredNodes::from_storage_proof::<HashingFor<Block>>(
 			proofs.last().expect("We just pushed the proof into the vector; qed"),
 		));
@@ -660,6 +815,22 @@ where
 
 	let proof = StorageProof::merge(proofs);
 
+	if !blocks.is_empty() {
+		collation_cache.insert(
+			pov_parent_hash,
+			relay_parent_hash,
+			*relay_parent_header.number(),
+			slot_claim.slot(),
+			CachedCollation {
+				blocks: blocks.clone(),
+				proof: proof.clone(),
+				ignored_nodes: ignored_nodes.clone(),
+				validation_code_hash,
+				parent_header: parent_header.clone(),
+			},
+		);
+	}
+
 	if let Err(err) = collator_sender.unbounded_send(CollatorMessage {
 		relay_parent: relay_parent_hash,
 		parent_header: pov_parent_header.clone(),
@@ -722,7 +893,7 @@ pub async fn offset_relay_parent_find_descendants<RelayClient>(
 where
 	RelayClient: RelayChainInterface + Clone + 'static,
 {
-	let Ok(mut relay_header) = relay_chain_data_cache
+	let Ok(relay_header) = relay_chain_data_cache
 		.get_mut_relay_chain_data(relay_best_block)
 		.await
 		.map(|d| d.relay_parent_header.clone())
@@ -735,60 +906,117 @@ where
 		return Ok(Some(RelayParentData::new(relay_header)));
 	}
 
-	if sc_consensus_babe::contains_epoch_change::<RelayBlock>(&relay_header) {
-		tracing::debug!(
-			target: LOG_TARGET,
-			?relay_best_block,
-			relay_best_block_number = relay_header.number(),
-			"Relay parent is in previous session.",
-		);
-		return Ok(None);
-	}
+	// Warm up the cache with a single batched fetch instead of letting the sequential walk below
+	// await one ancestor at a time.
+	relay_chain_data_cache.prefetch_ancestry(relay_header.hash(), relay_parent_offset).await;
+
+	// We need `relay_parent_offset` descendants plus the relay parent itself.
+	let mut ancestors = collect_same_session_ancestors(
+		relay_chain_data_cache,
+		relay_best_block,
+		relay_header,
+		relay_parent_offset as usize + 1,
+	)
+	.await?;
+
+	// The oldest header we could reach without crossing a session boundary becomes the relay
+	// parent; everything younger than it is a descendant, oldest first.
+	let relay_parent = ancestors.pop_front().expect("always contains at least the best block; qed");
+
+	tracing::debug!(
+		target: LOG_TARGET,
+		relay_parent_hash = %relay_parent.hash(),
+		relay_parent_num = relay_parent.number(),
+		num_descendants = ancestors.len(),
+		"Relay parent descendants."
+	);
+
+	Ok(Some(RelayParentData::new_with_descendants(relay_parent, ancestors.into())))
+}
 
-	let mut required_ancestors: VecDeque<RelayHeader> = Default::default();
-	required_ancestors.push_front(relay_header.clone());
-	while required_ancestors.len() < relay_parent_offset as usize {
+/// Walk backwards from `relay_header` (inclusive), collecting up to `max_count` ancestors that
+/// all belong to the same BABE session, stopping early the moment an ancestor that introduces a
+/// new epoch is reached.
+///
+/// Returns the ancestors gathered, oldest first, ending with `relay_header` itself. The number of
+/// ancestors returned is the longest contiguous same-session prefix achievable; it may be smaller
+/// than `max_count` if a session boundary was hit first, but is never empty.
+async fn collect_same_session_ancestors<RelayClient>(
+	relay_chain_data_cache: &mut RelayChainDataCache<RelayClient>,
+	relay_best_block: RelayHash,
+	relay_header: RelayHeader,
+	max_count: usize,
+) -> Result<VecDeque<RelayHeader>, ()>
+where
+	RelayClient: RelayChainInterface + Clone + 'static,
+{
+	let mut current = relay_header.clone();
+	let mut ancestors: VecDeque<RelayHeader> = Default::default();
+	ancestors.push_front(relay_header);
+
+	while ancestors.len() < max_count {
 		let next_header = relay_chain_data_cache
-			.get_mut_relay_chain_data(*relay_header.parent_hash())
+			.get_mut_relay_chain_data(*current.parent_hash())
 			.await?
 			.relay_parent_header
 			.clone();
+
 		if sc_consensus_babe::contains_epoch_change::<RelayBlock>(&next_header) {
 			tracing::debug!(
 				target: LOG_TARGET,
-				?relay_best_block, ancestor = %next_header.hash(),
+				?relay_best_block,
+				ancestor = %next_header.hash(),
 				ancestor_block_number = next_header.number(),
-				"Ancestor of best block is in previous session.",
+				effective_count = ancestors.len(),
+				requested_count = max_count,
+				"Ancestor of best block is in a previous session, using a reduced relay-parent offset.",
 			);
-
-			return Ok(None);
+			break;
 		}
-		required_ancestors.push_front(next_header.clone());
-		relay_header = next_header;
+
+		current = next_header.clone();
+		ancestors.push_front(next_header);
 	}
 
-	let relay_parent = relay_chain_data_cache
-		.get_mut_relay_chain_data(*relay_header.parent_hash())
-		.await?
-		.relay_parent_header
-		.clone();
+	Ok(ancestors)
+}
 
-	tracing::debug!(
-		target: LOG_TARGET,
-		relay_parent_hash = %relay_parent.hash(),
-		relay_parent_num = relay_parent.number(),
-		num_descendants = required_ancestors.len(),
-		"Relay parent descendants."
-	);
+/// The availability status of a core, from the perspective of a specific parachain.
+///
+/// Used to decide whether a core offered to us by the claim queue can actually be built upon
+/// right now, see [`core_availability_for_para`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CoreAvailability {
+	/// The core is currently free (or scheduled), and can be claimed immediately.
+	Free,
+	/// The core is currently occupied, but the candidate occupying it is set to vacate the core
+	/// in our favor, so we can still build upon it speculatively.
+	OccupiedButFreeingForUs,
+}
+
+/// Determine whether `para_id` can make use of `core_state` right now.
+///
+/// Returns `None` if the core is occupied by a candidate that will hand the core to some other
+/// para once it vacates, since a candidate built for it now could never be backed.
+fn core_availability_for_para(core_state: &CoreState, para_id: ParaId) -> Option<CoreAvailability> {
+	match core_state {
+		CoreState::Free | CoreState::Scheduled(_) => Some(CoreAvailability::Free),
+		CoreState::Occupied(occupied) => {
+			let frees_for_us = |next_up: &Option<polkadot_primitives::ScheduledCore>| {
+				next_up.as_ref().is_some_and(|next_up| next_up.para_id == para_id)
+			};
 
-	Ok(Some(RelayParentData::new_with_descendants(relay_parent, required_ancestors.into())))
+			(frees_for_us(&occupied.next_up_on_available) || frees_for_us(&occupied.next_up_on_time_out))
+				.then_some(CoreAvailability::OccupiedButFreeingForUs)
+		},
+	}
 }
 
 /// Return value of [`determine_cores`].
 pub struct Cores {
 	selector: CoreSelector,
 	claim_queue_offset: ClaimQueueOffset,
-	core_indices: Vec<CoreIndex>,
+	core_indices: Vec<(CoreIndex, CoreAvailability)>,
 }
 
 impl Cores {
@@ -801,14 +1029,14 @@ impl Cores {
 		}
 	}
 
-	/// Returns the core indices.
-	fn core_indices(&self) -> &[CoreIndex] {
+	/// Returns the core indices, together with their availability status.
+	fn core_indices(&self) -> &[(CoreIndex, CoreAvailability)] {
 		&self.core_indices
 	}
 
 	/// Returns the current [`CoreIndex`].
 	pub fn core_index(&self) -> CoreIndex {
-		self.core_indices[self.selector.0 as usize]
+		self.core_indices[self.selector.0 as usize].0
 	}
 
 	/// Advance to the next available core.
@@ -848,13 +1076,21 @@ pub async fn determine_cores<RI: RelayChainInterface + 'static>(
 	para_id: ParaId,
 	relay_parent_offset: u32,
 ) -> Result<Option<Cores>, ()> {
-	let claim_queue = &relay_chain_data_cache
-		.get_mut_relay_chain_data(relay_parent.hash())
-		.await?
-		.claim_queue;
+	let relay_chain_data = relay_chain_data_cache.get_mut_relay_chain_data(relay_parent.hash()).await?;
+	let availability_cores = &relay_chain_data.availability_cores;
 
-	let core_indices = claim_queue
+	let core_indices = relay_chain_data
+		.claim_queue
 		.iter_claims_at_depth_for_para(relay_parent_offset as _, para_id)
+		.filter_map(|core_index| {
+			let availability = match availability_cores.get(core_index.0 as usize) {
+				Some(core_state) => core_availability_for_para(core_state, para_id)?,
+				// We have no occupancy information for this core, assume it is free.
+				None => CoreAvailability::Free,
+			};
+
+			Some((core_index, availability))
+		})
 		.collect::<Vec<_>>();
 
 	Ok(if core_indices.is_empty() {
@@ -867,3 +1103,29 @@ pub async fn determine_cores<RI: RelayChainInterface + 'static>(
 		})
 	})
 }
+
+/// Search for parachain blocks we could build upon, across competing unincluded forks.
+///
+/// This is the counterpart to [`determine_cores`]: where `determine_cores` tells us how many
+/// cores are available to us at `params.relay_parent`, this tells us which already-imported
+/// parachain blocks are valid parents to extend, so that an elastic-scaling collator can assign
+/// a distinct parent to each core instead of always extending the same tip. Every returned
+/// [`PotentialParent`][consensus_common::PotentialParent] is at a depth of at most
+/// `params.max_depth` from the included block. Apart from the included block, the pending block
+/// and the blocks between them, which are always returned, each one's relay parent is an
+/// ancestor of `params.relay_parent` within `params.ancestry_lookback` blocks of it.
+pub async fn search_potential_parents<Block: BlockT>(
+	params: consensus_common::ParentSearchParams,
+	para_backend: &impl sc_client_api::Backend<Block>,
+	relay_client: &impl RelayChainInterface,
+) -> Result<Vec<consensus_common::PotentialParent<Block>>, ()> {
+	consensus_common::find_potential_parents::<Block>(params, para_backend, relay_client)
+		.await
+		.map_err(|error| {
+			tracing::error!(
+				target: LOG_TARGET,
+				?error,
+				"Failed to search for potential parents.",
+			);
+		})
+}
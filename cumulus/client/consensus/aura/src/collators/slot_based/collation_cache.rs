@@ -0,0 +1,125 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus. If not, see <https://www.gnu.org/licenses/>.
+
+//! A small cache of already-built, but not yet injected, collations.
+//!
+//! `build_collation_for_core` can be asked to build the same collation more than once: the relay
+//! chain may briefly reorg away from and back to the same block, or a downstream node may not
+//! have imported our previous attempt in time for its own slot. Since the inputs to block
+//! production (the parachain parent, the relay parent, and the parachain slot) fully determine
+//! the resulting candidate, we keep the most recently built candidate for each combination around
+//! so that a repeat request can be served by resending it, without re-running the proposer.
+
+use cumulus_primitives_aura::Slot;
+use polkadot_primitives::{BlockNumber as RelayBlockNumber, Hash as RelayHash, ValidationCodeHash};
+use sp_runtime::traits::Block as BlockT;
+use sp_trie::{recorder::IgnoredNodes, StorageProof};
+use std::collections::HashMap;
+
+/// Uniquely identifies a single collation attempt.
+struct CacheKey<Block: BlockT> {
+	parent_hash: Block::Hash,
+	relay_parent: RelayHash,
+	slot: Slot,
+}
+
+impl<Block: BlockT> PartialEq for CacheKey<Block> {
+	fn eq(&self, other: &Self) -> bool {
+		self.parent_hash == other.parent_hash &&
+			self.relay_parent == other.relay_parent &&
+			self.slot == other.slot
+	}
+}
+
+impl<Block: BlockT> Eq for CacheKey<Block> {}
+
+impl<Block: BlockT> std::hash::Hash for CacheKey<Block> {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.parent_hash.hash(state);
+		self.relay_parent.hash(state);
+		self.slot.hash(state);
+	}
+}
+
+/// A previously built collation that has not yet been superseded.
+#[derive(Clone)]
+pub(super) struct CachedCollation<Block: BlockT> {
+	pub(super) blocks: Vec<Block>,
+	pub(super) proof: StorageProof,
+	pub(super) ignored_nodes: IgnoredNodes,
+	pub(super) validation_code_hash: ValidationCodeHash,
+	pub(super) parent_header: Block::Header,
+}
+
+/// Caches the most recent [`CachedCollation`] built for each `(parent, relay parent, slot)`
+/// combination.
+pub(super) struct CollationCache<Block: BlockT> {
+	entries: HashMap<CacheKey<Block>, (RelayBlockNumber, CachedCollation<Block>)>,
+}
+
+impl<Block: BlockT> CollationCache<Block> {
+	/// Create a new, empty cache.
+	pub(super) fn new() -> Self {
+		Self { entries: HashMap::new() }
+	}
+
+	/// Look up a previously built collation for the given key.
+	pub(super) fn get(
+		&self,
+		parent_hash: Block::Hash,
+		relay_parent: RelayHash,
+		slot: Slot,
+	) -> Option<&CachedCollation<Block>> {
+		self.entries
+			.get(&CacheKey { parent_hash, relay_parent, slot })
+			.map(|(_, cached)| cached)
+	}
+
+	/// Record a newly built collation, keyed by the parent it was built on, the relay parent it
+	/// was authored against, and the slot it was produced for.
+	pub(super) fn insert(
+		&mut self,
+		parent_hash: Block::Hash,
+		relay_parent: RelayHash,
+		relay_parent_number: RelayBlockNumber,
+		slot: Slot,
+		collation: CachedCollation<Block>,
+	) {
+		self.entries
+			.insert(CacheKey { parent_hash, relay_parent, slot }, (relay_parent_number, collation));
+	}
+
+	/// Drop every cached collation built on top of `included_parent`.
+	///
+	/// Once the relay chain has included `included_parent`, any collation built on top of it has
+	/// either already made it into a collation or is now stale; either way it is no longer
+	/// "uninjected" and keeping it around would only let us resend an outdated candidate.
+	pub(super) fn evict_included(&mut self, included_parent: Block::Hash) {
+		self.entries.retain(|key, _| key.parent_hash != included_parent);
+	}
+
+	/// Drop every cached collation whose relay parent has fallen outside of the ancestry window
+	/// ending at `current_relay_parent_number`.
+	pub(super) fn evict_outside_ancestry(
+		&mut self,
+		current_relay_parent_number: RelayBlockNumber,
+		ancestry_lookback: RelayBlockNumber,
+	) {
+		let oldest_allowed = current_relay_parent_number.saturating_sub(ancestry_lookback);
+		self.entries.retain(|_, (relay_parent_number, _)| *relay_parent_number >= oldest_allowed);
+	}
+}
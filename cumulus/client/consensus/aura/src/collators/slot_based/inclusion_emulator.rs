@@ -0,0 +1,151 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus. If not, see <https://www.gnu.org/licenses/>.
+
+//! A minimal, collator-side re-implementation of the prospective-parachains fragment-validity
+//! checks.
+//!
+//! The relay chain only rejects an invalid candidate once it reaches backing, which is long
+//! after the collator has already spent slot time building it. This module lets the block
+//! builder track the same [`Constraints`] the relay chain's backing subsystem would enforce for
+//! the unincluded segment and stop building further blocks on a core as soon as the next
+//! candidate would be rejected, instead of discovering that after the fact.
+//!
+//! This is intentionally a small subset of `polkadot-node-subsystem-util`'s inclusion emulator:
+//! only the checks that can be evaluated from data already on hand in the collator (relay-parent
+//! number, head-data size, outstanding code-upgrade cooldown) are implemented. Per-candidate
+//! HRMP/UMP message accounting requires decoding the parachain's own XCMP/UMP commitments, which
+//! this generic, runtime-agnostic builder has no way to do, so those limits are left to the
+//! relay chain's own backing checks as before.
+
+use cumulus_relay_chain_interface::{call_runtime_api, RelayChainInterface, RelayChainResult};
+use polkadot_primitives::{
+	vstaging::async_backing::{BackingState, Constraints as PrimitiveConstraints},
+	Hash as RelayHash, Id as ParaId,
+};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+
+/// The subset of the relay chain's [`PrimitiveConstraints`] this emulator tracks and enforces.
+#[derive(Clone, Debug)]
+pub struct Constraints<N> {
+	/// The minimum relay-parent number a candidate built on top of this constraint set may use.
+	pub min_relay_parent_number: N,
+	/// The maximum allowed size of the candidate's head data, in bytes.
+	pub max_head_data_size: u32,
+	/// The relay-parent number at which a previously-enqueued code upgrade would be applied, if
+	/// any. While this is `Some`, enqueueing another upgrade is forbidden.
+	pub future_validation_code: Option<N>,
+}
+
+impl<N: Copy> Constraints<N> {
+	/// Derive the initial constraint set from the relay chain's own [`BackingState`] for the
+	/// parachain's included head.
+	fn from_backing_state(state: &BackingState<RelayHash, N>) -> Self {
+		let c: &PrimitiveConstraints<N> = &state.constraints;
+		Constraints {
+			min_relay_parent_number: c.min_relay_parent_number,
+			max_head_data_size: c.max_head_data_size,
+			future_validation_code: c.future_validation_code.as_ref().map(|(at, _)| *at),
+		}
+	}
+}
+
+/// Describes how building one more block in the unincluded segment would change the working
+/// [`Constraints`], so the next candidate is checked against the post-modification state rather
+/// than the initial one.
+#[derive(Clone, Debug, Default)]
+pub struct ConstraintModifications<N> {
+	/// The code-upgrade cooldown introduced by this block, if it enqueued one.
+	pub new_future_validation_code: Option<N>,
+}
+
+/// Why a candidate was rejected before being built.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConstraintViolation {
+	/// The candidate's relay parent is older than the constraints allow.
+	RelayParentTooOld,
+	/// The candidate's head data would exceed the maximum allowed size.
+	HeadDataTooBig,
+	/// A code upgrade from an earlier block in this segment is still cooling down.
+	CodeUpgradeCooldownActive,
+}
+
+impl<N: PartialOrd + Copy> Constraints<N> {
+	/// Check whether a candidate built on these constraints, with a head data of
+	/// `head_data_size` bytes at relay-parent number `relay_parent_number`, and which would (or
+	/// would not) enqueue a code upgrade, is admissible.
+	pub fn check_modifications(
+		&self,
+		relay_parent_number: N,
+		head_data_size: u32,
+		enqueues_code_upgrade: bool,
+	) -> Result<(), ConstraintViolation> {
+		if relay_parent_number < self.min_relay_parent_number {
+			return Err(ConstraintViolation::RelayParentTooOld)
+		}
+
+		if head_data_size > self.max_head_data_size {
+			return Err(ConstraintViolation::HeadDataTooBig)
+		}
+
+		if enqueues_code_upgrade && self.future_validation_code.is_some() {
+			return Err(ConstraintViolation::CodeUpgradeCooldownActive)
+		}
+
+		Ok(())
+	}
+
+	/// Fold a [`ConstraintModifications`] produced by a just-built block into the working
+	/// constraints, so the next candidate in the segment is checked against the updated state.
+	pub fn apply_modifications(&mut self, modifications: &ConstraintModifications<N>) {
+		if let Some(at) = modifications.new_future_validation_code {
+			self.future_validation_code = Some(at);
+		}
+	}
+}
+
+/// Build the [`ConstraintModifications`] implied by having just built `header` on top of the
+/// working constraints.
+pub fn modifications_for_built_block<Block: BlockT>(
+	header: &Block::Header,
+	enqueued_code_upgrade: bool,
+) -> ConstraintModifications<sp_runtime::traits::NumberFor<Block>> {
+	ConstraintModifications {
+		new_future_validation_code: enqueued_code_upgrade.then(|| *header.number()),
+	}
+}
+
+/// Fetch the initial [`Constraints`] for `para_id`'s unincluded segment at `relay_parent`, by
+/// querying the relay chain's `para_backing_state` runtime API.
+///
+/// Returns `Ok(None)` if the relay chain doesn't know about the parachain yet (e.g. it hasn't
+/// been onboarded), and propagates the error if the runtime API call itself fails (for example
+/// because the connected relay chain is too old to implement it).
+pub(crate) async fn fetch_constraints(
+	relay_client: &impl RelayChainInterface,
+	relay_parent: RelayHash,
+	para_id: ParaId,
+) -> RelayChainResult<Option<Constraints<polkadot_primitives::BlockNumber>>> {
+	let backing_state: Option<BackingState> = call_runtime_api(
+		relay_client,
+		"ParachainHost_para_backing_state",
+		relay_parent,
+		para_id,
+	)
+	.await?;
+
+	Ok(backing_state.as_ref().map(Constraints::from_backing_state))
+}
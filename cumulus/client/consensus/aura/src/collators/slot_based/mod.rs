@@ -95,7 +95,9 @@ use std::{path::PathBuf, sync::Arc, time::Duration};
 
 mod block_builder_task;
 mod block_import;
+mod collation_cache;
 mod collation_task;
+mod inclusion_emulator;
 mod relay_chain_data_cache;
 mod slot_timer;
 
@@ -146,6 +148,10 @@ pub struct Params<Block, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS,
 	/// The maximum percentage of the maximum PoV size that the collator can use.
 	/// It will be removed once <https://github.com/paritytech/polkadot-sdk/issues/6020> is fixed.
 	pub max_pov_percentage: Option<u32>,
+	/// Upper bound on the number of sibling blocks this collator keeps around at the same
+	/// height before pruning the least useful one, to stop a restarting or misbehaving
+	/// collator from piling up competing forks in the backend.
+	pub level_limit: consensus_common::LevelLimit,
 }
 
 /// Run aura-based block building and collation task.
@@ -197,6 +203,7 @@ pub fn run<Block, P, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS, Spaw
 		export_pov,
 		relay_chain_slot_duration,
 		max_pov_percentage,
+		level_limit,
 	} = params;
 
 	let (tx, rx) = tracing_unbounded("mpsc_builder_to_collator", 100);
@@ -229,6 +236,7 @@ pub fn run<Block, P, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS, Spaw
 		relay_chain_slot_duration,
 		slot_offset,
 		max_pov_percentage,
+		level_limit,
 	};
 
 	let block_builder_fut =
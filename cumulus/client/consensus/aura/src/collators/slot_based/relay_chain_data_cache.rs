@@ -22,9 +22,10 @@ use cumulus_primitives_core::CoreSelector;
 use cumulus_relay_chain_interface::RelayChainInterface;
 use polkadot_node_subsystem_util::runtime::ClaimQueueSnapshot;
 use polkadot_primitives::{
-	Hash as RelayHash, Header as RelayHeader, Id as ParaId, OccupiedCoreAssumption,
+	Block as RelayBlock, BlockNumber as RelayBlockNumber, CoreState, Hash as RelayHash,
+	Header as RelayHeader, Id as ParaId, OccupiedCoreAssumption,
 };
-use sp_runtime::generic::BlockId;
+use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
 
 /// Contains relay chain data necessary for parachain block building.
 #[derive(Clone, Debug)]
@@ -33,6 +34,9 @@ pub struct RelayChainData {
 	pub relay_parent_header: RelayHeader,
 	/// The claim queue at the relay parent.
 	pub claim_queue: ClaimQueueSnapshot,
+	/// The availability core states at the relay parent, indexed by
+	/// [`CoreIndex`][polkadot_primitives::CoreIndex].
+	pub availability_cores: Vec<CoreState<RelayHash, RelayBlockNumber>>,
 	/// Maximum configured PoV size on the relay chain.
 	pub max_pov_size: u32,
 	/// The last [`CoreSelector`] we used.
@@ -83,6 +87,60 @@ where
 			.expect("There is space for at least one element; qed"))
 	}
 
+	/// Prefetch [`RelayChainData`] for up to `depth` ancestors of `starting_hash` (exclusive),
+	/// stopping early at the first ancestor that introduces a new BABE session.
+	///
+	/// Discovering the ancestor hashes is inherently sequential, since a header only reveals the
+	/// hash of its own parent, but that part only needs cheap header lookups. Once the hashes are
+	/// known, the expensive per-hash data (claim queue, persisted validation data, availability
+	/// cores) is fetched for all of them concurrently and the results are populated into the
+	/// cache, so that a caller walking the same ancestry one hash at a time via
+	/// [`Self::get_mut_relay_chain_data`] finds everything already cached.
+	///
+	/// This is a best-effort warm-up: errors are swallowed, leaving it to the caller's own
+	/// sequential walk to fetch (and report failures for) whatever did not get prefetched.
+	pub async fn prefetch_ancestry(&mut self, starting_hash: RelayHash, depth: u32) {
+		let Some(mut current_header) = self.header_for(starting_hash).await else { return };
+
+		let mut ancestor_hashes = Vec::with_capacity(depth as usize);
+		for _ in 0..depth {
+			let parent_hash = *current_header.parent_hash();
+			let Some(parent_header) = self.header_for(parent_hash).await else { break };
+
+			if sc_consensus_babe::contains_epoch_change::<RelayBlock>(&parent_header) {
+				break;
+			}
+
+			ancestor_hashes.push(parent_hash);
+			current_header = parent_header;
+		}
+
+		let to_fetch: Vec<_> = ancestor_hashes
+			.into_iter()
+			.filter(|hash| self.cached_data.peek(hash).is_none())
+			.collect();
+
+		let fetched = futures::future::join_all(
+			to_fetch.iter().map(|hash| self.update_for_relay_parent(*hash)),
+		)
+		.await;
+
+		for (hash, data) in to_fetch.into_iter().zip(fetched) {
+			if let Ok(data) = data {
+				self.cached_data.get_or_insert(hash, || data);
+			}
+		}
+	}
+
+	/// Look up a relay chain header, preferring an already cached one.
+	async fn header_for(&self, hash: RelayHash) -> Option<RelayHeader> {
+		if let Some(data) = self.cached_data.peek(&hash) {
+			return Some(data.relay_parent_header.clone());
+		}
+
+		self.relay_client.header(BlockId::Hash(hash)).await.ok().flatten()
+	}
+
 	/// Fetch fresh data from the relay chain for the given relay parent hash.
 	async fn update_for_relay_parent(&self, relay_parent: RelayHash) -> Result<RelayChainData, ()> {
 		let claim_queue = claim_queue_at(relay_parent, &self.relay_client).await;
@@ -107,9 +165,22 @@ where
 			},
 		};
 
+		let availability_cores = match self.relay_client.availability_cores(relay_parent).await {
+			Ok(availability_cores) => availability_cores,
+			Err(err) => {
+				tracing::error!(
+					target: crate::LOG_TARGET,
+					?err,
+					"Failed to fetch availability cores from relay-client"
+				);
+				return Err(())
+			},
+		};
+
 		Ok(RelayChainData {
 			relay_parent_header,
 			claim_queue,
+			availability_cores,
 			max_pov_size,
 			last_claimed_core_selector: None,
 		})
@@ -25,7 +25,7 @@ use cumulus_relay_chain_interface::*;
 use futures::Stream;
 use polkadot_node_subsystem_util::runtime::ClaimQueueSnapshot;
 use polkadot_primitives::{
-	CandidateEvent, CommittedCandidateReceiptV2, CoreIndex, Hash as RelayHash,
+	CandidateEvent, CommittedCandidateReceiptV2, CoreIndex, CoreState, Hash as RelayHash,
 	Header as RelayHeader, Id as ParaId,
 };
 use sp_runtime::{generic::BlockId, testing::Header as TestHeader, traits::Header};
@@ -641,9 +641,14 @@ impl RelayChainDataCache<TestRelayClient> {
 
 		let claim_queue_snapshot = ClaimQueueSnapshot::from(claim_queue);
 
+		let highest_core = claim_queue_snapshot.iter_all_claims().map(|(core, _)| core.0).max();
+		let availability_cores =
+			vec![CoreState::Free; highest_core.map(|core| core as usize + 1).unwrap_or(0)];
+
 		let data = RelayChainData {
 			relay_parent_header,
 			claim_queue: claim_queue_snapshot,
+			availability_cores,
 			max_pov_size: 1024 * 1024,
 			last_claimed_core_selector,
 		};
@@ -378,6 +378,16 @@ where
 		remove_leaf(number, target_hash);
 	}
 
+	/// The number of blocks currently tracked at the given level.
+	pub fn level_count(&self, number: NumberFor<Block>) -> usize {
+		self.levels.get(&number).map(|l| l.len()).unwrap_or_default()
+	}
+
+	/// The configured upper bound on the number of blocks allowed per level.
+	pub fn level_limit(&self) -> usize {
+		self.level_limit
+	}
+
 	/// Add a new imported block information to the monitor.
 	pub fn block_imported(&mut self, number: NumberFor<Block>, hash: Block::Hash) {
 		let finalized_num = self.backend.blockchain().info().finalized_number;
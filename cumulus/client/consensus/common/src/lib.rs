@@ -44,8 +44,7 @@ pub use parent_search::*;
 
 pub use parachain_consensus::run_parachain_consensus;
 
-use level_monitor::LevelMonitor;
-pub use level_monitor::{LevelLimit, MAX_LEAVES_PER_LEVEL_SENSIBLE_DEFAULT};
+pub use level_monitor::{LevelLimit, LevelMonitor, MAX_LEAVES_PER_LEVEL_SENSIBLE_DEFAULT};
 
 pub mod import_queue;
 
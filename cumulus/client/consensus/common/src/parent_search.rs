@@ -35,8 +35,9 @@ const PARENT_SEARCH_LOG_TARGET: &str = "consensus::common::find_potential_parent
 /// Parameters when searching for suitable parents to build on top of.
 #[derive(Debug)]
 pub struct ParentSearchParams {
-	/// The best known relay chain block. Must be a descendant of the intended relay parent.
-	pub relay_best_block: RelayHash,
+	/// The relay-chain block we intend to build the next parachain block against. Must be an
+	/// ancestor of, or equal to, the best known relay chain block.
+	pub relay_parent: RelayHash,
 	/// The ID of the parachain.
 	pub para_id: ParaId,
 	/// A limitation on the age of relay parents for parachain blocks that are being
@@ -45,6 +46,11 @@ pub struct ParentSearchParams {
 	/// How "deep" parents can be relative to the included parachain block at the relay-parent.
 	/// The included block has depth 0.
 	pub max_depth: usize,
+	/// Whether to only consider branches that contain the last known pending block, ignoring
+	/// any competing branch built on the same included block. Set this to `false` to also
+	/// return candidates from alternative branches, e.g. to recover when the tip a collator
+	/// imported locally turned out not to be the one that got backed.
+	pub ignore_alternative_branches: bool,
 }
 
 /// A potential parent block returned from [`find_potential_parents`]
@@ -56,6 +62,9 @@ pub struct PotentialParent<B: BlockT> {
 	pub header: B::Header,
 	/// The depth of the block with respect to the included block.
 	pub depth: usize,
+	/// The relay parent this block was built against, if it could be determined from the
+	/// block's digest.
+	pub relay_parent: Option<RelayHash>,
 }
 
 impl<B: BlockT> std::fmt::Debug for PotentialParent<B> {
@@ -64,10 +73,16 @@ impl<B: BlockT> std::fmt::Debug for PotentialParent<B> {
 			.field("hash", &self.hash)
 			.field("depth", &self.depth)
 			.field("number", &self.header.number())
+			.field("relay_parent", &self.relay_parent)
 			.finish()
 	}
 }
 
+/// Extract the relay parent a block was built against from its header digest, if present.
+fn relay_parent_of<H: HeaderT>(header: &H) -> Option<RelayHash> {
+	cumulus_primitives_core::extract_relay_parent(header.digest())
+}
+
 /// Perform a recursive search through blocks to find potential
 /// parent blocks for a new block.
 ///
@@ -94,7 +109,7 @@ pub async fn find_potential_parents<B: BlockT>(
 		relay_client,
 		backend,
 		params.para_id,
-		params.relay_best_block,
+		params.relay_parent,
 	)
 	.await?
 	else {
@@ -103,6 +118,7 @@ pub async fn find_potential_parents<B: BlockT>(
 
 	let only_included = vec![PotentialParent {
 		hash: included_hash,
+		relay_parent: relay_parent_of(&included_header),
 		header: included_header.clone(),
 		depth: 0,
 	}];
@@ -118,7 +134,7 @@ pub async fn find_potential_parents<B: BlockT>(
 		// before being returned to us.
 		let pending_header = relay_client
 			.persisted_validation_data(
-				params.relay_best_block,
+				params.relay_parent,
 				params.para_id,
 				OccupiedCoreAssumption::Included,
 			)
@@ -180,6 +196,7 @@ pub async fn find_potential_parents<B: BlockT>(
 
 				potential_parents.push(PotentialParent {
 					hash: block.hash,
+					relay_parent: relay_parent_of(&header),
 					header,
 					depth: 1 + num,
 				});
@@ -191,6 +208,7 @@ pub async fn find_potential_parents<B: BlockT>(
 			let frontier = if pending_depth <= params.max_depth {
 				vec![PotentialParent {
 					hash: *pending_hash,
+					relay_parent: relay_parent_of(pending_header),
 					header: pending_header.clone(),
 					depth: pending_depth,
 				}]
@@ -210,7 +228,7 @@ pub async fn find_potential_parents<B: BlockT>(
 	// Build up the ancestry record of the relay chain to compare against.
 	let rp_ancestry = build_relay_parent_ancestry(
 		params.ancestry_lookback,
-		params.relay_best_block,
+		params.relay_parent,
 		relay_client,
 	)
 	.await?;
@@ -224,6 +242,7 @@ pub async fn find_potential_parents<B: BlockT>(
 		params.max_depth,
 		rp_ancestry,
 		potential_parents,
+		params.ignore_alternative_branches,
 	))
 }
 
@@ -315,11 +334,13 @@ async fn build_relay_parent_ancestry(
 
 /// Start search for child blocks that can be used as parents.
 ///
-/// This function only respects branches that contain the pending block.
+/// When `ignore_alternative_branches` is `true`, this function only respects branches that
+/// contain the pending block. When `false`, branches that diverge from the pending block are
+/// also explored, as long as they otherwise satisfy the ancestry and depth constraints.
 ///
 /// The frontier is initialized with either the pending block (if it exists and is within max_depth)
 /// or the included block (if there's no pending block). This function validates blocks from the
-/// frontier and explores their children, ensuring all blocks are aligned with the pending block.
+/// frontier and explores their children.
 pub fn search_child_branches_for_parents<Block: BlockT>(
 	mut frontier: Vec<PotentialParent<Block>>,
 	maybe_route_to_last_pending: Option<TreeRoute<Block>>,
@@ -329,6 +350,7 @@ pub fn search_child_branches_for_parents<Block: BlockT>(
 	max_depth: usize,
 	rp_ancestry: Vec<(RelayHash, RelayHash)>,
 	mut potential_parents: Vec<PotentialParent<Block>>,
+	ignore_alternative_branches: bool,
 ) -> Vec<PotentialParent<Block>> {
 	let included_hash = included_header.hash();
 	let is_hash_in_ancestry = |hash| rp_ancestry.iter().any(|x| x.0 == hash);
@@ -400,8 +422,9 @@ pub fn search_child_branches_for_parents<Block: BlockT>(
 				pending_distance.map_or(true, |dist| child_depth > dist) ||
 					is_child_pending(child);
 
-			// We only respect branches that contain the pending block.
-			if !aligned_with_pending {
+			// We only respect branches that contain the pending block, unless the caller asked
+			// us to also consider alternative branches.
+			if ignore_alternative_branches && !aligned_with_pending {
 				tracing::trace!(target: PARENT_SEARCH_LOG_TARGET, ?child, "Child is not aligned with pending block.");
 				continue
 			}
@@ -410,6 +433,7 @@ pub fn search_child_branches_for_parents<Block: BlockT>(
 
 			frontier.push(PotentialParent {
 				hash: child,
+				relay_parent: relay_parent_of(&header),
 				header,
 				depth: child_depth,
 			});
@@ -385,6 +385,9 @@ pub mod pallet {
 		BatchReceived { pallet: PalletEventName, count: u32 },
 		/// We processed a batch of messages for this pallet.
 		BatchProcessed { pallet: PalletEventName, count_good: u32, count_bad: u32 },
+		/// A proxy deposit was recomputed after dropping or truncating some delegations, and the
+		/// difference was unreserved from the delegator.
+		ProxyDepositUpdated { delegator: T::AccountId, new_deposit: BalanceOf<T>, unreserved: BalanceOf<T> },
 	}
 
 	#[pallet::pallet]
@@ -50,12 +50,13 @@ impl<T: Config> Pallet<T> {
 	pub fn do_receive_proxy(proxy: RcProxyOf<T, T::RcProxyType>) -> Result<(), Error<T>> {
 		log::info!(target: LOG_TARGET, "Integrating proxy {}, deposit {:?}", proxy.delegator.to_polkadot_ss58(), proxy.deposit);
 		let max_proxies = <T as pallet_proxy::Config>::MaxProxies::get() as usize;
+		let mut dropped = 0u32;
 
 		// Translate the incoming ones from RC
 		let mut proxies = proxy.proxies.into_iter().enumerate().filter_map(|(i, p)| {
 			let Ok(proxy_type) = T::RcToProxyType::try_convert(p.proxy_type.clone()) else {
 				log::info!(target: LOG_TARGET, "Dropping unsupported proxy kind of '{:?}' at index {} for {}", p.proxy_type, i, proxy.delegator.to_polkadot_ss58());
-				// TODO unreserve deposit
+				dropped += 1;
 				return None;
 			};
 			let delay = T::RcToAhDelay::convert(p.delay);
@@ -86,6 +87,7 @@ impl<T: Config> Pallet<T> {
 			// always the `Any` proxy and low Delay proxies are more important.
 			defensive!("Truncating proxy list with best-effort priority");
 			proxies.sort_by(|a, b| b.proxy_type.cmp(&a.proxy_type).then(b.delay.cmp(&a.delay)));
+			dropped += (proxies.len() - max_proxies) as u32;
 			proxies.truncate(max_proxies);
 		}
 
@@ -95,8 +97,38 @@ impl<T: Config> Pallet<T> {
 			return Err(Error::TODO);
 		};
 
+		// If we dropped unsupported or truncated any delegations, the deposit charged on RC no
+		// longer matches what AH's own `pallet_proxy` would charge for the surviving (merged)
+		// count, so recompute it and refund the difference.
+		let deposit = if dropped > 0 {
+			let new_deposit = <T as pallet_proxy::Config>::ProxyDepositBase::get().saturating_add(
+				<T as pallet_proxy::Config>::ProxyDepositFactor::get()
+					.saturating_mul((bounded_proxies.len() as u32).into()),
+			);
+			let to_unreserve = proxy.deposit.saturating_sub(new_deposit);
+			let missing = <T as pallet_proxy::Config>::Currency::unreserve(
+				&proxy.delegator,
+				to_unreserve,
+			);
+			let unreserved = to_unreserve.saturating_sub(missing);
+
+			if !missing.is_zero() {
+				log::warn!(target: LOG_TARGET, "Could not unreserve full proxy deposit difference for {}, unreserved {:?} / {:?} since account had {:?} reserved", proxy.delegator.to_polkadot_ss58(), unreserved, &to_unreserve, frame_system::Account::<T>::get(&proxy.delegator).data.reserved);
+			}
+
+			Self::deposit_event(Event::ProxyDepositUpdated {
+				delegator: proxy.delegator.clone(),
+				new_deposit,
+				unreserved,
+			});
+
+			new_deposit
+		} else {
+			proxy.deposit
+		};
+
 		// Add the proxies
-		pallet_proxy::Proxies::<T>::insert(&proxy.delegator, (bounded_proxies, proxy.deposit));
+		pallet_proxy::Proxies::<T>::insert(&proxy.delegator, (bounded_proxies, deposit));
 
 		Ok(())
 	}
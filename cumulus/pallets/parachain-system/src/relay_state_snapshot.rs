@@ -19,6 +19,7 @@ use cumulus_primitives_core::{
 	relay_chain, AbridgedHostConfiguration, AbridgedHrmpChannel, ParaId,
 };
 use scale_info::TypeInfo;
+use sp_core::storage::ChildInfo;
 use sp_runtime::traits::HashingFor;
 use sp_state_machine::{Backend, TrieBackend, TrieBackendBuilder};
 use sp_std::vec::Vec;
@@ -75,6 +76,8 @@ pub enum Error {
 	ReadEntry(ReadEntryErr),
 	/// The optional entry cannot be read.
 	ReadOptionalEntry(ReadEntryErr),
+	/// A child trie entry, or a proof for one, cannot be read.
+	ReadChildEntry(ReadEntryErr),
 	/// The slot cannot be extracted.
 	Slot(ReadEntryErr),
 	/// The upgrade go-ahead signal cannot be read.
@@ -343,4 +346,57 @@ impl RelayChainStateProof {
 	{
 		read_optional_entry(&self.trie_backend, key).map_err(Error::ReadOptionalEntry)
 	}
+
+	/// Read a single raw value from the child trie identified by `child_info`.
+	///
+	/// Returns `Ok(None)` if `key` is absent from the child trie. Returns `Err` if the backend
+	/// can't resolve the value (likely due to a malformed proof).
+	pub fn read_child_storage(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<Vec<u8>>, Error> {
+		self.trie_backend
+			.child_storage(child_info, key)
+			.map_err(|_| Error::ReadChildEntry(ReadEntryErr::Proof))
+	}
+
+	/// Read every entry in the child trie identified by `child_info` whose key begins with
+	/// `prefix`, up to `limit` matches, together with a storage proof covering exactly those
+	/// entries.
+	///
+	/// The returned proof is self-contained: a caller holding the child trie root (e.g. the one
+	/// returned by reading the child's prefixed storage key via [`Self::read_optional_entry`])
+	/// can check it independently with [`sp_state_machine::read_child_proof_check`], without
+	/// trusting this struct's internal state.
+	pub fn read_child_storage_with_prefix(
+		&self,
+		child_info: &ChildInfo,
+		prefix: &[u8],
+		limit: u32,
+	) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, StorageProof), Error> {
+		let mut matched_keys = Vec::new();
+		self.trie_backend.for_child_keys_with_prefix(child_info, prefix, |key| {
+			if (matched_keys.len() as u32) < limit {
+				matched_keys.push(key.to_vec());
+			}
+		});
+
+		let mut entries = Vec::with_capacity(matched_keys.len());
+		for key in &matched_keys {
+			if let Some(value) = self.read_child_storage(child_info, key)? {
+				entries.push((key.clone(), value));
+			}
+		}
+
+		let proof = sp_state_machine::prove_child_read_on_trie_backend(
+			&self.trie_backend,
+			&child_info.prefixed_storage_key(),
+			child_info.clone(),
+			matched_keys.iter().map(|key| key.as_slice()),
+		)
+		.map_err(|_| Error::ReadChildEntry(ReadEntryErr::Proof))?;
+
+		Ok((entries, proof))
+	}
 }
@@ -21,6 +21,10 @@
 //! This pallet is heavily opinionated toward a parachain-to-parachain publish-subscribe model.
 //! It assumes ParaId as the identifier for each child trie and is designed specifically for
 //! extracting published data from relay chain proofs in a pubsub mechanism.
+//!
+//! Subscriptions may name an [`SubscriptionKey::Exact`] key or a [`SubscriptionKey::Prefix`] to
+//! match a whole namespace of keys under a publisher's child trie; the latter is bounded by
+//! [`Config::MaxKeysPerPublisher`] to keep weight deterministic.
 
 extern crate alloc;
 
@@ -51,15 +55,48 @@ mod mock;
 mod tests;
 pub mod weights;
 
+/// A single subscribed child-trie key, either matched exactly or as a prefix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubscriptionKey {
+	/// Match exactly this child-trie key.
+	Exact(Vec<u8>),
+	/// Match every child-trie key starting with this prefix, up to `MaxKeysPerPublisher` matches.
+	/// Lets a subscriber consume a whole namespace (e.g. all price feeds a publisher exposes)
+	/// without hard-coding each key.
+	Prefix(Vec<u8>),
+}
+
+impl SubscriptionKey {
+	/// The raw bytes identifying this subscription: the exact key, or the prefix.
+	fn as_bytes(&self) -> &[u8] {
+		match self {
+			SubscriptionKey::Exact(key) => key,
+			SubscriptionKey::Prefix(prefix) => prefix,
+		}
+	}
+}
+
 /// Define subscriptions and handle received data.
 pub trait SubscriptionHandler {
 	/// List of subscriptions as (ParaId, keys) tuples.
 	/// Returns (subscriptions, weight) where weight is the cost of computing the subscriptions.
-	fn subscriptions() -> (Vec<(ParaId, Vec<Vec<u8>>)>, Weight);
+	fn subscriptions() -> (Vec<(ParaId, Vec<SubscriptionKey>)>, Weight);
 
 	/// Called when subscribed data is updated.
+	///
+	/// `proof_nodes` carries the raw child-trie proof nodes touched while resolving `key` from a
+	/// [`SubscriptionKey::Prefix`] match, so implementations can independently re-verify `value`
+	/// against the publisher's child trie root (as returned by
+	/// [`Pallet::collect_publisher_roots`]) instead of trusting that this pallet read it
+	/// correctly. It is empty for [`SubscriptionKey::Exact`] matches.
+	///
 	/// Returns the weight consumed by processing the data.
-	fn on_data_updated(publisher: ParaId, key: Vec<u8>, value: Vec<u8>) -> Weight;
+	fn on_data_updated(
+		publisher: ParaId,
+		key: Vec<u8>,
+		value: Vec<u8>,
+		proof_nodes: Vec<Vec<u8>>,
+	) -> Weight;
 }
 
 #[frame_support::pallet]
@@ -82,6 +119,11 @@ pub mod pallet {
 		/// Maximum number of publishers that can be tracked simultaneously.
 		#[pallet::constant]
 		type MaxPublishers: Get<u32>;
+		/// Maximum number of keys a [`SubscriptionKey::Prefix`] subscription can match per
+		/// publisher per block. Bounds the work done resolving a prefix so weight stays
+		/// deterministic.
+		#[pallet::constant]
+		type MaxKeysPerPublisher: Get<u32>;
 	}
 
 	/// Child trie roots from previous block for change detection.
@@ -151,7 +193,7 @@ pub mod pallet {
 				data_keys.into_iter().map(move |key| {
 					cumulus_primitives_core::RelayStorageKey::Child {
 						storage_key: storage_key.clone(),
-						key,
+						key: key.as_bytes().to_vec(),
 					}
 				})
 				})
@@ -175,7 +217,7 @@ pub mod pallet {
 
 		pub fn collect_publisher_roots(
 			relay_state_proof: &RelayChainStateProof,
-			subscriptions: &[(ParaId, Vec<Vec<u8>>)],
+			subscriptions: &[(ParaId, Vec<SubscriptionKey>)],
 		) -> BTreeMap<ParaId, Vec<u8>> {
 			subscriptions
 				.iter()
@@ -193,10 +235,41 @@ pub mod pallet {
 				.collect()
 		}
 
+		/// Decode an encoded value read from a publisher's child trie and, on success, notify
+		/// `T::SubscriptionHandler` and deposit [`Event::DataProcessed`].
+		///
+		/// Returns the handler weight consumed and the number of encoded bytes decoded, for the
+		/// caller to accumulate.
+		fn decode_and_notify(
+			publisher: ParaId,
+			key: Vec<u8>,
+			encoded_value: Vec<u8>,
+			proof_nodes: Vec<Vec<u8>>,
+		) -> (Weight, u32) {
+			let encoded_size = encoded_value.len() as u32;
+
+			match Vec::<u8>::decode(&mut &encoded_value[..]) {
+				Ok(value) => {
+					let value_size = value.len() as u32;
+
+					let handler_weight =
+						T::SubscriptionHandler::on_data_updated(publisher, key.clone(), value, proof_nodes);
+
+					Self::deposit_event(Event::DataProcessed { publisher, key, value_size });
+
+					(handler_weight, encoded_size)
+				},
+				Err(_) => {
+					defensive!("Failed to decode published data value");
+					(Weight::zero(), encoded_size)
+				},
+			}
+		}
+
 		pub fn process_published_data(
 			relay_state_proof: &RelayChainStateProof,
 			current_roots: &BTreeMap<ParaId, Vec<u8>>,
-			subscriptions: &[(ParaId, Vec<Vec<u8>>)],
+			subscriptions: &[(ParaId, Vec<SubscriptionKey>)],
 		) -> (Weight, u32) {
 			// Load roots from previous block for change detection.
 			let previous_roots = <PreviousPublishedDataRoots<T>>::get();
@@ -222,41 +295,56 @@ pub mod pallet {
 					if should_update {
 						let child_info = Self::derive_child_info(*publisher);
 
-						// Read each subscribed key from relay proof.
-						for key in subscription_keys.iter() {
-							match relay_state_proof.read_child_storage(&child_info, key) {
-								Ok(Some(encoded_value)) => {
-									let encoded_size = encoded_value.len() as u32;
-									total_bytes_decoded = total_bytes_decoded.saturating_add(encoded_size);
-
-									match Vec::<u8>::decode(&mut &encoded_value[..]) {
-										Ok(value) => {
-											let value_size = value.len() as u32;
-
-											// Notify handler of new data.
-											let handler_weight = T::SubscriptionHandler::on_data_updated(
+						// Read each subscribed key (or prefix) from relay proof.
+						for subscription_key in subscription_keys.iter() {
+							match subscription_key {
+								SubscriptionKey::Exact(key) => {
+									match relay_state_proof.read_child_storage(&child_info, key) {
+										Ok(Some(encoded_value)) => {
+											let (handler_weight, bytes_decoded) = Self::decode_and_notify(
 												*publisher,
 												key.clone(),
-												value.clone(),
+												encoded_value,
+												Vec::new(),
 											);
-											total_handler_weight = total_handler_weight.saturating_add(handler_weight);
-
-											Self::deposit_event(Event::DataProcessed {
-												publisher: *publisher,
-												key: key.clone(),
-												value_size,
-											});
+											total_handler_weight =
+												total_handler_weight.saturating_add(handler_weight);
+											total_bytes_decoded =
+												total_bytes_decoded.saturating_add(bytes_decoded);
+										},
+										Ok(None) => {
+											// Key not published yet - expected.
 										},
 										Err(_) => {
-											defensive!("Failed to decode published data value");
+											defensive!("Failed to read child storage from relay chain proof");
 										},
 									}
 								},
-								Ok(None) => {
-									// Key not published yet - expected.
-								},
-								Err(_) => {
-									defensive!("Failed to read child storage from relay chain proof");
+								SubscriptionKey::Prefix(prefix) => {
+									match relay_state_proof.read_child_storage_with_prefix(
+										&child_info,
+										prefix,
+										T::MaxKeysPerPublisher::get(),
+									) {
+										Ok((entries, proof)) => {
+											let proof_nodes: Vec<Vec<u8>> = proof.iter_nodes().collect();
+											for (matched_key, encoded_value) in entries {
+												let (handler_weight, bytes_decoded) = Self::decode_and_notify(
+													*publisher,
+													matched_key,
+													encoded_value,
+													proof_nodes.clone(),
+												);
+												total_handler_weight =
+													total_handler_weight.saturating_add(handler_weight);
+												total_bytes_decoded =
+													total_bytes_decoded.saturating_add(bytes_decoded);
+											}
+										},
+										Err(_) => {
+											defensive!("Failed to read child storage prefix from relay chain proof");
+										},
+									}
 								},
 							}
 						}
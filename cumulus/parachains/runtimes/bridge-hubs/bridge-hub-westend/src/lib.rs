@@ -902,6 +902,10 @@ impl_runtime_apis! {
 			snowbridge_pallet_outbound_queue::api::prove_message::<Runtime>(leaf_index)
 		}
 
+		fn messages_root() -> sp_core::H256 {
+			snowbridge_pallet_outbound_queue::api::messages_root::<Runtime>()
+		}
+
 		fn calculate_fee(command: Command, parameters: Option<PricingParameters<Balance>>) -> Fee<Balance> {
 			snowbridge_pallet_outbound_queue::api::calculate_fee::<Runtime>(command, parameters)
 		}
@@ -21,12 +21,14 @@ use crate::{
 	RuntimeCallOf, RuntimeOriginOf, ValidatorIdOf,
 };
 use codec::Encode;
+use frame_metadata::{RuntimeMetadata, META_RESERVED};
 use frame_support::{
 	assert_ok,
 	traits::{Get, OriginTrait},
 	weights::WeightToFee as WeightToFeeT,
 };
 use parachains_common::AccountId;
+use scale_info::{form::PortableForm, TypeDef};
 use sp_runtime::{
 	traits::{Block as BlockT, SaturatedConversion, StaticLookup},
 	DispatchError, Either,
@@ -251,3 +253,108 @@ where
 		}
 	})
 }
+
+/// The snapshot of one pallet's dispatchable calls, as recorded by
+/// [`assert_extrinsic_ordering_stable`]: the pallet's index and name, and the index/name of every
+/// call it exposes.
+pub type ExpectedPalletCalls = (u8, &'static str, &'static [(u8, &'static str)]);
+
+/// Decodes `Runtime`'s `V15` metadata into the flat `(pallet index, pallet name, calls)` shape
+/// that [`assert_extrinsic_ordering_stable`] and [`print_extrinsic_ordering_snapshot`] compare
+/// against `expected`.
+fn extrinsic_ordering_snapshot<Runtime: frame_system::Config>() -> Vec<(u8, String, Vec<(u8, String)>)>
+{
+	let prefixed = Runtime::metadata();
+	assert_eq!(prefixed.0, META_RESERVED);
+	let RuntimeMetadata::V15(metadata) = prefixed.1 else {
+		panic!("expected runtime metadata version 15, the ordering check needs updating for a new version");
+	};
+
+	metadata
+		.pallets
+		.into_iter()
+		.filter_map(|pallet| {
+			let calls = pallet.calls?;
+			let ty = metadata
+				.types
+				.resolve(calls.ty.id)
+				.expect("the call type of a pallet is always present in its own metadata registry");
+			let TypeDef::Variant(variant) = &ty.type_def else {
+				panic!("pallet `{}`'s call type is not a variant, metadata is malformed", pallet.name);
+			};
+			let calls: Vec<(u8, String)> = variant
+				.variants
+				.iter()
+				.map(|v: &scale_info::Variant<PortableForm>| (v.index, v.name.clone()))
+				.collect();
+			Some((pallet.index, pallet.name, calls))
+		})
+		.collect()
+}
+
+/// Test-case that asserts the `Runtime`'s pallet indices, call indices, and call names match the
+/// hardcoded `expected` snapshot.
+///
+/// This is meant to catch accidental re-ordering or removal of pallets/calls that silently changes
+/// extrinsic encoding without a corresponding `transaction_version` bump. When a change is
+/// intentional, regenerate `expected` with [`print_extrinsic_ordering_snapshot`] and bump
+/// `transaction_version` in the runtime's `VERSION`.
+pub fn assert_extrinsic_ordering_stable<Runtime: frame_system::Config>(
+	expected: &[ExpectedPalletCalls],
+) {
+	let actual = extrinsic_ordering_snapshot::<Runtime>();
+
+	for (pallet_index, pallet_name, expected_calls) in expected {
+		let (_, actual_name, actual_calls) = actual
+			.iter()
+			.find(|(index, _, _)| index == pallet_index)
+			.unwrap_or_else(|| {
+				panic!(
+					"pallet index {} is missing from the runtime metadata, expected `{}`. Did a \
+					 pallet get removed or re-ordered without a `transaction_version` bump?",
+					pallet_index, pallet_name,
+				)
+			});
+		assert_eq!(
+			actual_name, pallet_name,
+			"pallet at index {} is now called `{}`, expected `{}`. Bump `transaction_version` if \
+			 this rename is intentional.",
+			pallet_index, actual_name, pallet_name,
+		);
+
+		for (call_index, call_name) in *expected_calls {
+			let (_, actual_call_name) = actual_calls
+				.iter()
+				.find(|(index, _)| index == call_index)
+				.unwrap_or_else(|| {
+					panic!(
+						"call index {} is missing from pallet `{}`, expected `{}`",
+						call_index, pallet_name, call_name,
+					)
+				});
+			assert_eq!(
+				actual_call_name, call_name,
+				"call {} in pallet `{}` is now called `{}`, expected `{}`. Bump \
+				 `transaction_version` if this rename is intentional.",
+				call_index, pallet_name, actual_call_name, call_name,
+			);
+		}
+	}
+}
+
+/// Prints the `Runtime`'s current pallet/call table as a Rust source snippet, formatted as the
+/// `expected` array that [`assert_extrinsic_ordering_stable`] takes. Maintainers can paste the
+/// output back into their test after an intentional re-ordering or rename.
+pub fn print_extrinsic_ordering_snapshot<Runtime: frame_system::Config>() {
+	let actual = extrinsic_ordering_snapshot::<Runtime>();
+
+	println!("&[");
+	for (pallet_index, pallet_name, calls) in actual {
+		println!("\t({pallet_index}, \"{pallet_name}\", &[");
+		for (call_index, call_name) in calls {
+			println!("\t\t({call_index}, \"{call_name}\"),");
+		}
+		println!("\t]),");
+	}
+	println!("]");
+}
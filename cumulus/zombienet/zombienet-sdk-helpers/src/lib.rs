@@ -506,6 +506,34 @@ fn create_assign_core_call(core_and_para: &[(u32, u32)]) -> DynamicPayload {
 	)
 }
 
+/// Creates a call authorizing a future runtime upgrade to `code_hash`, via `sudo` and
+/// `System::authorize_upgrade`.
+///
+/// This mirrors the call a runtime's governance dispatches through `execute_as_governance_call`
+/// (see `can_governance_authorize_upgrade`); `sudo` stands in for the Root origin here.
+pub fn create_authorize_upgrade_call(code_hash: H256) -> DynamicPayload {
+	zombienet_sdk::subxt::tx::dynamic(
+		"Sudo",
+		"sudo",
+		vec![value! {
+			System(authorize_upgrade { code_hash: code_hash.0 })
+		}],
+	)
+}
+
+/// Creates a permissionless call applying a previously authorized runtime upgrade.
+///
+/// Unlike [`create_runtime_upgrade_call`], this does not require `sudo`: `System::
+/// apply_authorized_upgrade` checks the supplied code against the hash authorized by
+/// [`create_authorize_upgrade_call`] and is callable by anyone.
+pub fn create_apply_authorized_upgrade_call(wasm: &[u8]) -> DynamicPayload {
+	zombienet_sdk::subxt::tx::dynamic(
+		"System",
+		"apply_authorized_upgrade",
+		vec![Value::from_bytes(wasm)],
+	)
+}
+
 /// Creates a runtime upgrade call using `sudo` and `set_code`.
 pub fn create_runtime_upgrade_call(wasm: &[u8]) -> DynamicPayload {
 	zombienet_sdk::subxt::tx::dynamic(
@@ -552,3 +580,37 @@ pub async fn wait_for_runtime_upgrade(
 
 	Err(anyhow!("Did not find a runtime upgrade"))
 }
+
+/// Wait until `client`'s runtime has upgraded to `expected_spec_version`.
+///
+/// Polls the runtime version on every finalized block, analogous to
+/// [`wait_for_nth_session_change`] polling for session-change events, and resolves as soon as
+/// `spec_version` reaches `expected_spec_version`.
+pub async fn wait_for_upgrade(
+	client: OnlineClient<PolkadotConfig>,
+	expected_spec_version: u32,
+) -> Result<(), anyhow::Error> {
+	let mut finalized_blocks = client.blocks().subscribe_finalized().await?;
+
+	while let Some(block) = finalized_blocks.next().await {
+		let block = block?;
+		let spec_version = client.backend().current_runtime_version().await?.spec_version;
+		log::debug!(
+			"Finalized block {}, runtime spec version {spec_version}, waiting for {expected_spec_version}",
+			block.number()
+		);
+
+		if spec_version == expected_spec_version {
+			log::info!("Runtime upgraded to spec version {spec_version} at block {}", block.number());
+			return Ok(());
+		}
+
+		if spec_version > expected_spec_version {
+			return Err(anyhow!(
+				"Runtime spec version {spec_version} overshot the expected {expected_spec_version}"
+			));
+		}
+	}
+
+	Err(anyhow!("Did not reach runtime spec version {expected_spec_version} before the finalized block stream ended"))
+}
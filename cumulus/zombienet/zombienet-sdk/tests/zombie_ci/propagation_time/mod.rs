@@ -2,22 +2,28 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-	self,
-	path::PathBuf,
+	collections::BTreeMap,
 	str::FromStr,
 	time::{Duration, Instant},
 };
 
+use anyhow::anyhow;
 use cumulus_zombienet_sdk_helpers::assert_para_throughput;
 use polkadot_primitives::Id as ParaId;
-use statrs::statistics::OrderStatistics;
+use rand::{rngs::StdRng, SeedableRng};
+use statrs::statistics::{Data, OrderStatistics};
 use zombienet_sdk::{
 	subxt::{ext::futures, OnlineClient, PolkadotConfig},
-	AddCollatorOptions, LocalFileSystem, Network, NetworkConfig, NetworkNode,
+	AddCollatorOptions, LocalFileSystem, Network, NetworkConfigBuilder, NetworkNode,
 };
 
 const PARA_ID: u32 = 2000;
 const BEST_BLOCK_METRIC: &str = "block_height{status=\"best\"}";
+/// Number of runs to collect propagation-time samples over.
+const RUNS: usize = 20;
+/// Placeholder "peer index" standing in for the block-producing validator in a [`Topology`]'s
+/// adjacency lists, distinct from any real collator index.
+const VALIDATOR: usize = usize::MAX;
 
 #[ignore = "Slow test used to measure block propagation time in a sparsely connected network"]
 #[tokio::test(flavor = "multi_thread")]
@@ -29,19 +35,32 @@ async fn sparsely_connected_network_block_propagation_time() -> Result<(), anyho
 	log::warn!("This test is slow. It will take a long time to complete.");
 	tokio::time::sleep(Duration::from_secs(3)).await;
 
+	let config = PropagationTestConfig::from_env();
+	log::info!(
+		"Testing a {:?} topology with {} collators ({}/{} in/out peers)",
+		config.topology,
+		config.node_count,
+		config.in_peers,
+		config.out_peers,
+	);
+
 	let mut num_failures = 0;
 	let mut propagation_times = Vec::new();
+	let mut per_hop_samples: BTreeMap<usize, Vec<f64>> = BTreeMap::new();
 
-	// Run many tests to get a better average.
-	while propagation_times.len() < 20 {
+	// Run many tests to get a better picture of the distribution.
+	while propagation_times.len() < RUNS {
 		log::info!("Running test #{}", propagation_times.len() + 1);
 		if num_failures > 7 {
 			anyhow::bail!("Too many failures ({num_failures}), aborting further tests.");
 		}
-		match run_test().await {
-			Ok(propagation_time) => {
-				log::info!("Propagation time: {propagation_time} seconds");
-				propagation_times.push(propagation_time);
+		match run_test(&config).await {
+			Ok(result) => {
+				log::info!("Propagation time: {} seconds", result.overall_seconds);
+				propagation_times.push(result.overall_seconds);
+				for (distance, seconds) in result.per_hop_seconds {
+					per_hop_samples.entry(distance).or_default().push(seconds);
+				}
 			},
 			Err(e) => {
 				log::error!("Test failed: {e}");
@@ -50,27 +69,38 @@ async fn sparsely_connected_network_block_propagation_time() -> Result<(), anyho
 		}
 	}
 
-	propagation_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
-	log::info!("Propagation times distribution: {propagation_times:?}");
-	let avg = propagation_times.iter().sum::<f64>() / propagation_times.len() as f64;
-	log::info!("Average propagation time: {avg} seconds");
-	let median = if propagation_times.len() % 2 == 0 {
-		(propagation_times[propagation_times.len() / 2 - 1] +
-			propagation_times[propagation_times.len() / 2]) /
-			2.0
-	} else {
-		propagation_times[propagation_times.len() / 2]
-	};
-	log::info!("Median propagation time: {median} seconds");
-	let mut propagation_times = statrs::statistics::Data::new(propagation_times);
-	log::info!("90th percentile propagation time: {} seconds", propagation_times.percentile(90));
-	log::info!("99th percentile propagation time: {} seconds", propagation_times.percentile(99));
+	report_percentiles("overall", &propagation_times);
+	for (distance, samples) in &per_hop_samples {
+		report_percentiles(&format!("hop-distance {distance}"), samples);
+	}
 
 	Ok(())
 }
 
-async fn run_test() -> Result<f64, anyhow::Error> {
-	let NetworkActors { network, validator, collators } = initialize_network().await?;
+/// Log the mean, max, and p50/p90/p95/p99 of `samples` (in seconds), tagged with `label`.
+fn report_percentiles(label: &str, samples: &[f64]) {
+	let avg = samples.iter().sum::<f64>() / samples.len() as f64;
+	let max = samples.iter().cloned().fold(f64::MIN, f64::max);
+	log::info!("[{label}] mean: {avg:.3}s, max: {max:.3}s, over {} samples", samples.len());
+
+	let mut data = Data::new(samples.to_vec());
+	for p in [50, 90, 95, 99] {
+		log::info!("[{label}] p{p}: {:.3}s", data.percentile(p));
+	}
+}
+
+/// The result of a single propagation-time run.
+struct RunResult {
+	/// Time from the validator producing the block to every collator having received it.
+	overall_seconds: f64,
+	/// For each collator, its hop distance from the validator in the gossip topology alongside
+	/// how long it individually took to receive the block.
+	per_hop_seconds: Vec<(usize, f64)>,
+}
+
+async fn run_test(config: &PropagationTestConfig) -> Result<RunResult, anyhow::Error> {
+	let NetworkActors { network, validator, collators, distance } =
+		initialize_network(config).await?;
 
 	let relay_alice = network.get_node("alice")?;
 	let relay_client: OnlineClient<PolkadotConfig> = relay_alice.wait_client().await?;
@@ -95,15 +125,17 @@ async fn run_test() -> Result<f64, anyhow::Error> {
 	log::info!("Waiting for validator to advance beyond block height {block_height}");
 	timeout(wait_next_block(&[validator], block_height)).await?;
 	log::info!("Validator advanced beyond block height {block_height}");
-	// At this point, the new block will start to propagate through the network. Store the timestamp
-	// so we can measure the propagation time.
+	// At this point, the new block will start to propagate through the network. Store the
+	// timestamp so we can measure the propagation time, both overall and per collator.
 	let start = Instant::now();
-	// Wait for the new block to propagate to all collators.
 	log::info!("Waiting for collators to propagate the new block");
-	timeout(wait_next_block(&collators, block_height)).await?;
+	let crossing_times = timeout(wait_next_block_per_node(&collators, block_height, start)).await?;
 	log::info!("All collators received the new block");
 
-	Ok(start.elapsed().as_secs_f64())
+	let per_hop_seconds =
+		crossing_times.into_iter().enumerate().map(|(i, seconds)| (distance[i], seconds)).collect();
+
+	Ok(RunResult { overall_seconds: start.elapsed().as_secs_f64(), per_hop_seconds })
 }
 
 async fn timeout<F, T>(future: F) -> Result<T, anyhow::Error>
@@ -113,66 +145,233 @@ where
 	tokio::time::timeout(Duration::from_secs(180), future).await?
 }
 
-async fn initialize_network() -> Result<NetworkActors, anyhow::Error> {
-	// Load network configuration from TOML file.
-	let toml_path = PathBuf::from_str(env!("CARGO_MANIFEST_DIR"))
-		.unwrap()
-		.join("tests/zombie_ci/propagation_time/sparsely_connected_network.toml");
-	let config = NetworkConfig::load_from_toml(toml_path.to_str().unwrap())?;
+/// The shape of the collator-to-collator gossip graph to test propagation over, rooted at the
+/// block-producing validator. Configurable via `PROPAGATION_TEST_TOPOLOGY`.
+#[derive(Clone, Copy, Debug)]
+enum Topology {
+	/// Each collator only bootnodes off the previous one: `validator - c0 - c1 - ... - cN`.
+	LinearChain,
+	/// Like [`Topology::LinearChain`], but the last collator also bootnodes off the validator,
+	/// closing the loop.
+	Ring,
+	/// Every collator bootnodes directly off the validator.
+	Star,
+	/// Every collator bootnodes off `degree` peers chosen at random from the validator and the
+	/// collators started before it, using `seed` so a given configuration is reproducible.
+	KRegularMesh { degree: usize, seed: u64 },
+}
+
+impl Topology {
+	fn from_env() -> Self {
+		match std::env::var("PROPAGATION_TEST_TOPOLOGY").as_deref() {
+			Ok("ring") => Self::Ring,
+			Ok("star") => Self::Star,
+			Ok("mesh") => Self::KRegularMesh {
+				degree: env_var_or("PROPAGATION_TEST_MESH_DEGREE", 3),
+				seed: env_var_or("PROPAGATION_TEST_SEED", 0),
+			},
+			_ => Self::LinearChain,
+		}
+	}
+
+	/// For each collator index, the indices of the peers it should bootnode off of. An entry of
+	/// [`VALIDATOR`] refers to the block-producing validator rather than another collator.
+	///
+	/// Every peer listed for collator `i` is either [`VALIDATOR`] or a collator with an index
+	/// smaller than `i`, so that the graph is acyclic and hop distances from the validator can be
+	/// computed with a single forward pass in [`hop_distances`].
+	fn adjacency(&self, node_count: usize) -> Vec<Vec<usize>> {
+		match *self {
+			Self::LinearChain =>
+				(0..node_count).map(|i| vec![if i == 0 { VALIDATOR } else { i - 1 }]).collect(),
+			Self::Ring => {
+				let mut adjacency: Vec<Vec<usize>> = Self::LinearChain.adjacency(node_count);
+				if let Some(last) = adjacency.last_mut() {
+					last.push(VALIDATOR);
+				}
+				adjacency
+			},
+			Self::Star => (0..node_count).map(|_| vec![VALIDATOR]).collect(),
+			Self::KRegularMesh { degree, seed } => {
+				let mut rng = StdRng::seed_from_u64(seed);
+				(0..node_count)
+					.map(|i| {
+						let mut candidates: Vec<usize> =
+							std::iter::once(VALIDATOR).chain(0..i).collect();
+						shuffle(&mut candidates, &mut rng);
+						candidates.truncate(degree.max(1).min(candidates.len()));
+						candidates
+					})
+					.collect()
+			},
+		}
+	}
+}
+
+/// A minimal Fisher-Yates shuffle, to keep this test harness free of an extra `rand` feature flag
+/// for `SliceRandom`.
+fn shuffle<T>(slice: &mut [T], rng: &mut StdRng) {
+	use rand::Rng;
+	for i in (1..slice.len()).rev() {
+		slice.swap(i, rng.gen_range(0..=i));
+	}
+}
+
+/// Compute each collator's hop distance from the validator, given its [`Topology::adjacency`].
+fn hop_distances(adjacency: &[Vec<usize>]) -> Vec<usize> {
+	let mut distance = vec![0usize; adjacency.len()];
+	for (i, peers) in adjacency.iter().enumerate() {
+		distance[i] = peers
+			.iter()
+			.map(|&peer| if peer == VALIDATOR { 1 } else { distance[peer] + 1 })
+			.min()
+			.expect("every collator bootnodes off at least one peer");
+	}
+	distance
+}
+
+/// Parameters of the propagation-time test, overridable through environment variables so the
+/// same harness can be driven across topologies, node counts, and peer limits without code
+/// changes.
+struct PropagationTestConfig {
+	topology: Topology,
+	node_count: usize,
+	in_peers: u32,
+	out_peers: u32,
+}
+
+impl PropagationTestConfig {
+	fn from_env() -> Self {
+		Self {
+			topology: Topology::from_env(),
+			node_count: env_var_or("PROPAGATION_TEST_NODE_COUNT", 20),
+			in_peers: env_var_or("PROPAGATION_TEST_IN_PEERS", 3),
+			out_peers: env_var_or("PROPAGATION_TEST_OUT_PEERS", 3),
+		}
+	}
+}
+
+fn env_var_or<T: FromStr>(key: &str, default: T) -> T {
+	std::env::var(key).ok().and_then(|value| value.parse().ok()).unwrap_or(default)
+}
+
+async fn initialize_network(
+	config: &PropagationTestConfig,
+) -> Result<NetworkActors, anyhow::Error> {
+	log::info!("Spawning network");
 
 	let images = zombienet_sdk::environment::get_images_from_env();
 	log::info!("Using images: {images:?}");
 
-	// Spawn network.
+	// Network setup:
+	// - relaychain Nodes:
+	// 	 - alice
+	// 	 - bob
+	// - parachain Nodes:
+	//   - 1 validator
+	//   - `config.node_count` collators, bootnode-connected per `config.topology`
+	let network_config = NetworkConfigBuilder::new()
+		.with_relaychain(|r| {
+			r.with_chain("rococo-local")
+				.with_default_command("polkadot")
+				.with_default_image(images.polkadot.as_str())
+				.with_default_args(vec![("-lparachain=debug").into()])
+				.with_default_resources(|resources| {
+					resources.with_request_cpu(2).with_request_memory("2G")
+				})
+				.with_node(|node| node.with_name("alice"))
+				.with_node(|node| node.with_name("bob"))
+		})
+		.with_parachain(|p| {
+			p.with_id(PARA_ID)
+				.with_default_command("polkadot-parachain")
+				.with_default_image(images.cumulus.as_str())
+				.with_default_args(vec![("-lparachain=debug").into()])
+				.with_collator(|n| {
+					n.with_name("validator").validator(true).with_args(vec![
+						("--in-peers", config.in_peers.to_string()).into(),
+						("--out-peers", config.out_peers.to_string()).into(),
+						("--relay-chain-rpc-url", "{{ZOMBIE:alice:ws_uri}}").into(),
+					])
+				})
+		})
+		.with_global_settings(|global_settings| match std::env::var("ZOMBIENET_SDK_BASE_DIR") {
+			Ok(val) => global_settings.with_base_dir(val),
+			_ => global_settings,
+		})
+		.build()
+		.map_err(|e| {
+			let errs = e.into_iter().map(|e| e.to_string()).collect::<Vec<_>>().join(" ");
+			anyhow!("config errs: {errs}")
+		})?;
+
+	// Spawn network
 	let spawn_fn = zombienet_sdk::environment::get_spawn_fn();
-	let mut network = spawn_fn(config).await?;
+	let mut network = spawn_fn(network_config).await?;
 
-	// Sparsely connected network of many nodes.
-	let mut collators = Vec::new();
 	let validator = network.get_node("validator")?.clone();
-	let mut peer = validator.clone();
-	for i in 0..20 {
-		let collator =
-			add_sparsely_connected_collator(&mut network, &images, format!("collator{i}"), peer)
-				.await?;
-		collators.push(collator.clone());
-		peer = collator;
+	let adjacency = config.topology.adjacency(config.node_count);
+
+	let mut collators: Vec<NetworkNode> = Vec::with_capacity(config.node_count);
+	for (i, peers) in adjacency.iter().enumerate() {
+		let bootnodes = peers
+			.iter()
+			.map(|&peer| {
+				if peer == VALIDATOR { validator.multiaddr() } else { collators[peer].multiaddr() }
+			})
+			.collect();
+		let collator = add_sparsely_connected_collator(
+			&mut network,
+			&images,
+			format!("collator{i}"),
+			bootnodes,
+			config,
+		)
+		.await?;
+		collators.push(collator);
 	}
-	log::info!("Added sparsely connected collators");
+	log::info!("Added {} collators in a {:?} topology", config.node_count, config.topology);
+
+	let distance = hop_distances(&adjacency);
 
-	Ok(NetworkActors { network, validator, collators })
+	Ok(NetworkActors { network, validator, collators, distance })
 }
 
 async fn add_sparsely_connected_collator(
 	network: &mut Network<LocalFileSystem>,
 	images: &zombienet_sdk::environment::Images,
 	name: String,
-	peer: NetworkNode,
+	bootnodes: Vec<String>,
+	config: &PropagationTestConfig,
 ) -> Result<NetworkNode, anyhow::Error> {
+	let mut args = vec![
+		"-lparachain=debug".into(),
+		("--in-peers", config.in_peers.to_string()).into(),
+		("--out-peers", config.out_peers.to_string()).into(),
+	];
+	args.extend(bootnodes.into_iter().map(|addr| ("--bootnodes", addr).into()));
+
 	network
 		.add_collator(
 			&name,
 			AddCollatorOptions {
 				command: Some("polkadot-parachain".try_into().unwrap()),
 				image: Some(images.cumulus.as_str().try_into().unwrap()),
-				args: vec![
-					"-lparachain=debug".into(),
-					("--in-peers", "3").into(),
-					("--out-peers", "3").into(),
-					("--bootnodes", peer.multiaddr()).into(),
-				],
+				args,
 				..Default::default()
 			},
 			PARA_ID,
 		)
 		.await?;
-	network.get_node(&name).cloned()
+	network.get_node(&name).cloned().map_err(Into::into)
 }
 
 struct NetworkActors {
 	network: Network<LocalFileSystem>,
 	validator: NetworkNode,
 	collators: Vec<NetworkNode>,
+	/// `distance[i]` is collator `i`'s hop distance from the validator in the gossip topology.
+	distance: Vec<usize>,
 }
 
 /// Wait for all of the nodes to reach consensus on the same block height.
@@ -201,3 +400,28 @@ async fn wait_next_block(nodes: &[NetworkNode], block_height: f64) -> Result<(),
 		tokio::time::sleep(Duration::from_millis(50)).await;
 	}
 }
+
+/// Wait for each node to individually advance beyond `block_height`, recording the elapsed time
+/// since `start` at which each one did so, so propagation latency can be attributed per node
+/// rather than only as a single aggregate "everyone caught up" number.
+async fn wait_next_block_per_node(
+	nodes: &[NetworkNode],
+	block_height: f64,
+	start: Instant,
+) -> Result<Vec<f64>, anyhow::Error> {
+	let mut crossed: Vec<Option<f64>> = vec![None; nodes.len()];
+	loop {
+		let best_blocks =
+			futures::future::try_join_all(nodes.iter().map(|node| node.reports(BEST_BLOCK_METRIC)))
+				.await?;
+		for (slot, &height) in crossed.iter_mut().zip(best_blocks.iter()) {
+			if slot.is_none() && height > block_height {
+				*slot = Some(start.elapsed().as_secs_f64());
+			}
+		}
+		if crossed.iter().all(Option::is_some) {
+			return Ok(crossed.into_iter().map(|elapsed| elapsed.expect("checked above")).collect());
+		}
+		tokio::time::sleep(Duration::from_millis(50)).await;
+	}
+}
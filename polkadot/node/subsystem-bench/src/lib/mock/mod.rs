@@ -17,6 +17,13 @@
 use polkadot_node_subsystem::HeadSupportsParachains;
 use polkadot_node_subsystem_types::Hash;
 use sp_consensus::SyncOracle;
+use std::{
+	collections::HashSet,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
 
 pub mod av_store;
 pub mod chain_api;
@@ -33,10 +40,42 @@ impl HeadSupportsParachains for AlwaysSupportsParachains {
 	}
 }
 
+/// A [`HeadSupportsParachains`] whose answer is configurable, for benchmarks that need to
+/// exercise the startup/catch-up code paths instead of always reporting support.
+///
+/// Heads present in `supported` are always reported as supported; every other head falls back to
+/// `default`.
+#[derive(Clone, Default)]
+pub struct ConfigurableSupportsParachains {
+	pub supported: HashSet<Hash>,
+	pub default: bool,
+}
+
+impl ConfigurableSupportsParachains {
+	/// A `ConfigurableSupportsParachains` behaving like [`AlwaysSupportsParachains`].
+	pub fn always() -> Self {
+		Self { supported: HashSet::new(), default: true }
+	}
+}
+
+#[async_trait::async_trait]
+impl HeadSupportsParachains for ConfigurableSupportsParachains {
+	async fn head_supports_parachains(&self, head: &Hash) -> bool {
+		self.supported.contains(head) || self.default
+	}
+}
+
 // An orchestra with dummy subsystems
 #[macro_export]
 macro_rules! dummy_builder {
-	($spawn_task_handle: ident, $metrics: ident) => {{
+	($spawn_task_handle: ident, $metrics: ident) => {
+		$crate::dummy_builder!(
+			$spawn_task_handle,
+			$metrics,
+			$crate::mock::AlwaysSupportsParachains {}
+		)
+	};
+	($spawn_task_handle: ident, $metrics: ident, $supports_parachains: expr) => {{
 		use $crate::mock::dummy::*;
 
 		// Initialize a mock overseer.
@@ -69,7 +108,7 @@ macro_rules! dummy_builder {
 			.span_per_active_leaf(Default::default())
 			.active_leaves(Default::default())
 			.metrics($metrics)
-			.supports_parachains(AlwaysSupportsParachains {})
+			.supports_parachains($supports_parachains)
 			.spawner(SpawnGlue($spawn_task_handle))
 	}};
 }
@@ -86,3 +125,22 @@ impl SyncOracle for TestSyncOracle {
 		unimplemented!("not used by subsystem benchmarks")
 	}
 }
+
+/// A [`SyncOracle`] whose major-syncing/offline state is controlled through shared atomics, for
+/// benchmarks that need to exercise subsystem behavior while the node reports itself as
+/// major-syncing or offline.
+#[derive(Clone, Default)]
+pub struct ConfigurableSyncOracle {
+	pub major_syncing: Arc<AtomicBool>,
+	pub offline: Arc<AtomicBool>,
+}
+
+impl SyncOracle for ConfigurableSyncOracle {
+	fn is_major_syncing(&self) -> bool {
+		self.major_syncing.load(Ordering::Relaxed)
+	}
+
+	fn is_offline(&self) -> bool {
+		self.offline.load(Ordering::Relaxed)
+	}
+}
@@ -92,12 +92,7 @@ fn create_messages(msg_len: usize, n_msgs: usize) -> Vec<Vec<u8>> {
 mod benchmarks {
 	use super::*;
 
-	#[benchmark]
-	fn enact_candidate(u: Linear<0, 2>, h: Linear<0, 2>, c: Linear<0, 1>) {
-		let para = 42_u32.into(); // not especially important.
-
-		let max_len = mq::MaxMessageLenOf::<T>::get() as usize;
-
+	fn setup_session<T: crate::hrmp::pallet::Config + configuration::Config>() -> (u32, u32) {
 		let config = configuration::ActiveConfig::<T>::get();
 		let n_validators = config.max_validators.unwrap_or(500);
 		let validators = generate_validator_pairs::<T>(n_validators);
@@ -110,15 +105,19 @@ mod benchmarks {
 			None,
 		);
 		let backing_group_size = config.scheduler_params.max_validators_per_core.unwrap_or(5);
-		let head_data = HeadData(vec![0xFF; 1024]);
 
-		let relay_parent_number = BlockNumberFor::<T>::from(10_u32);
-		let commitments = create_candidate_commitments::<T>(para, head_data, max_len, u, h, c != 0);
-		let backers = bitvec![u8, Lsb0; 1; backing_group_size as usize];
-		let availability_votes = bitvec![u8, Lsb0; 1; n_validators as usize];
-		let core_index = CoreIndex::from(0);
-		let backing_group = GroupIndex::from(0);
+		(n_validators, backing_group_size)
+	}
 
+	fn candidate_receipt<T: crate::hrmp::pallet::Config>(
+		para: ParaId,
+		max_msg_len: usize,
+		u: u32,
+		h: u32,
+		c: u32,
+	) -> CommittedCandidateReceipt<T::Hash> {
+		let head_data = HeadData(vec![0xFF; 1024]);
+		let commitments = create_candidate_commitments::<T>(para, head_data, max_msg_len, u, h, c != 0);
 		let descriptor = CandidateDescriptor::<T::Hash>::new(
 			para,
 			Default::default(),
@@ -131,7 +130,26 @@ mod benchmarks {
 			ValidationCode(vec![1, 2, 3]).hash(),
 		);
 
-		let receipt = CommittedCandidateReceipt::<T::Hash> { descriptor, commitments };
+		CommittedCandidateReceipt::<T::Hash> { descriptor, commitments }
+	}
+
+	// `u`/`h` cover the number of UMP/HRMP messages, and `l` covers message size: 1 measures the
+	// cheapest realistic message, and `max_len` the adversarial maximum, so that the resulting
+	// weight formula isn't fit to a single, maximal-size data point.
+	#[benchmark]
+	fn enact_candidate(u: Linear<0, 2>, h: Linear<0, 2>, c: Linear<0, 1>, l: Linear<1, 65536>) {
+		let para = 42_u32.into(); // not especially important.
+
+		let max_len = (l as usize).min(mq::MaxMessageLenOf::<T>::get() as usize);
+
+		let (n_validators, backing_group_size) = setup_session::<T>();
+
+		let relay_parent_number = BlockNumberFor::<T>::from(10_u32);
+		let receipt = candidate_receipt::<T>(para, max_len, u, h, c);
+		let backers = bitvec![u8, Lsb0; 1; backing_group_size as usize];
+		let availability_votes = bitvec![u8, Lsb0; 1; n_validators as usize];
+		let core_index = CoreIndex::from(0);
+		let backing_group = GroupIndex::from(0);
 
 		Pallet::<T>::receive_upward_messages(para, &vec![vec![0; max_len]; 1]);
 
@@ -148,6 +166,59 @@ mod benchmarks {
 		}
 	}
 
+	// Isolates the message-queue enqueueing cost from the rest of `enact_candidate` (code-upgrade
+	// scheduling, DMP/HRMP pruning, HRMP outbound queueing), so that under an adversarial mix of
+	// many small messages the queueing base weight is attributed on its own rather than folded
+	// into a single per-candidate component.
+	#[benchmark]
+	fn enqueue_upward_messages(u: Linear<0, 16>, l: Linear<1, 65536>) {
+		let para = 42_u32.into();
+		let max_len = (l as usize).min(mq::MaxMessageLenOf::<T>::get() as usize);
+		let messages = vec![vec![0_u8; max_len]; u as usize];
+
+		#[block]
+		{
+			Pallet::<T>::receive_upward_messages(para, &messages);
+		}
+	}
+
+	// Measures `b` backed candidates, each on a distinct para, enacted within the same block, to
+	// cross-check that summing `enact_candidate`'s per-candidate weight over the candidates
+	// actually included in a block tracks the real cost of including that many candidates.
+	#[benchmark]
+	fn enact_candidates_per_block(b: Linear<1, 10>) {
+		let (n_validators, backing_group_size) = setup_session::<T>();
+		let max_len = mq::MaxMessageLenOf::<T>::get() as usize;
+
+		let relay_parent_number = BlockNumberFor::<T>::from(10_u32);
+		let backers = bitvec![u8, Lsb0; 1; backing_group_size as usize];
+		let availability_votes = bitvec![u8, Lsb0; 1; n_validators as usize];
+		let core_index = CoreIndex::from(0);
+		let backing_group = GroupIndex::from(0);
+
+		let receipts: Vec<_> = (0..b)
+			.map(|i| {
+				let para: ParaId = (42_u32 + i).into();
+				Pallet::<T>::receive_upward_messages(para, &vec![vec![0; max_len]; 1]);
+				candidate_receipt::<T>(para, max_len, 1, 1, 0)
+			})
+			.collect();
+
+		#[block]
+		{
+			for receipt in receipts {
+				Pallet::<T>::enact_candidate(
+					relay_parent_number,
+					receipt,
+					backers.clone(),
+					availability_votes.clone(),
+					core_index,
+					backing_group,
+				);
+			}
+		}
+	}
+
 	impl_benchmark_test_suite! {
 		Pallet,
 		crate::mock::new_test_ext(Default::default()),
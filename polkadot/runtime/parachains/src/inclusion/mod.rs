@@ -74,6 +74,18 @@ pub trait WeightInfo {
 	/// NOTE: due to a shortcoming of the current benchmarking framework,
 	/// we use `u32` for the code upgrade, even though it is a `bool`.
 	fn enact_candidate(u: u32, h: u32, c: u32) -> Weight;
+
+	/// Weight for enqueueing `u` upward messages of length `l` bytes each, in isolation from the
+	/// rest of `enact_candidate`. This is the message-queueing component of inclusion weight,
+	/// measured separately from commitment processing so that `configuration`'s max UMP/HRMP
+	/// counts can be tuned against the two components independently.
+	fn enqueue_upward_messages(u: u32, l: u32) -> Weight;
+
+	/// Weight for enacting `b` backed candidates within a single block, each carrying a small,
+	/// fixed set of messages. Used to cross-check that per-candidate weight accounted for via
+	/// repeated calls to `enact_candidate` scales linearly with the number of backed candidates
+	/// actually included per block.
+	fn enact_candidates_per_block(b: u32) -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -81,12 +93,28 @@ impl WeightInfo for TestWeightInfo {
 	fn enact_candidate(_u: u32, _h: u32, _c: u32) -> Weight {
 		Weight::zero()
 	}
+
+	fn enqueue_upward_messages(_u: u32, _l: u32) -> Weight {
+		Weight::zero()
+	}
+
+	fn enact_candidates_per_block(_b: u32) -> Weight {
+		Weight::zero()
+	}
 }
 
 impl WeightInfo for () {
 	fn enact_candidate(_u: u32, _h: u32, _c: u32) -> Weight {
 		Weight::zero()
 	}
+
+	fn enqueue_upward_messages(_u: u32, _l: u32) -> Weight {
+		Weight::zero()
+	}
+
+	fn enact_candidates_per_block(_b: u32) -> Weight {
+		Weight::zero()
+	}
 }
 
 /// Maximum value that `config.max_upward_message_size` can be set to.
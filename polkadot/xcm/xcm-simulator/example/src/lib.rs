@@ -16,10 +16,13 @@
 
 mod parachain;
 mod relay_chain;
+mod xcm_trace;
 
 #[cfg(test)]
 mod tests;
 
+pub use xcm_trace::{trace_xcm, XcmTraceStep};
+
 use sp_runtime::BuildStorage;
 use sp_tracing::{self, tracing_subscriber};
 use std::{
@@ -32,6 +35,8 @@ use xcm_executor::traits::ConvertLocation;
 use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain, TestExt};
 
 pub const ALICE: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([1u8; 32]);
+/// The relay chain's sudo key, used to rehearse governance-initiated parachain upgrades.
+pub const ADMIN: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([42u8; 32]);
 pub const INITIAL_BALANCE: u128 = 1_000_000_000;
 
 /// A reusable log capturing struct for unit tests.
@@ -210,6 +215,7 @@ pub fn relay_ext() -> sp_io::TestExternalities {
 	pallet_balances::GenesisConfig::<Runtime> {
 		balances: vec![
 			(ALICE, INITIAL_BALANCE),
+			(ADMIN, INITIAL_BALANCE),
 			(child_account_id(1), INITIAL_BALANCE),
 			(child_account_id(2), INITIAL_BALANCE),
 		],
@@ -218,6 +224,8 @@ pub fn relay_ext() -> sp_io::TestExternalities {
 	.assimilate_storage(&mut t)
 	.unwrap();
 
+	pallet_sudo::GenesisConfig::<Runtime> { key: Some(ADMIN) }.assimilate_storage(&mut t).unwrap();
+
 	let mut ext = sp_io::TestExternalities::new(t);
 	ext.execute_with(|| {
 		System::set_block_number(1);
@@ -229,3 +237,22 @@ pub fn relay_ext() -> sp_io::TestExternalities {
 
 pub type RelayChainPalletXcm = pallet_xcm::Pallet<relay_chain::Runtime>;
 pub type ParachainPalletXcm = pallet_xcm::Pallet<parachain::Runtime>;
+
+/// Trace a message's execution on the relay chain. Call from inside `Relay::execute_with`.
+pub fn relay_trace_xcm(
+	origin: impl Into<xcm::latest::Location>,
+	message_id: xcm::latest::XcmHash,
+	message: xcm::latest::Xcm<relay_chain::RuntimeCall>,
+) -> (Vec<XcmTraceStep<relay_chain::RuntimeCall>>, xcm::latest::Outcome) {
+	trace_xcm::<relay_chain::XcmConfig>(origin, message_id, message)
+}
+
+/// Trace a message's execution on a parachain. Call from inside `ParaA::execute_with` (or
+/// `ParaB::execute_with`).
+pub fn parachain_trace_xcm(
+	origin: impl Into<xcm::latest::Location>,
+	message_id: xcm::latest::XcmHash,
+	message: xcm::latest::Xcm<parachain::RuntimeCall>,
+) -> (Vec<XcmTraceStep<parachain::RuntimeCall>>, xcm::latest::Outcome) {
+	trace_xcm::<parachain::XcmConfig>(origin, message_id, message)
+}
@@ -123,6 +123,12 @@ impl pallet_xcm::Config for Runtime {
 
 impl origin::Config for Runtime {}
 
+impl pallet_sudo::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type WeightInfo = ();
+}
+
 type Block = frame_system::mocking::MockBlock<Runtime>;
 
 parameter_types! {
@@ -176,5 +182,6 @@ construct_runtime!(
 		XcmPallet: pallet_xcm,
 		Uniques: pallet_uniques,
 		MessageQueue: pallet_message_queue,
+		Sudo: pallet_sudo,
 	}
 );
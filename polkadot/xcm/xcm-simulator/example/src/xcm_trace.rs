@@ -0,0 +1,112 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Instruction-level execution tracing for the XCM executor, built on top of the accessors
+//! `XcmExecutor` exposes under `cfg(any(test, feature = "runtime-benchmarks"))` for benchmarking
+//! (`holding`, `origin`, `topic`, `bench_process`, `bench_post_process`). Requires this crate's
+//! `xcm-executor` dependency to have its `runtime-benchmarks` feature enabled when used outside
+//! of `xcm-executor`'s own test builds. Lets test authors see, instruction by instruction, why a
+//! message trapped assets or returned an unexpected `Outcome`, instead of only observing the
+//! final result.
+
+use sp_weights::Weight;
+use xcm::latest::prelude::*;
+use xcm_executor::{traits::WeightBounds, AssetsInHolding, Config, XcmExecutor};
+
+/// A single step of an instruction-level XCM execution trace.
+#[derive(Debug, Clone)]
+pub struct XcmTraceStep<Call> {
+	/// The instruction this step corresponds to.
+	pub instruction: Instruction<Call>,
+	/// The weight the executor charged for this instruction.
+	pub weight: Weight,
+	/// The holding register immediately after this instruction ran (unchanged if it didn't run).
+	pub holding: AssetsInHolding,
+	/// The XCM origin immediately after this instruction ran.
+	pub origin: Option<Location>,
+	/// The topic of the program being executed, if any.
+	pub topic: Option<XcmHash>,
+	/// Set if this instruction is the one that caused execution to fail.
+	pub error: Option<XcmError>,
+	/// Whether this instruction actually ran. `false` for every instruction after the one that
+	/// failed, so the trace still shows what was left on the program.
+	pub executed: bool,
+}
+
+/// Execute `message` against `Config`'s `XcmExecutor`, recording an [`XcmTraceStep`] for every
+/// instruction, and return the full trace alongside the [`Outcome`] `execute` would have
+/// produced.
+///
+/// This mirrors `XcmExecutor::execute` instruction-by-instruction by repeatedly calling
+/// `bench_process` on a single shared executor (so the holding register, origin, and topic
+/// persist across steps exactly as they would during normal execution), rather than modifying
+/// the executor itself.
+///
+/// Known limitation: unlike `execute`, this does not fall back to running the error handler or
+/// appendix programs after a failure, since driving those requires executor internals that
+/// aren't exposed outside the `xcm-executor` crate. For debugging why a message trapped or
+/// errored on its main instruction list, the instruction index and remaining, unexecuted
+/// instructions are what matter, and those are captured faithfully here.
+pub fn trace_xcm<C: Config>(
+	origin: impl Into<Location>,
+	message_id: XcmHash,
+	mut message: Xcm<C::RuntimeCall>,
+) -> (Vec<XcmTraceStep<C::RuntimeCall>>, Outcome) {
+	let xcm_weight = match C::Weigher::weight(&mut message) {
+		Ok(weight) => weight,
+		Err(_) => return (Vec::new(), Outcome::Error { error: XcmError::WeightNotComputable }),
+	};
+
+	let mut executor = XcmExecutor::<C>::new(origin, message_id);
+	let mut trace = Vec::with_capacity(message.0.len());
+	let mut failed = false;
+
+	for instruction in message.0 {
+		if failed {
+			trace.push(XcmTraceStep {
+				instruction,
+				weight: Weight::zero(),
+				holding: executor.holding().clone(),
+				origin: executor.origin().clone(),
+				topic: *executor.topic(),
+				error: None,
+				executed: false,
+			});
+			continue;
+		}
+
+		let mut to_weigh = instruction.clone();
+		let weight = C::Weigher::instr_weight(&mut to_weigh).unwrap_or(Weight::zero());
+
+		let result = executor.bench_process(Xcm(vec![instruction.clone()]));
+		let error = result.as_ref().err().map(|e| e.xcm_error);
+		if error.is_some() {
+			failed = true;
+		}
+
+		trace.push(XcmTraceStep {
+			instruction,
+			weight,
+			holding: executor.holding().clone(),
+			origin: executor.origin().clone(),
+			topic: *executor.topic(),
+			error,
+			executed: true,
+		});
+	}
+
+	(trace, executor.bench_post_process(xcm_weight))
+}
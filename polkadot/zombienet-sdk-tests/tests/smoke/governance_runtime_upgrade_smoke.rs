@@ -0,0 +1,121 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Governance-Authorized Runtime Upgrade Smoke Test
+//!
+//! `can_governance_authorize_upgrade` (see `parachains-runtimes-test-utils`) only checks the
+//! XCM barrier/origin logic for `authorize_upgrade` against a mock runtime. This test exercises
+//! the full two-step flow end to end: a governance call authorizes a `code_hash`, a permissionless
+//! `apply_authorized_upgrade` applies the matching wasm, and the parachain keeps producing blocks
+//! on the new `spec_version` across a session boundary.
+
+use crate::utils::initialize_network;
+use anyhow::anyhow;
+use cumulus_zombienet_sdk_helpers::{
+	assert_para_is_registered, assert_para_throughput, create_apply_authorized_upgrade_call,
+	create_authorize_upgrade_call, submit_extrinsic_and_wait_for_finalization_success,
+	wait_for_nth_session_change, wait_for_upgrade,
+};
+use polkadot_primitives::Id as ParaId;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+use zombienet_sdk::{
+	subxt::{OnlineClient, PolkadotConfig},
+	subxt_signer::sr25519::dev,
+	NetworkConfig, NetworkConfigBuilder,
+};
+
+const PARA_ID: u32 = 100;
+
+/// Smoke test that authorizes then applies a runtime upgrade via the
+/// `authorize_upgrade`/`apply_authorized_upgrade` pair, instead of the direct `set_code` path
+/// used by [`super::parachains_upgrade_smoke::parachains_upgrade_smoke_test`].
+#[tokio::test(flavor = "multi_thread")]
+async fn governance_runtime_upgrade_smoke_test() -> Result<(), anyhow::Error> {
+	let _ = env_logger::try_init_from_env(
+		env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, "info"),
+	);
+
+	let config = build_network_config()?;
+	let network = initialize_network(config).await?;
+
+	let alice = network.get_node("alice")?;
+	let alice_client: OnlineClient<PolkadotConfig> = alice.wait_client().await?;
+
+	let para_node = network.get_node("collator01")?;
+	let para_client: OnlineClient<PolkadotConfig> = para_node.wait_client().await?;
+
+	log::info!("Checking parachain {} is registered", PARA_ID);
+	assert_para_is_registered(&alice_client, ParaId::from(PARA_ID), 75).await?;
+
+	log::info!("Checking parachain {} is producing blocks (phase 1)", PARA_ID);
+	assert_para_throughput(&alice_client, 30, [(ParaId::from(PARA_ID), 10..100)]).await?;
+
+	let current_spec_version = para_client.backend().current_runtime_version().await?.spec_version;
+	let expected_spec_version = current_spec_version + 1;
+	log::info!("Current runtime spec version: {current_spec_version}, upgrading to {expected_spec_version}");
+
+	// Re-apply the current code, but rely on it having been built with the spec version bumped by
+	// one, mirroring the other smoke tests' `_spec_version_incremented` wasm artifacts.
+	let code_key = sp_core::storage::well_known_keys::CODE;
+	let new_code = para_client
+		.storage()
+		.at_latest()
+		.await?
+		.fetch_raw(code_key)
+		.await?
+		.ok_or_else(|| anyhow!("Failed to fetch current runtime code"))?;
+	let code_hash = BlakeTwo256::hash(&new_code);
+
+	log::info!("Authorizing upgrade to code hash {code_hash:?}");
+	let authorize_call = create_authorize_upgrade_call(code_hash);
+	submit_extrinsic_and_wait_for_finalization_success(&para_client, &authorize_call, &dev::alice())
+		.await?;
+
+	log::info!("Applying the authorized upgrade");
+	let apply_call = create_apply_authorized_upgrade_call(&new_code);
+	submit_extrinsic_and_wait_for_finalization_success(&para_client, &apply_call, &dev::bob())
+		.await?;
+
+	log::info!("Waiting for the upgrade to take effect");
+	wait_for_upgrade(para_client.clone(), expected_spec_version).await?;
+
+	// Cross a session boundary on the relay chain to make sure the upgraded parachain keeps
+	// being included afterwards, not just in the block that enacted the upgrade.
+	let mut relay_blocks = alice_client.blocks().subscribe_finalized().await?;
+	wait_for_nth_session_change(&mut relay_blocks, 1).await?;
+
+	log::info!("Checking parachain {} is producing blocks (phase 2 - after upgrade)", PARA_ID);
+	assert_para_throughput(&alice_client, 10, [(ParaId::from(PARA_ID), 4..50)]).await?;
+
+	log::info!("Test finished successfully");
+	Ok(())
+}
+
+fn build_network_config() -> Result<NetworkConfig, anyhow::Error> {
+	let images = zombienet_sdk::environment::get_images_from_env();
+
+	NetworkConfigBuilder::new()
+		.with_relaychain(|r| {
+			r.with_chain("rococo-local")
+				.with_default_command("polkadot")
+				.with_default_image(images.polkadot.as_str())
+				.with_node(|node| node.with_name("alice"))
+				.with_node(|node| node.with_name("bob"))
+		})
+		.with_parachain(|p| {
+			p.with_id(PARA_ID)
+				.cumulus_based(true)
+				.with_default_command("polkadot-parachain")
+				.with_default_image(images.cumulus.as_str())
+				.with_collator(|n| n.with_name("collator01"))
+		})
+		.with_global_settings(|global_settings| match std::env::var("ZOMBIENET_SDK_BASE_DIR") {
+			Ok(val) => global_settings.with_base_dir(val),
+			_ => global_settings,
+		})
+		.build()
+		.map_err(|e| {
+			let errs = e.into_iter().map(|e| e.to_string()).collect::<Vec<_>>().join(" ");
+			anyhow!("config errs: {errs}")
+		})
+}
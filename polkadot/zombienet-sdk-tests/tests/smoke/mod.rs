@@ -13,5 +13,8 @@ mod coretime_smoke;
 #[cfg(feature = "zombie-ci")]
 mod deregister_register_validator;
 
+#[cfg(feature = "zombie-ci")]
+mod governance_runtime_upgrade_smoke;
+
 #[cfg(feature = "zombie-ci")]
 mod parachains_smoke;
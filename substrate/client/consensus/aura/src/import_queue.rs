@@ -23,8 +23,12 @@ use crate::{
 	LOG_TARGET,
 };
 use codec::Codec;
-use log::{debug, info, trace};
-use prometheus_endpoint::Registry;
+use futures_timer::Delay;
+use log::{debug, error, info, trace};
+use prometheus_endpoint::{
+	exponential_buckets, histogram_opts, register, Counter, CounterVec, Histogram, Opts,
+	PrometheusError, Registry, U64,
+};
 use sc_client_api::{backend::AuxStore, BlockOf, UsageProvider};
 use sc_consensus::{
 	block_import::{BlockImport, BlockImportParams, ForkChoiceStrategy},
@@ -33,19 +37,26 @@ use sc_consensus::{
 };
 use sc_consensus_slots::{check_equivocation, CheckedHeader, InherentDataProviderExt};
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG, CONSENSUS_TRACE};
+use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder as BlockBuilderApi;
 use sp_blockchain::{HeaderBackend, HeaderMetadata};
-use sp_consensus::Error as ConsensusError;
-use sp_consensus_aura::{inherents::AuraInherentData, AuraApi};
+use sp_consensus::{CanAuthorWith, Error as ConsensusError};
+use sp_consensus_aura::{inherents::AuraInherentData, AuraApi, EquivocationProof};
 use sp_consensus_slots::Slot;
 use sp_core::crypto::Pair;
 use sp_inherents::{CreateInherentDataProviders, InherentDataProvider as _};
 use sp_runtime::{
+	generic::BlockId,
 	traits::{Block as BlockT, Header, NumberFor},
 	DigestItem,
 };
-use std::{fmt::Debug, sync::Arc};
+use std::{
+	collections::{BTreeMap, HashSet},
+	fmt::Debug,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 
 /// check a header has been signed by the right key. If the slot is too far in the future, an error
 /// will be returned. If it's successful, returns the pre-header and the digest item
@@ -57,13 +68,17 @@ fn check_header<C, B: BlockT, P: Pair>(
 	slot_now: Slot,
 	header: B::Header,
 	hash: B::Hash,
+	parent_hash: B::Hash,
 	authorities: &[AuthorityId<P>],
 	check_for_equivocation: CheckForEquivocation,
+	offchain_tx_pool_factory: Option<&OffchainTransactionPoolFactory<B>>,
+	metrics: Option<&Metrics>,
 ) -> Result<CheckedHeader<B::Header, (Slot, DigestItem)>, Error<B>>
 where
-	P::Public: Codec,
+	P::Public: Codec + Debug,
 	P::Signature: Codec,
-	C: sc_client_api::backend::AuxStore,
+	C: sc_client_api::backend::AuxStore + ProvideRuntimeApi<B>,
+	C::Api: AuraApi<B, AuthorityId<P>>,
 {
 	let check_result =
 		crate::standalone::check_header_slot_and_seal::<B, P>(slot_now, header, authorities);
@@ -84,6 +99,17 @@ where
 						equivocation_proof.first_header.hash(),
 						equivocation_proof.second_header.hash(),
 					);
+
+					if let Some(metrics) = metrics {
+						metrics.equivocations.inc();
+					}
+
+					report_equivocation::<B, C, P>(
+						client,
+						offchain_tx_pool_factory,
+						parent_hash,
+						equivocation_proof,
+					);
 				}
 			}
 
@@ -91,49 +117,314 @@ where
 		},
 		Err(SealVerificationError::Deferred(header, slot)) =>
 			Ok(CheckedHeader::Deferred(header, slot)),
-		Err(SealVerificationError::Unsealed) => Err(Error::HeaderUnsealed(hash)),
-		Err(SealVerificationError::BadSeal) => Err(Error::HeaderBadSeal(hash)),
-		Err(SealVerificationError::BadSignature) => Err(Error::BadSignature(hash)),
-		Err(SealVerificationError::SlotAuthorNotFound) => Err(Error::SlotAuthorNotFound),
-		Err(SealVerificationError::InvalidPreDigest(e)) => Err(Error::from(e)),
+		Err(SealVerificationError::Unsealed) => {
+			if let Some(metrics) = metrics {
+				metrics.rejected_headers.with_label_values(&["unsealed"]).inc();
+			}
+			Err(Error::HeaderUnsealed(hash))
+		},
+		Err(SealVerificationError::BadSeal) => {
+			if let Some(metrics) = metrics {
+				metrics.rejected_headers.with_label_values(&["bad_seal"]).inc();
+			}
+			Err(Error::HeaderBadSeal(hash))
+		},
+		Err(SealVerificationError::BadSignature) => {
+			if let Some(metrics) = metrics {
+				metrics.rejected_headers.with_label_values(&["bad_signature"]).inc();
+			}
+			Err(Error::BadSignature(hash))
+		},
+		Err(SealVerificationError::SlotAuthorNotFound) => {
+			if let Some(metrics) = metrics {
+				metrics.rejected_headers.with_label_values(&["slot_author_not_found"]).inc();
+			}
+			Err(Error::SlotAuthorNotFound)
+		},
+		Err(SealVerificationError::InvalidPreDigest(e)) => {
+			if let Some(metrics) = metrics {
+				metrics.rejected_headers.with_label_values(&["invalid_pre_digest"]).inc();
+			}
+			Err(Error::from(e))
+		},
+	}
+}
+
+/// Turn a detected equivocation into an on-chain offence report, analogous to BABE/GRANDPA.
+///
+/// This fetches a key ownership proof for the offender from the runtime and, if one is found,
+/// submits an unsigned extrinsic reporting the equivocation. Doing so requires an offchain
+/// transaction pool; if none was configured, or the runtime has no key ownership proof for the
+/// offender (e.g. it is not actually part of the current authority set), this is a no-op beyond
+/// the `info!` log the caller already emitted for the equivocation itself.
+fn report_equivocation<B, C, P>(
+	client: &C,
+	offchain_tx_pool_factory: Option<&OffchainTransactionPoolFactory<B>>,
+	at_hash: B::Hash,
+	equivocation_proof: EquivocationProof<B::Header, AuthorityId<P>>,
+) where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: AuraApi<B, AuthorityId<P>>,
+	P: Pair,
+	P::Public: Codec + Debug,
+{
+	let Some(offchain_tx_pool_factory) = offchain_tx_pool_factory else {
+		debug!(
+			target: LOG_TARGET,
+			"No offchain transaction pool configured; not reporting the equivocation on-chain.",
+		);
+		return
+	};
+
+	let offender = equivocation_proof.offender.clone();
+	let slot = equivocation_proof.slot;
+
+	let mut runtime_api = client.runtime_api();
+	runtime_api.register_extension(offchain_tx_pool_factory.offchain_transaction_pool(at_hash));
+
+	let key_owner_proof = match runtime_api
+		.generate_key_ownership_proof(at_hash, slot, offender.clone())
+	{
+		Ok(Some(key_owner_proof)) => key_owner_proof,
+		Ok(None) => {
+			debug!(
+				target: LOG_TARGET,
+				"Equivocation offender {:?} has no key ownership proof at slot {:?}; skipping report.",
+				offender, slot,
+			);
+			return
+		},
+		Err(err) => {
+			error!(
+				target: LOG_TARGET,
+				"Failed to generate key ownership proof for equivocation offender {:?}: {:?}",
+				offender, err,
+			);
+			return
+		},
+	};
+
+	match runtime_api.submit_report_equivocation_unsigned_extrinsic(
+		at_hash,
+		equivocation_proof,
+		key_owner_proof,
+	) {
+		Ok(Some(())) => info!(
+			target: LOG_TARGET,
+			"Submitted an equivocation report for offender {:?} at slot {:?}.", offender, slot,
+		),
+		Ok(None) => debug!(
+			target: LOG_TARGET,
+			"Runtime declined to accept the equivocation report for offender {:?} at slot {:?}.",
+			offender, slot,
+		),
+		Err(err) => error!(
+			target: LOG_TARGET,
+			"Failed to submit the equivocation report for offender {:?}: {:?}",
+			offender, err,
+		),
+	}
+}
+
+/// Upper bound on the number of headers [`DeferredHeaders`] will hold at once, so that a burst of
+/// near-future blocks from loosely-synchronized peers can't grow the pending set without limit.
+const MAX_DEFERRED_HEADERS: usize = 256;
+
+/// Headers whose slot is still ahead of our wall clock, parked by [`AuraVerifier::verify`] until
+/// it is time to re-verify them instead of being rejected outright.
+///
+/// Entries are keyed by the slot they are waiting for, so that stale ones - whose slot has already
+/// passed without the header being picked back up, e.g. because its wait was abandoned - can be
+/// evicted cheaply.
+struct DeferredHeaders<B: BlockT> {
+	pending: parking_lot::Mutex<BTreeMap<Slot, HashSet<B::Hash>>>,
+}
+
+impl<B: BlockT> DeferredHeaders<B> {
+	fn new() -> Self {
+		Self { pending: parking_lot::Mutex::new(BTreeMap::new()) }
+	}
+
+	/// The number of headers currently parked, across all slots.
+	fn len(&self) -> usize {
+		self.pending.lock().values().map(HashSet::len).sum()
+	}
+
+	/// Evict every entry waiting on a slot at or before `slot_now`: their wait has already elapsed
+	/// without the header being re-verified, so whatever was going to wake them up didn't.
+	fn evict_stale(&self, slot_now: Slot) {
+		self.pending.lock().retain(|&slot, _| slot > slot_now);
+	}
+
+	/// Try to park `hash` until `slot`, returning `false` without parking it if the pending set is
+	/// already at [`MAX_DEFERRED_HEADERS`].
+	fn try_park(&self, slot: Slot, hash: B::Hash) -> bool {
+		let mut pending = self.pending.lock();
+		if pending.values().map(HashSet::len).sum::<usize>() >= MAX_DEFERRED_HEADERS {
+			return false
+		}
+		pending.entry(slot).or_default().insert(hash);
+		true
+	}
+
+	/// Stop tracking `hash` as parked for `slot`, e.g. once it has been re-verified.
+	fn unpark(&self, slot: Slot, hash: &B::Hash) {
+		let mut pending = self.pending.lock();
+		if let Some(hashes) = pending.get_mut(&slot) {
+			hashes.remove(hash);
+			if hashes.is_empty() {
+				pending.remove(&slot);
+			}
+		}
+	}
+}
+
+/// Unparks a header from its [`DeferredHeaders`] on drop, so it stops being counted as pending
+/// even if re-verification is abandoned, e.g. the containing future is cancelled.
+struct ParkGuard<'a, B: BlockT> {
+	deferred_headers: &'a DeferredHeaders<B>,
+	slot: Slot,
+	hash: B::Hash,
+}
+
+impl<'a, B: BlockT> Drop for ParkGuard<'a, B> {
+	fn drop(&mut self) {
+		self.deferred_headers.unpark(self.slot, &self.hash);
+	}
+}
+
+/// Prometheus metrics for [`AuraVerifier`].
+#[derive(Clone)]
+struct Metrics {
+	/// Total time spent in [`Verifier::verify`], from receiving the block to the final verdict.
+	verify_time: Histogram,
+	/// Time spent checking that the block's inherents match what the seal claims.
+	check_inherents_time: Histogram,
+	/// Number of headers whose slot was too far in the future and had to be parked for
+	/// re-verification, see [`DeferredHeaders`].
+	deferred_headers: Counter<U64>,
+	/// Header rejections, broken out by `reason`.
+	rejected_headers: CounterVec<U64>,
+	/// Equivocations detected while verifying headers.
+	equivocations: Counter<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			verify_time: register(
+				Histogram::with_opts(histogram_opts!(
+					"substrate_aura_verify_time",
+					"Time taken to verify an Aura block, in seconds",
+					exponential_buckets(0.001, 2.0, 16).unwrap(),
+				))?,
+				registry,
+			)?,
+			check_inherents_time: register(
+				Histogram::with_opts(histogram_opts!(
+					"substrate_aura_check_inherents_time",
+					"Time taken to check a block's inherents against its seal, in seconds",
+					exponential_buckets(0.001, 2.0, 16).unwrap(),
+				))?,
+				registry,
+			)?,
+			deferred_headers: register(
+				Counter::new(
+					"substrate_aura_deferred_headers_total",
+					"Number of headers parked for re-verification because their slot was too \
+					 far in the future",
+				)?,
+				registry,
+			)?,
+			rejected_headers: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_aura_rejected_headers_total",
+						"Number of headers rejected during verification, by reason",
+					),
+					&["reason"],
+				)?,
+				registry,
+			)?,
+			equivocations: register(
+				Counter::new(
+					"substrate_aura_equivocations_total",
+					"Number of equivocations detected while verifying headers",
+				)?,
+				registry,
+			)?,
+		})
 	}
 }
 
 /// A verifier for Aura blocks.
-pub struct AuraVerifier<C, P: Pair, CIDP, B: BlockT> {
+pub struct AuraVerifier<C, P: Pair, CIDP, B: BlockT, CAW> {
 	client: Arc<C>,
 	create_inherent_data_providers: CIDP,
 	check_for_equivocation: CheckForEquivocation,
 	telemetry: Option<TelemetryHandle>,
 	authorities_tracker: Arc<AuthoritiesTracker<P, B, C>>,
+	offchain_tx_pool_factory: Option<OffchainTransactionPoolFactory<B>>,
+	can_author_with: CAW,
+	deferred_headers: Arc<DeferredHeaders<B>>,
+	metrics: Option<Metrics>,
 }
 
-impl<C, P: Pair, CIDP, B: BlockT> AuraVerifier<C, P, CIDP, B>
+impl<C, P: Pair, CIDP, B: BlockT, CAW> AuraVerifier<C, P, CIDP, B, CAW>
 where
 	C: HeaderBackend<B> + HeaderMetadata<B, Error = sp_blockchain::Error> + ProvideRuntimeApi<B>,
 	P::Public: Codec + Debug,
 	C::Api: AuraApi<B, AuthorityId<P>>,
 {
 	/// Create a new Aura verifier.
+	///
+	/// `offchain_tx_pool_factory` is used to report detected equivocations to the runtime as an
+	/// on-chain offence. If `None`, equivocations are still detected and logged, but not reported.
+	///
+	/// `can_author_with` is consulted before verifying inherents, so that a node running an old
+	/// native runtime doesn't reject otherwise-valid blocks produced just after a forkless
+	/// runtime upgrade. Use [`sp_consensus::AlwaysCanAuthor`] if this check isn't needed.
+	///
+	/// `registry` is used to register the verifier's Prometheus metrics. If `None`, no metrics
+	/// are collected.
 	pub fn new(
 		client: Arc<C>,
 		create_inherent_data_providers: CIDP,
 		check_for_equivocation: CheckForEquivocation,
 		telemetry: Option<TelemetryHandle>,
 		authorities_tracker: Arc<AuthoritiesTracker<P, B, C>>,
+		offchain_tx_pool_factory: Option<OffchainTransactionPoolFactory<B>>,
+		can_author_with: CAW,
+		registry: Option<&Registry>,
 	) -> Result<Self, String> {
+		let metrics = registry
+			.map(Metrics::register)
+			.transpose()
+			.map_err(|e| format!("Failed to register Aura verifier metrics: {e}"))?;
+
 		Ok(Self {
 			client: client.clone(),
 			create_inherent_data_providers,
 			check_for_equivocation,
 			telemetry,
 			authorities_tracker,
+			offchain_tx_pool_factory,
+			can_author_with,
+			deferred_headers: Arc::new(DeferredHeaders::new()),
+			metrics,
 		})
 	}
+
+	/// The number of headers currently parked awaiting their slot to arrive, see
+	/// [`DeferredHeaders`].
+	pub fn num_deferred_headers(&self) -> usize {
+		self.deferred_headers.len()
+	}
 }
 
 #[async_trait::async_trait]
-impl<B, C, P, CIDP> Verifier<B> for AuraVerifier<C, P, CIDP, B>
+impl<B, C, P, CIDP, CAW> Verifier<B> for AuraVerifier<C, P, CIDP, B, CAW>
 where
 	B: BlockT,
 	C: HeaderBackend<B>
@@ -148,6 +439,7 @@ where
 	P::Signature: Codec,
 	CIDP: CreateInherentDataProviders<B, ()> + Send + Sync,
 	CIDP::InherentDataProviders: InherentDataProviderExt + Send + Sync,
+	CAW: CanAuthorWith<B> + Send + Sync,
 {
 	async fn verify(
 		&self,
@@ -165,6 +457,8 @@ where
 			return Ok(block)
 		}
 
+		let verify_start = Instant::now();
+
 		let hash = block.header.hash();
 		let parent_hash = *block.header.parent_hash();
 		let number = *block.header.number();
@@ -187,15 +481,16 @@ where
 		let slot_now = create_inherent_data_providers.slot();
 
 		// we add one to allow for some small drift.
-		// FIXME #1019 in the future, alter this queue to allow deferring of
-		// headers
 		let checked_header = check_header::<C, B, P>(
 			&self.client,
 			slot_now + 1,
 			block.header,
 			hash,
+			parent_hash,
 			&authorities[..],
 			self.check_for_equivocation,
+			self.offchain_tx_pool_factory.as_ref(),
+			self.metrics.as_ref(),
 		)
 		.map_err(|e| e.to_string())?;
 		match checked_header {
@@ -208,23 +503,36 @@ where
 
 					inherent_data.aura_replace_inherent_data(slot);
 
+					if let Err(err) = self.can_author_with.can_author_with(&BlockId::Hash(parent_hash)) {
+						debug!(
+							target: LOG_TARGET,
+							"Skipping `check_inherents_with_data` for block {:?}: {}", hash, err,
+						);
 					// skip the inherents verification if the runtime API is old or not expected to
 					// exist.
-					if self
+					} else if self
 						.client
 						.runtime_api()
 						.has_api_with::<dyn BlockBuilderApi<B>, _>(parent_hash, |v| v >= 2)
 						.map_err(|e| e.to_string())?
 					{
-						sp_block_builder::check_inherents_with_data(
+						let check_inherents_start = Instant::now();
+						let result = sp_block_builder::check_inherents_with_data(
 							self.client.clone(),
 							parent_hash,
 							new_block.clone(),
 							&create_inherent_data_providers,
 							inherent_data,
 						)
-						.await
-						.map_err(|e| format!("Error checking block inherents {:?}", e))?;
+						.await;
+
+						if let Some(metrics) = &self.metrics {
+							metrics
+								.check_inherents_time
+								.observe(check_inherents_start.elapsed().as_secs_f64());
+						}
+
+						result.map_err(|e| format!("Error checking block inherents {:?}", e))?;
 					}
 
 					let (_, inner_body) = new_block.deconstruct();
@@ -244,19 +552,56 @@ where
 				block.fork_choice = Some(ForkChoiceStrategy::LongestChain);
 				block.post_hash = Some(hash);
 
+				if let Some(metrics) = &self.metrics {
+					metrics.verify_time.observe(verify_start.elapsed().as_secs_f64());
+				}
+
 				Ok(block)
 			},
-			CheckedHeader::Deferred(a, b) => {
-				debug!(target: LOG_TARGET, "Checking {:?} failed; {:?}, {:?}.", hash, a, b);
-				telemetry!(
-					self.telemetry;
-					CONSENSUS_DEBUG;
-					"aura.header_too_far_in_future";
-					"hash" => ?hash,
-					"a" => ?a,
-					"b" => ?b,
+			CheckedHeader::Deferred(deferred_header, deferred_slot) => {
+				if !self.deferred_headers.try_park(deferred_slot, hash) {
+					telemetry!(
+						self.telemetry;
+						CONSENSUS_DEBUG;
+						"aura.header_too_far_in_future";
+						"hash" => ?hash,
+					);
+					return Err(format!(
+						"Header {:?} rejected: too far in the future and the deferred-header \
+						 queue is already full",
+						hash,
+					))
+				}
+				let _park_guard = ParkGuard {
+					deferred_headers: &self.deferred_headers,
+					slot: deferred_slot,
+					hash,
+				};
+
+				if let Some(metrics) = &self.metrics {
+					metrics.deferred_headers.inc();
+					metrics.verify_time.observe(verify_start.elapsed().as_secs_f64());
+				}
+
+				let wait = Duration::from_millis(create_inherent_data_providers.slot_duration())
+					.saturating_mul(
+						u32::try_from(*deferred_slot - *slot_now).unwrap_or(u32::MAX),
+					);
+
+				debug!(
+					target: LOG_TARGET,
+					"Deferring {:?} until slot {:?} ({:?} from now); {} header(s) pending.",
+					hash,
+					deferred_slot,
+					wait,
+					self.deferred_headers.len(),
 				);
-				Err(format!("Header {:?} rejected: too far in the future", hash))
+
+				Delay::new(wait).await;
+				self.deferred_headers.evict_stale(slot_now);
+
+				block.header = deferred_header;
+				self.verify(block).await
 			},
 		}
 	}
@@ -287,7 +632,15 @@ impl Default for CheckForEquivocation {
 }
 
 /// Parameters of [`import_queue`].
-pub struct ImportQueueParams<'a, Block: BlockT, I, C, S, CIDP> {
+pub struct ImportQueueParams<
+	'a,
+	Block: BlockT,
+	I,
+	C,
+	S,
+	CIDP,
+	CAW = sp_consensus::AlwaysCanAuthor,
+> {
 	/// The block import to use.
 	pub block_import: I,
 	/// The justification import.
@@ -308,10 +661,20 @@ pub struct ImportQueueParams<'a, Block: BlockT, I, C, S, CIDP> {
 	///
 	/// If in doubt, use `Default::default()`.
 	pub compatibility_mode: CompatibilityMode<NumberFor<Block>>,
+	/// Used to report detected equivocations to the runtime as an on-chain offence.
+	///
+	/// If `None`, equivocations are still detected and logged, but not reported.
+	pub offchain_tx_pool_factory: Option<OffchainTransactionPoolFactory<Block>>,
+	/// Something that can verify if a block can be authored using the current native runtime
+	/// version (or any other similar check), used to gracefully skip inherent verification
+	/// around forkless runtime upgrades instead of rejecting the block outright.
+	///
+	/// If in doubt, use `sp_consensus::AlwaysCanAuthor`.
+	pub can_author_with: CAW,
 }
 
 /// Start an import queue for the Aura consensus algorithm.
-pub fn import_queue<P, Block, I, C, S, CIDP>(
+pub fn import_queue<P, Block, I, C, S, CIDP, CAW>(
 	ImportQueueParams {
 		block_import,
 		justification_import,
@@ -322,7 +685,9 @@ pub fn import_queue<P, Block, I, C, S, CIDP>(
 		check_for_equivocation,
 		telemetry,
 		compatibility_mode,
-	}: ImportQueueParams<Block, I, C, S, CIDP>,
+		offchain_tx_pool_factory,
+		can_author_with,
+	}: ImportQueueParams<Block, I, C, S, CIDP, CAW>,
 ) -> Result<DefaultImportQueue<Block>, sp_consensus::Error>
 where
 	Block: BlockT,
@@ -343,13 +708,17 @@ where
 	S: sp_core::traits::SpawnEssentialNamed,
 	CIDP: CreateInherentDataProviders<Block, ()> + Sync + Send + 'static,
 	CIDP::InherentDataProviders: InherentDataProviderExt + Send + Sync,
+	CAW: CanAuthorWith<Block> + Send + Sync + 'static,
 {
-	let verifier = build_verifier::<P, _, _, _>(BuildVerifierParams {
+	let verifier = build_verifier::<P, _, _, _, _>(BuildVerifierParams {
 		client,
 		create_inherent_data_providers,
 		check_for_equivocation,
 		telemetry,
 		compatibility_mode,
+		offchain_tx_pool_factory,
+		can_author_with,
+		registry,
 	})
 	.map_err(|e| sp_consensus::Error::Other(e.into()))?;
 
@@ -445,7 +814,7 @@ where
 }
 
 /// Parameters of [`build_verifier`].
-pub struct BuildVerifierParams<C, CIDP, N> {
+pub struct BuildVerifierParams<'a, C, CIDP, B: BlockT, CAW = sp_consensus::AlwaysCanAuthor> {
 	/// The client to interact with the chain.
 	pub client: Arc<C>,
 	/// Something that can create the inherent data providers.
@@ -457,29 +826,47 @@ pub struct BuildVerifierParams<C, CIDP, N> {
 	/// Compatibility mode that should be used.
 	///
 	/// If in doubt, use `Default::default()`.
-	pub compatibility_mode: CompatibilityMode<N>,
+	pub compatibility_mode: CompatibilityMode<NumberFor<B>>,
+	/// Used to report detected equivocations to the runtime as an on-chain offence.
+	///
+	/// If `None`, equivocations are still detected and logged, but not reported.
+	pub offchain_tx_pool_factory: Option<OffchainTransactionPoolFactory<B>>,
+	/// Something that can verify if a block can be authored using the current native runtime
+	/// version (or any other similar check), used to gracefully skip inherent verification
+	/// around forkless runtime upgrades instead of rejecting the block outright.
+	///
+	/// If in doubt, use `sp_consensus::AlwaysCanAuthor`.
+	pub can_author_with: CAW,
+	/// The prometheus registry, used to register the verifier's metrics.
+	pub registry: Option<&'a Registry>,
 }
 
 /// Build the [`AuraVerifier`]
-pub fn build_verifier<P: Pair, C, CIDP, B: BlockT>(
+pub fn build_verifier<P: Pair, C, CIDP, B: BlockT, CAW>(
 	BuildVerifierParams {
 		client,
 		create_inherent_data_providers,
 		check_for_equivocation,
 		telemetry,
 		compatibility_mode,
-	}: BuildVerifierParams<C, CIDP, NumberFor<B>>,
-) -> Result<AuraVerifier<C, P, CIDP, B>, String>
+		offchain_tx_pool_factory,
+		can_author_with,
+		registry,
+	}: BuildVerifierParams<C, CIDP, B, CAW>,
+) -> Result<AuraVerifier<C, P, CIDP, B, CAW>, String>
 where
 	C: HeaderBackend<B> + HeaderMetadata<B, Error = sp_blockchain::Error> + ProvideRuntimeApi<B>,
 	P::Public: Codec + Debug,
 	C::Api: AuraApi<B, AuthorityId<P>>,
 {
-	AuraVerifier::<_, P, _, _>::new(
+	AuraVerifier::<_, P, _, _, _>::new(
 		client.clone(),
 		create_inherent_data_providers,
 		check_for_equivocation,
 		telemetry,
 		Arc::new(AuthoritiesTracker::new(client, &compatibility_mode)?),
+		offchain_tx_pool_factory,
+		can_author_with,
+		registry,
 	)
 }
@@ -78,6 +78,52 @@ pub fn remove_children<
 	tx.delete(column, &key[..]);
 }
 
+/// Add `child` to the children of `parent_hash`, queuing the re-encoded list in `tx`.
+/// A child already present is not duplicated. Returns the number of children after the
+/// append, so callers don't need a separate read to know the list is non-empty.
+pub fn append_child<
+	K: Eq + Hash + Clone + Encode + Decode,
+	V: Eq + Hash + Clone + Encode + Decode,
+>(
+	db: &dyn KeyValueDB,
+	tx: &mut DBTransaction,
+	column: u32,
+	prefix: &[u8],
+	parent_hash: K,
+	child: V,
+) -> sp_blockchain::Result<usize> {
+	let mut children: Vec<V> = read_children(db, column, prefix, parent_hash.clone())?;
+	if !children.contains(&child) {
+		children.push(child);
+		write_children(tx, column, prefix, parent_hash, children.clone());
+	}
+	Ok(children.len())
+}
+
+/// Remove `child` from the children of `parent_hash`, queuing the re-encoded list in `tx`.
+/// A no-op, besides the read, if `child` is not among the current children. Returns the
+/// number of children remaining, so callers can call `remove_children` instead when it
+/// reaches zero, rather than storing an empty list.
+pub fn remove_child<
+	K: Eq + Hash + Clone + Encode + Decode,
+	V: Eq + Hash + Clone + Encode + Decode,
+>(
+	db: &dyn KeyValueDB,
+	tx: &mut DBTransaction,
+	column: u32,
+	prefix: &[u8],
+	parent_hash: K,
+	child: V,
+) -> sp_blockchain::Result<usize> {
+	let mut children: Vec<V> = read_children(db, column, prefix, parent_hash.clone())?;
+	let original_len = children.len();
+	children.retain(|c| c != &child);
+	if children.len() != original_len {
+		write_children(tx, column, prefix, parent_hash, children.clone());
+	}
+	Ok(children.len())
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -117,4 +163,81 @@ mod tests {
 		assert_eq!(r1, vec![1_3, 1_5]);
 		assert_eq!(r2.len(), 0);
 	}
+
+	#[test]
+	fn append_child_to_missing_key_creates_it() {
+		const PREFIX: &[u8] = b"children";
+		let db = ::kvdb_memorydb::create(1);
+
+		let mut tx = DBTransaction::new();
+		let len = append_child(&db, &mut tx, 0, PREFIX, 1_1, 1_3).expect("append succeeds");
+		assert_eq!(len, 1);
+		db.write(tx).expect("Commiting transaction failed");
+
+		let r1: Vec<u32> = read_children(&db, 0, PREFIX, 1_1).expect("Getting r1 failed");
+		assert_eq!(r1, vec![1_3]);
+	}
+
+	#[test]
+	fn append_duplicate_child_is_a_no_op() {
+		const PREFIX: &[u8] = b"children";
+		let db = ::kvdb_memorydb::create(1);
+
+		let mut tx = DBTransaction::new();
+		append_child(&db, &mut tx, 0, PREFIX, 1_1, 1_3).expect("append succeeds");
+		db.write(tx).expect("Commiting transaction failed");
+
+		let mut tx = DBTransaction::new();
+		let len = append_child(&db, &mut tx, 0, PREFIX, 1_1, 1_3).expect("append succeeds");
+		assert_eq!(len, 1);
+		db.write(tx).expect("Commiting transaction failed");
+
+		let r1: Vec<u32> = read_children(&db, 0, PREFIX, 1_1).expect("Getting r1 failed");
+		assert_eq!(r1, vec![1_3]);
+	}
+
+	#[test]
+	fn remove_last_child_collapses_to_empty() {
+		const PREFIX: &[u8] = b"children";
+		let db = ::kvdb_memorydb::create(1);
+
+		let mut tx = DBTransaction::new();
+		append_child(&db, &mut tx, 0, PREFIX, 1_1, 1_3).expect("append succeeds");
+		append_child(&db, &mut tx, 0, PREFIX, 1_1, 1_5).expect("append succeeds");
+		db.write(tx).expect("Commiting transaction failed");
+
+		let mut tx = DBTransaction::new();
+		let len = remove_child(&db, &mut tx, 0, PREFIX, 1_1, 1_3).expect("remove succeeds");
+		assert_eq!(len, 1);
+		db.write(tx).expect("Commiting transaction failed");
+
+		let r1: Vec<u32> = read_children(&db, 0, PREFIX, 1_1).expect("Getting r1 failed");
+		assert_eq!(r1, vec![1_5]);
+
+		let mut tx = DBTransaction::new();
+		let len = remove_child(&db, &mut tx, 0, PREFIX, 1_1, 1_5).expect("remove succeeds");
+		assert_eq!(len, 0);
+		db.write(tx).expect("Commiting transaction failed");
+
+		let r1: Vec<u32> = read_children(&db, 0, PREFIX, 1_1).expect("Getting r1 failed");
+		assert_eq!(r1.len(), 0);
+	}
+
+	#[test]
+	fn remove_absent_child_is_a_no_op() {
+		const PREFIX: &[u8] = b"children";
+		let db = ::kvdb_memorydb::create(1);
+
+		let mut tx = DBTransaction::new();
+		append_child(&db, &mut tx, 0, PREFIX, 1_1, 1_3).expect("append succeeds");
+		db.write(tx).expect("Commiting transaction failed");
+
+		let mut tx = DBTransaction::new();
+		let len = remove_child(&db, &mut tx, 0, PREFIX, 1_1, 1_9).expect("remove succeeds");
+		assert_eq!(len, 1);
+		db.write(tx).expect("Commiting transaction failed");
+
+		let r1: Vec<u32> = read_children(&db, 0, PREFIX, 1_1).expect("Getting r1 failed");
+		assert_eq!(r1, vec![1_3]);
+	}
 }
@@ -656,6 +656,9 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 		metrics: NotificationMetrics,
 		peerstore_handle: Arc<dyn PeerStoreProvider>,
 	) -> (Self::NotificationProtocolConfig, Box<dyn NotificationService>) {
+		// `notification_config` doesn't have access to the node's base path, so peer set
+		// persistence is left disabled here; callers that do have one can still enable it by
+		// calling `NotificationProtocolConfig::new` directly.
 		Self::NotificationProtocolConfig::new(
 			protocol_name,
 			fallback_names,
@@ -664,6 +667,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 			set_config,
 			metrics,
 			peerstore_handle,
+			None,
 		)
 	}
 
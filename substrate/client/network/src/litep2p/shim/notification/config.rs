@@ -33,7 +33,10 @@ use litep2p::protocol::notification::{Config, ConfigBuilder};
 
 use sc_utils::mpsc::TracingUnboundedSender;
 
-use std::sync::{atomic::AtomicUsize, Arc};
+use std::{
+	path::PathBuf,
+	sync::{atomic::AtomicUsize, Arc},
+};
 
 /// Handle for controlling the notification protocol.
 #[derive(Debug, Clone)]
@@ -68,6 +71,11 @@ pub struct NotificationProtocolConfig {
 	/// Base configuration.
 	set_config: SetConfig,
 
+	/// Path to persist the reserved/connected peer set and reputation scores to across restarts.
+	///
+	/// `None` disables persistence.
+	peer_set_path: Option<PathBuf>,
+
 	/// `litep2p` notification config.
 	pub config: Config,
 
@@ -85,6 +93,7 @@ impl NotificationProtocolConfig {
 		set_config: SetConfig,
 		metrics: NotificationMetrics,
 		peerstore_handle: Arc<dyn PeerStoreProvider>,
+		peer_set_path: Option<PathBuf>,
 	) -> (Self, Box<dyn NotificationService>) {
 		// create `Peerset`/`Peerstore` handle for the protocol
 		let connected_peers = Arc::new(Default::default());
@@ -96,6 +105,7 @@ impl NotificationProtocolConfig {
 			set_config.reserved_nodes.iter().map(|address| address.peer_id).collect(),
 			Arc::clone(&connected_peers),
 			peerstore_handle,
+			peer_set_path.clone(),
 		);
 
 		// create `litep2p` notification protocol configuration for the protocol
@@ -114,13 +124,20 @@ impl NotificationProtocolConfig {
 		// initialize the actual object implementing `NotificationService` and combine the
 		// `litep2p::NotificationHandle` with `Peerset` to implement a full and independent
 		// notification protocol runner
-		let protocol = NotificationProtocol::new(protocol_name.clone(), handle, peerset, metrics);
+		let protocol = NotificationProtocol::new(
+			protocol_name.clone(),
+			handle,
+			peerset,
+			peerset_tx.clone(),
+			metrics,
+		);
 
 		(
 			Self {
 				protocol_name,
 				max_notification_size,
 				set_config,
+				peer_set_path,
 				config,
 				handle: ProtocolControlHandle::new(peerset_tx, connected_peers),
 			},
@@ -154,6 +171,11 @@ impl NotificationProtocolConfig {
 	pub fn max_notification_size(&self) -> usize {
 		self.max_notification_size
 	}
+
+	/// Get reference to the path the peer set is persisted to, if configured.
+	pub fn peer_set_path(&self) -> Option<&PathBuf> {
+		self.peer_set_path.as_ref()
+	}
 }
 
 impl NotificationConfig for NotificationProtocolConfig {
@@ -21,7 +21,9 @@
 
 use crate::{
 	error::Error,
-	litep2p::shim::notification::peerset::{OpenResult, Peerset, PeersetNotificationCommand},
+	litep2p::shim::notification::peerset::{
+		OpenResult, Peerset, PeersetCommand, PeersetNotificationCommand,
+	},
 	service::{
 		metrics::NotificationMetrics,
 		traits::{NotificationEvent as SubstrateNotificationEvent, ValidationResult},
@@ -29,19 +31,32 @@ use crate::{
 	MessageSink, NotificationService, ProtocolName,
 };
 
-use futures::{future::BoxFuture, stream::FuturesUnordered, StreamExt};
+use futures::{
+	future::{join_all, BoxFuture},
+	stream::FuturesUnordered,
+	StreamExt,
+};
 use litep2p::protocol::notification::{
 	NotificationEvent, NotificationHandle, NotificationSink,
 	ValidationResult as Litep2pValidationResult,
 };
-use tokio::sync::oneshot;
+use tokio::sync::{mpsc, oneshot, Mutex as AsyncMutex};
 
 use sc_network_types::PeerId;
-
-use std::{collections::HashSet, fmt};
+use sc_utils::mpsc::TracingUnboundedSender;
+
+use std::{
+	collections::HashSet,
+	fmt,
+	future::Future,
+	pin::Pin,
+	sync::{Arc, Mutex},
+	task::{Context, Poll},
+};
 
 pub mod config;
 pub mod peerset;
+pub(crate) mod persist;
 
 #[cfg(test)]
 mod tests;
@@ -119,90 +134,193 @@ impl MessageSink for Litep2pMessageSink {
 	}
 }
 
-/// Notification protocol implementation.
-pub struct NotificationProtocol {
+/// Future that polls the shared [`NotificationHandle`]'s event stream exactly once per `poll`
+/// call.
+///
+/// The lock is only ever held for that single, non-blocking `poll_next` call, never across an
+/// await, so other clones of [`NotificationServiceHandle`] can keep sending notifications through
+/// the same handle while the background task driving it is waiting for the next event.
+struct NextEvent<'a>(&'a AsyncMutex<NotificationHandle>);
+
+impl<'a> Future for NextEvent<'a> {
+	type Output = Option<NotificationEvent>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		match self.0.try_lock() {
+			Ok(mut handle) => Pin::new(&mut *handle).poll_next(cx),
+			Err(_) => {
+				// the handle is momentarily held by a clone sending a notification; come back
+				// around rather than risk missing the wake-up `litep2p` will have registered.
+				cx.waker().wake_by_ref();
+				Poll::Pending
+			},
+		}
+	}
+}
+
+/// State shared by every clone of a litep2p-backed [`NotificationService`].
+///
+/// A single background task ([`NotificationProtocol::run`]) owns the [`Peerset`] and polls the
+/// `litep2p` [`NotificationHandle`]'s event stream, fanning the events out to every live clone
+/// through [`Shared::primary_tx`] and [`Shared::event_txs`]. The handle itself lives here, behind
+/// an async mutex, so clones can also use it directly to send notifications and look up per-peer
+/// sinks.
+struct Shared {
 	/// Protocol name.
 	protocol: ProtocolName,
 
-	/// `litep2p` notification handle.
-	handle: NotificationHandle,
+	/// Notification metrics.
+	metrics: NotificationMetrics,
 
-	/// Peerset for the notification protocol.
+	/// Shared handle to the underlying `litep2p` notification protocol.
+	handle: AsyncMutex<NotificationHandle>,
+
+	/// TX channel for sending commands to the [`Peerset`] driving this protocol.
 	///
-	/// Listens to peering-related events and either opens or closes substreams to remote peers.
-	peerset: Peerset,
+	/// Used to implement [`NotificationService::open_substream`]/
+	/// [`NotificationService::close_substream`], which let a caller pin a connection to a peer
+	/// outside of `Peerset`'s generic slot allocation.
+	peerset_handle: TracingUnboundedSender<PeersetCommand>,
 
-	/// Pending validations for inbound substreams.
-	pending_validations: FuturesUnordered<
-		BoxFuture<'static, (PeerId, Result<ValidationResult, oneshot::error::RecvError>)>,
-	>,
+	/// Event channel of every live, non-primary clone.
+	///
+	/// Fed by the background task for every event other than
+	/// [`SubstrateNotificationEvent::ValidateInboundSubstream`].
+	event_txs: Mutex<Vec<mpsc::UnboundedSender<SubstrateNotificationEvent>>>,
 
-	/// Pending cancels.
-	pending_cancels: HashSet<litep2p::PeerId>,
+	/// Event channel of the primary clone, i.e. the one returned by
+	/// [`NotificationProtocol::new`].
+	///
+	/// Unlike the other notification events, an inbound substream validation must be answered by
+	/// exactly one clone, so [`SubstrateNotificationEvent::ValidateInboundSubstream`] is
+	/// delivered only here instead of being fanned out through [`Shared::event_txs`].
+	primary_tx: mpsc::UnboundedSender<SubstrateNotificationEvent>,
+}
 
-	/// Notification metrics.
-	metrics: NotificationMetrics,
+impl Shared {
+	/// Send an event built by `make_event` to the primary clone and to every live, non-primary
+	/// clone.
+	///
+	/// A fresh event is built per recipient via `make_event` rather than cloning a single value,
+	/// since [`SubstrateNotificationEvent`] carries a one-shot responder in one of its variants
+	/// and therefore isn't `Clone`.
+	fn broadcast(&self, mut make_event: impl FnMut() -> SubstrateNotificationEvent) {
+		let _ = self.primary_tx.send(make_event());
+
+		let mut event_txs = self.event_txs.lock().expect("event_txs lock not poisoned");
+		event_txs.retain(|tx| tx.send(make_event()).is_ok());
+	}
+}
+
+/// Handle to a litep2p-backed [`NotificationService`].
+///
+/// Every clone shares the same underlying `litep2p` substream and [`Peerset`], driven by a single
+/// background task; each clone can independently call [`NotificationService::next_event`],
+/// send notifications, and fetch a [`MessageSink`] for a peer.
+pub struct NotificationServiceHandle {
+	/// Shared state, see [`Shared`].
+	shared: Arc<Shared>,
+
+	/// This clone's event channel, fed by the background task driving [`Shared`].
+	event_rx: mpsc::UnboundedReceiver<SubstrateNotificationEvent>,
 }
 
-impl fmt::Debug for NotificationProtocol {
+impl fmt::Debug for NotificationServiceHandle {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		f.debug_struct("NotificationProtocol")
-			.field("protocol", &self.protocol)
-			.field("handle", &self.handle)
+		f.debug_struct("NotificationServiceHandle")
+			.field("protocol", &self.shared.protocol)
 			.finish()
 	}
 }
 
-impl NotificationProtocol {
-	/// Create new [`NotificationProtocol`].
-	pub fn new(
-		protocol: ProtocolName,
-		handle: NotificationHandle,
-		peerset: Peerset,
-		metrics: NotificationMetrics,
-	) -> Self {
-		Self {
-			protocol,
-			handle,
-			peerset,
-			metrics,
-			pending_cancels: HashSet::new(),
-			pending_validations: FuturesUnordered::new(),
-		}
-	}
-
-	/// Handle `Peerset` command.
-	async fn on_peerset_command(&mut self, command: PeersetNotificationCommand) {
-		match command {
-			PeersetNotificationCommand::OpenSubstream { peers } => {
-				log::debug!(target: LOG_TARGET, "{}: open substreams to {peers:?}", self.protocol);
-
-				let _ = self.handle.open_substream_batch(peers.into_iter().map(From::from)).await;
-			},
-			PeersetNotificationCommand::CloseSubstream { peers } => {
-				log::debug!(target: LOG_TARGET, "{}: close substreams to {peers:?}", self.protocol);
-
-				self.handle.close_substream_batch(peers.into_iter().map(From::from)).await;
-			},
-		}
+impl NotificationServiceHandle {
+	/// Send `notification` to every peer in `peers`, resolving only once every targeted peer's
+	/// sink has either accepted the notification or been found to have no open substream.
+	///
+	/// Unlike [`NotificationService::send_sync_notification`], which drops the notification
+	/// outright if a peer's channel is full, this reserves capacity in every targeted peer's
+	/// sink before the future resolves, giving broadcast producers (e.g. transaction and GRANDPA
+	/// gossip) genuine back-pressure across a whole batch of peers instead of a best-effort,
+	/// drop-on-congestion send to each peer individually.
+	///
+	/// `litep2p`'s [`NotificationSink`] doesn't expose a separate reserve-then-commit step, so
+	/// capacity is reserved and the notification delivered in the same step per peer; the
+	/// future still doesn't resolve until every peer's send has gone through (or been counted as
+	/// dropped because the peer has no open substream), which is what gives callers real
+	/// back-pressure instead of the fire-and-forget sync path.
+	pub async fn send_async_notification_batch(&self, peers: &[PeerId], notification: Vec<u8>) {
+		let delivered = join_all(peers.iter().map(|peer| {
+			let notification = notification.clone();
+
+			async move {
+				let sink = self.shared.handle.lock().await.notification_sink((*peer).into());
+				match sink {
+					Some(sink) => sink.send_async_notification(notification).await.is_ok(),
+					None => false,
+				}
+			}
+		}))
+		.await
+		.into_iter()
+		.filter(|delivered| *delivered)
+		.count();
+
+		self.shared.metrics.register_notifications_broadcast(
+			&self.shared.protocol,
+			delivered,
+			peers.len() - delivered,
+		);
 	}
 }
 
 #[async_trait::async_trait]
-impl NotificationService for NotificationProtocol {
-	async fn open_substream(&mut self, _peer: PeerId) -> Result<(), ()> {
-		unimplemented!();
+impl NotificationService for NotificationServiceHandle {
+	/// Ask `Peerset` to open a substream to `peer`, reserving an outbound slot for it outside of
+	/// the generic peer-slot allocation.
+	///
+	/// Returns `Err(())` immediately if no slot could be reserved (e.g. `Peerset` is already
+	/// connected to as many outbound peers as it's configured to allow). Otherwise the substream
+	/// open attempt proceeds the same way as any other `Peerset`-initiated connection: success is
+	/// reported to every clone via [`SubstrateNotificationEvent::NotificationStreamOpened`] and a
+	/// failure to actually open the substream is absorbed by `Peerset` itself (backoff and
+	/// reputation are adjusted as usual), which is observable as the peer never reaching
+	/// `NotificationStreamOpened`.
+	async fn open_substream(&mut self, peer: PeerId) -> Result<(), ()> {
+		let (result_tx, result_rx) = oneshot::channel();
+		self.shared
+			.peerset_handle
+			.unbounded_send(PeersetCommand::OpenSubstream { peer, result_tx })
+			.map_err(|_| ())?;
+
+		result_rx.await.map_err(|_| ())?
 	}
 
-	async fn close_substream(&mut self, _peer: PeerId) -> Result<(), ()> {
-		unimplemented!();
+	/// Ask `Peerset` to close the substream to `peer` that was previously pinned with
+	/// [`NotificationServiceHandle::open_substream`].
+	async fn close_substream(&mut self, peer: PeerId) -> Result<(), ()> {
+		let (result_tx, result_rx) = oneshot::channel();
+		self.shared
+			.peerset_handle
+			.unbounded_send(PeersetCommand::CloseSubstream { peer, result_tx })
+			.map_err(|_| ())?;
+
+		result_rx.await.map_err(|_| ())?
 	}
 
 	fn send_sync_notification(&mut self, peer: &PeerId, notification: Vec<u8>) {
 		let size = notification.len();
 
-		if let Ok(_) = self.handle.send_sync_notification(peer.into(), notification) {
-			self.metrics.register_notification_sent(&self.protocol, size);
+		let Ok(mut handle) = self.shared.handle.try_lock() else {
+			log::trace!(
+				target: LOG_TARGET,
+				"{}: dropping sync notification to {peer:?}, handle is busy",
+				self.shared.protocol,
+			);
+			return;
+		};
+
+		if let Ok(_) = handle.send_sync_notification(peer.into(), notification) {
+			self.shared.metrics.register_notification_sent(&self.shared.protocol, size);
 		}
 	}
 
@@ -213,18 +331,26 @@ impl NotificationService for NotificationProtocol {
 	) -> Result<(), Error> {
 		let size = notification.len();
 
-		match self.handle.send_async_notification(peer.into(), notification).await {
-			Ok(_) => {
-				self.metrics.register_notification_sent(&self.protocol, size);
-				Ok(())
+		// go through the peer's own `NotificationSink` rather than
+		// `NotificationHandle::send_async_notification` so the shared handle only needs to be
+		// locked long enough to look the sink up, not for the whole, potentially slow, send.
+		let sink = self.shared.handle.lock().await.notification_sink(peer.into());
+
+		match sink {
+			Some(sink) => match sink.send_async_notification(notification).await {
+				Ok(_) => {
+					self.shared.metrics.register_notification_sent(&self.shared.protocol, size);
+					Ok(())
+				},
+				Err(_) => Err(Error::ChannelClosed),
 			},
-			Err(_) => Err(Error::ChannelClosed),
+			None => Err(Error::ChannelClosed),
 		}
 	}
 
 	/// Set handshake for the notification protocol replacing the old handshake.
 	async fn set_handshake(&mut self, handshake: Vec<u8>) -> Result<(), ()> {
-		self.handle.set_handshake(handshake);
+		self.shared.handle.lock().await.set_handshake(handshake);
 
 		Ok(())
 	}
@@ -234,129 +360,235 @@ impl NotificationService for NotificationProtocol {
 	/// For `litep2p` this is identical to `NotificationService::set_handshake()` since `litep2p`
 	/// allows updating the handshake synchronously.
 	fn try_set_handshake(&mut self, handshake: Vec<u8>) -> Result<(), ()> {
-		self.handle.set_handshake(handshake);
+		let mut handle = self.shared.handle.try_lock().map_err(|_| ())?;
+		handle.set_handshake(handshake);
 
 		Ok(())
 	}
 
 	/// Make a copy of the object so it can be shared between protocol components
 	/// who wish to have access to the same underlying notification protocol.
+	///
+	/// The new clone observes the same fanned-out events (substream opened/closed, notifications
+	/// received) as every other clone and can send/query independently, but never receives
+	/// [`SubstrateNotificationEvent::ValidateInboundSubstream`] -- that's only ever delivered to
+	/// the original (primary) handle, since exactly one answer is expected per substream.
 	fn clone(&mut self) -> Result<Box<dyn NotificationService>, ()> {
-		unimplemented!("clonable `NotificationService` not supported by `litep2p`");
+		let (event_tx, event_rx) = mpsc::unbounded_channel();
+		self.shared.event_txs.lock().expect("event_txs lock not poisoned").push(event_tx);
+
+		Ok(Box::new(NotificationServiceHandle { shared: Arc::clone(&self.shared), event_rx }))
 	}
 
 	/// Get protocol name of the `NotificationService`.
 	fn protocol(&self) -> &ProtocolName {
-		&self.protocol
+		&self.shared.protocol
 	}
 
 	/// Get message sink of the peer.
 	fn message_sink(&self, peer: &PeerId) -> Option<Box<dyn MessageSink>> {
-		self.handle.notification_sink(peer.into()).map(|sink| {
-			let sink: Box<dyn MessageSink> = Box::new(Litep2pMessageSink::new(
-				*peer,
-				self.protocol.clone(),
-				sink,
-				self.metrics.clone(),
-			));
-			sink
-		})
+		let sink = self.shared.handle.try_lock().ok()?.notification_sink(peer.into())?;
+
+		Some(Box::new(Litep2pMessageSink::new(
+			*peer,
+			self.shared.protocol.clone(),
+			sink,
+			self.shared.metrics.clone(),
+		)))
 	}
 
 	/// Get next event from the `Notifications` event stream.
 	async fn next_event(&mut self) -> Option<SubstrateNotificationEvent> {
+		self.event_rx.recv().await
+	}
+}
+
+/// Background task driving a litep2p notification protocol.
+///
+/// Owns the [`Peerset`] and, together with every [`NotificationServiceHandle`] clone, shares the
+/// `litep2p` [`NotificationHandle`] via [`Shared`]. Fans the events it reads off the handle's
+/// event stream out to every live clone. Constructed and spawned by
+/// [`NotificationProtocol::new`], which returns the primary handle.
+struct NotificationProtocol {
+	/// Shared state, see [`Shared`].
+	shared: Arc<Shared>,
+
+	/// Peerset for the notification protocol.
+	///
+	/// Listens to peering-related events and either opens or closes substreams to remote peers.
+	peerset: Peerset,
+
+	/// Pending validations for inbound substreams.
+	pending_validations: FuturesUnordered<
+		BoxFuture<'static, (PeerId, Result<ValidationResult, oneshot::error::RecvError>)>,
+	>,
+
+	/// Pending cancels.
+	pending_cancels: HashSet<litep2p::PeerId>,
+}
+
+impl NotificationProtocol {
+	/// Create new litep2p-backed [`NotificationService`], spawning the background task that
+	/// drives it and returning the primary handle to it.
+	pub fn new(
+		protocol: ProtocolName,
+		handle: NotificationHandle,
+		peerset: Peerset,
+		peerset_handle: TracingUnboundedSender<PeersetCommand>,
+		metrics: NotificationMetrics,
+	) -> NotificationServiceHandle {
+		let (primary_tx, event_rx) = mpsc::unbounded_channel();
+
+		let shared = Arc::new(Shared {
+			protocol,
+			metrics,
+			handle: AsyncMutex::new(handle),
+			peerset_handle,
+			event_txs: Mutex::new(Vec::new()),
+			primary_tx,
+		});
+
+		let driver = NotificationProtocol {
+			shared: Arc::clone(&shared),
+			peerset,
+			pending_cancels: HashSet::new(),
+			pending_validations: FuturesUnordered::new(),
+		};
+
+		tokio::spawn(driver.run());
+
+		NotificationServiceHandle { shared, event_rx }
+	}
+
+	/// Handle `Peerset` command.
+	async fn on_peerset_command(&mut self, command: PeersetNotificationCommand) {
+		let handle = self.shared.handle.lock().await;
+
+		match command {
+			PeersetNotificationCommand::OpenSubstream { peers } => {
+				log::debug!(target: LOG_TARGET, "{}: open substreams to {peers:?}", self.shared.protocol);
+
+				let _ = handle.open_substream_batch(peers.into_iter().map(From::from)).await;
+			},
+			PeersetNotificationCommand::CloseSubstream { peers } => {
+				log::debug!(target: LOG_TARGET, "{}: close substreams to {peers:?}", self.shared.protocol);
+
+				handle.close_substream_batch(peers.into_iter().map(From::from)).await;
+			},
+		}
+	}
+
+	/// Drive the protocol forward, fanning out events to every live clone via [`Shared`], until
+	/// the underlying `litep2p` handle's event stream ends.
+	///
+	/// Persists the peer set (see [`Peerset::persist`]) once the loop below exits, i.e. once the
+	/// protocol is shutting down.
+	async fn run(mut self) {
+		self.run_until_closed().await;
+		self.peerset.persist();
+	}
+
+	async fn run_until_closed(&mut self) {
 		loop {
 			tokio::select! {
 				biased;
 
-				event = self.handle.next() => match event? {
-					NotificationEvent::ValidateSubstream { peer, handshake, .. } => {
-						if let ValidationResult::Reject = self.peerset.report_inbound_substream(peer.into()) {
-							self.handle.send_validation_result(peer, Litep2pValidationResult::Reject);
-							continue;
-						}
+				event = NextEvent(&self.shared.handle) => {
+					let Some(event) = event else { return };
 
-						let (tx, rx) = oneshot::channel();
-						self.pending_validations.push(Box::pin(async move { (peer.into(), rx.await) }));
+					match event {
+						NotificationEvent::ValidateSubstream { peer, handshake, .. } => {
+							if let ValidationResult::Reject = self.peerset.report_inbound_substream(peer.into()) {
+								self.shared.handle.lock().await.send_validation_result(peer, Litep2pValidationResult::Reject);
+								continue;
+							}
 
-						log::trace!(target: LOG_TARGET, "{}: validate substream for {peer:?}", self.protocol);
+							let (tx, rx) = oneshot::channel();
+							self.pending_validations.push(Box::pin(async move { (peer.into(), rx.await) }));
 
-						return Some(SubstrateNotificationEvent::ValidateInboundSubstream {
-							peer: peer.into(),
-							handshake,
-							result_tx: tx,
-						});
-					}
-					NotificationEvent::NotificationStreamOpened {
-						peer,
-						fallback,
-						handshake,
-						direction,
-						..
-					} => {
-						self.metrics.register_substream_opened(&self.protocol);
-
-						match self.peerset.report_substream_opened(peer.into(), direction.into()) {
-							OpenResult::Reject => {
-								let _ = self.handle.close_substream_batch(vec![peer].into_iter().map(From::from)).await;
-								self.pending_cancels.insert(peer);
+							log::trace!(target: LOG_TARGET, "{}: validate substream for {peer:?}", self.shared.protocol);
 
+							let _ = self.shared.primary_tx.send(SubstrateNotificationEvent::ValidateInboundSubstream {
+								peer: peer.into(),
+								handshake,
+								result_tx: tx,
+							});
+						}
+						NotificationEvent::NotificationStreamOpened {
+							peer,
+							fallback,
+							handshake,
+							direction,
+							..
+						} => {
+							self.shared.metrics.register_substream_opened(&self.shared.protocol);
+
+							match self.peerset.report_substream_opened(peer.into(), direction.into()) {
+								OpenResult::Reject => {
+									let _ = self.shared.handle.lock().await.close_substream_batch(vec![peer].into_iter().map(From::from)).await;
+									self.pending_cancels.insert(peer);
+
+									continue
+								}
+								OpenResult::Accept { direction } => {
+									log::trace!(target: LOG_TARGET, "{}: substream opened for {peer:?}", self.shared.protocol);
+
+									let negotiated_fallback = fallback.map(From::from);
+									self.shared.broadcast(|| SubstrateNotificationEvent::NotificationStreamOpened {
+										peer: peer.into(),
+										handshake: handshake.clone(),
+										direction,
+										negotiated_fallback: negotiated_fallback.clone(),
+									});
+								}
+							}
+						}
+						NotificationEvent::NotificationStreamClosed {
+							peer,
+						} => {
+							log::trace!(target: LOG_TARGET, "{}: substream closed for {peer:?}", self.shared.protocol);
+
+							self.shared.metrics.register_substream_closed(&self.shared.protocol);
+							self.peerset.report_substream_closed(peer.into());
+
+							if self.pending_cancels.remove(&peer) {
+								log::debug!(
+									target: LOG_TARGET,
+									"{}: substream closed to canceled peer ({peer:?})",
+									self.shared.protocol
+								);
 								continue
 							}
-							OpenResult::Accept { direction } => {
-								log::trace!(target: LOG_TARGET, "{}: substream opened for {peer:?}", self.protocol);
 
-								return Some(SubstrateNotificationEvent::NotificationStreamOpened {
+							self.shared.broadcast(|| SubstrateNotificationEvent::NotificationStreamClosed {
+								peer: peer.into(),
+							});
+						}
+						NotificationEvent::NotificationStreamOpenFailure {
+							peer,
+							error,
+						} => {
+							log::trace!(target: LOG_TARGET, "{}: open failure for {peer:?}", self.shared.protocol);
+							self.peerset.report_substream_open_failure(peer.into(), error);
+						}
+						NotificationEvent::NotificationReceived {
+							peer,
+							notification,
+						} => {
+							self.shared.metrics.register_notification_received(&self.shared.protocol, notification.len());
+
+							if !self.pending_cancels.contains(&peer) {
+								let notification = notification.to_vec();
+								self.shared.broadcast(|| SubstrateNotificationEvent::NotificationReceived {
 									peer: peer.into(),
-									handshake,
-									direction,
-									negotiated_fallback: fallback.map(From::from),
+									notification: notification.clone(),
 								});
 							}
 						}
 					}
-					NotificationEvent::NotificationStreamClosed {
-						peer,
-					} => {
-						log::trace!(target: LOG_TARGET, "{}: substream closed for {peer:?}", self.protocol);
-
-						self.metrics.register_substream_closed(&self.protocol);
-						self.peerset.report_substream_closed(peer.into());
-
-						if self.pending_cancels.remove(&peer) {
-							log::debug!(
-								target: LOG_TARGET,
-								"{}: substream closed to canceled peer ({peer:?})",
-								self.protocol
-							);
-							continue
-						}
-
-						return Some(SubstrateNotificationEvent::NotificationStreamClosed { peer: peer.into() })
-					}
-					NotificationEvent::NotificationStreamOpenFailure {
-						peer,
-						error,
-					} => {
-						log::trace!(target: LOG_TARGET, "{}: open failure for {peer:?}", self.protocol);
-						self.peerset.report_substream_open_failure(peer.into(), error);
-					}
-					NotificationEvent::NotificationReceived {
-						peer,
-						notification,
-					} => {
-						self.metrics.register_notification_received(&self.protocol, notification.len());
-
-						if !self.pending_cancels.contains(&peer) {
-							return Some(SubstrateNotificationEvent::NotificationReceived {
-								peer: peer.into(),
-								notification: notification.to_vec(),
-							});
-						}
-					}
 				},
 				result = self.pending_validations.next(), if !self.pending_validations.is_empty() => {
-					let (peer, result) = result?;
+					let Some((peer, result)) = result else { return };
 					let validation_result = match result {
 						Ok(ValidationResult::Accept) => Litep2pValidationResult::Accept,
 						_ => {
@@ -365,9 +597,12 @@ impl NotificationService for NotificationProtocol {
 						}
 					};
 
-					self.handle.send_validation_result(peer.into(), validation_result);
+					self.shared.handle.lock().await.send_validation_result(peer.into(), validation_result);
+				}
+				command = self.peerset.next() => {
+					let Some(command) = command else { return };
+					self.on_peerset_command(command).await;
 				}
-				command = self.peerset.next() => self.on_peerset_command(command?).await,
 			}
 		}
 	}
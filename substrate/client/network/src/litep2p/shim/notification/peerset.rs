@@ -39,6 +39,7 @@
 //! Peers for outbound slots are selected in a decreasing order of reputation.
 
 use crate::{
+	litep2p::shim::notification::persist,
 	peer_store::{PeerStoreProvider, ProtocolHandle},
 	service::traits::{self, ValidationResult},
 	ProtocolName, ReputationChange as Reputation,
@@ -54,6 +55,7 @@ use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnbound
 use std::{
 	collections::{HashMap, HashSet},
 	future::Future,
+	path::PathBuf,
 	pin::Pin,
 	sync::{
 		atomic::{AtomicUsize, Ordering},
@@ -87,6 +89,45 @@ const DISCONNECT_ADJUSTMENT: Reputation = Reputation::new(-256, "Peer disconnect
 /// Lessens the likelyhood of the peer getting selected for an outbound connection soon.
 const OPEN_FAILURE_ADJUSTMENT: Reputation = Reputation::new(-1024, "Open failure");
 
+/// How often peer scores are decayed.
+const SCORE_DECAY_FREQUENCY: Duration = Duration::from_secs(10);
+
+/// Factor peer scores are multiplied by on every decay tick.
+///
+/// Since the factor is in `(0, 1)`, repeated decay pulls every score towards zero
+/// ([`GOSSIP_THRESHOLD`]) regardless of its sign, giving a misbehaving peer a way back into good
+/// standing and preventing a peer that was well-behaved a long time ago from coasting on that
+/// forever.
+const SCORE_DECAY_FACTOR: f64 = 0.95;
+
+/// Score bonus applied when a substream to a peer is opened successfully.
+const SCORE_SUBSTREAM_OPENED: f64 = 10.0;
+
+/// Score bonus applied, on every decay tick, to a peer that has remained connected since the
+/// previous tick. Rewards sustained connectivity rather than just the initial handshake.
+const SCORE_SUSTAINED_CONNECTIVITY: f64 = 1.0;
+
+/// Score penalty applied when a substream fails to open.
+const SCORE_OPEN_FAILURE: f64 = -20.0;
+
+/// Score penalty applied when a substream is rejected by the protocol after being accepted by
+/// [`Peerset`].
+const SCORE_REJECTED: f64 = -20.0;
+
+/// Score at and above which a peer is treated normally.
+///
+/// This is also the value every score decays towards, see [`SCORE_DECAY_FACTOR`].
+const GOSSIP_THRESHOLD: f64 = 0.0;
+
+/// Score below which a peer is graylisted: its inbound substreams are rejected in
+/// [`Peerset::report_inbound_substream()`] and it is skipped when selecting peers for outbound
+/// connections, until its score decays back above the threshold.
+const GRAYLIST_THRESHOLD: f64 = -50.0;
+
+/// Score below which a peer is banned: in addition to being graylisted, an existing connection to
+/// the peer is closed on the next decay tick, unless the peer is reserved.
+const BAN_THRESHOLD: f64 = -200.0;
+
 /// Is the peer reserved?
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Reserved {
@@ -182,6 +223,32 @@ pub enum PeersetCommand {
 		/// `oneshot::Sender` for sending the current set of reserved peers.
 		tx: oneshot::Sender<Vec<PeerId>>,
 	},
+
+	/// Explicitly open a substream to `peer`, pinning the connection outside of the generic
+	/// peer-slot allocation performed by [`Peerset`].
+	///
+	/// An outbound slot is reserved for `peer` the same way it would be for any other outbound
+	/// peer (reserved peers don't consume a slot), so this can fail with `Err(())` if no slot is
+	/// free. If a slot was reserved, `Ok(())` is sent and the result of the actual connection
+	/// attempt is reported the usual way, through [`Peerset::report_substream_open_failure`] if
+	/// `litep2p` fails to open the substream.
+	OpenSubstream {
+		/// Peer ID.
+		peer: PeerId,
+
+		/// Channel for reporting whether a slot could be reserved for `peer`.
+		result_tx: oneshot::Sender<Result<(), ()>>,
+	},
+
+	/// Explicitly close the substream to `peer` that was previously pinned with
+	/// [`PeersetCommand::OpenSubstream`].
+	CloseSubstream {
+		/// Peer ID.
+		peer: PeerId,
+
+		/// Channel for reporting whether the peer was connected and its substream closed.
+		result_tx: oneshot::Sender<Result<(), ()>>,
+	},
 }
 
 /// Commands emitted by [`Peerset`] to the notification protocol.
@@ -350,6 +417,27 @@ pub struct Peerset {
 
 	/// Next time when [`Peerset`] should perform slot allocation.
 	next_slot_allocation: Delay,
+
+	/// Gossipsub-style reputation score of each peer known to this protocol.
+	///
+	/// Peers not present in the map are treated as having a score of `0.0`
+	/// ([`GOSSIP_THRESHOLD`]).
+	scores: HashMap<PeerId, f64>,
+
+	/// Next time when [`Peerset`] should decay [`Peerset::scores`].
+	next_score_decay: Delay,
+
+	/// Path to persist the reserved/connected peer set and reputation scores to, if configured.
+	///
+	/// See [`persist`] for the on-disk format.
+	peer_set_path: Option<PathBuf>,
+
+	/// Non-reserved peers reloaded from [`Peerset::peer_set_path`] on construction, still waiting
+	/// to be pre-seeded as outbound substream targets.
+	///
+	/// Drained, at most [`Peerset::max_out`] peers at a time, the first few times
+	/// [`Stream::poll_next`] runs.
+	pending_preseed: Vec<PeerId>,
 }
 
 macro_rules! decrement_or_warn {
@@ -394,6 +482,7 @@ impl Peerset {
 		reserved_peers: HashSet<PeerId>,
 		connected_peers: Arc<AtomicUsize>,
 		peerstore_handle: Arc<dyn PeerStoreProvider>,
+		peer_set_path: Option<PathBuf>,
 	) -> (Self, TracingUnboundedSender<PeersetCommand>) {
 		let (cmd_tx, cmd_rx) = tracing_unbounded("mpsc-peerset-protocol", 100_000);
 		let peers = reserved_peers
@@ -405,6 +494,21 @@ impl Peerset {
 		// if some connected peer gets banned.
 		peerstore_handle.register_protocol(Arc::new(PeersetHandle { tx: cmd_tx.clone() }));
 
+		// reload the peer set persisted on a previous, graceful shutdown, if configured, so
+		// `Peerset` can immediately try reconnecting to peers it already knew to be good instead
+		// of rebuilding connectivity from scratch.
+		let reloaded = peer_set_path.as_deref().map(persist::load).unwrap_or_default();
+		let scores = reloaded.scores;
+		let pending_preseed = reloaded
+			.reserved
+			.into_iter()
+			.chain(scores.keys().copied())
+			.filter(|peer| !reserved_peers.contains(peer))
+			.filter(|peer| scores.get(peer).map_or(true, |score| *score >= GOSSIP_THRESHOLD))
+			.collect::<HashSet<_>>()
+			.into_iter()
+			.collect::<Vec<_>>();
+
 		(
 			Self {
 				protocol,
@@ -420,11 +524,45 @@ impl Peerset {
 				connected_peers,
 				pending_backoffs: FuturesUnordered::new(),
 				next_slot_allocation: Delay::new(SLOT_ALLOCATION_FREQUENCY),
+				scores,
+				next_score_decay: Delay::new(SCORE_DECAY_FREQUENCY),
+				peer_set_path,
+				pending_preseed,
 			},
 			cmd_tx,
 		)
 	}
 
+	/// Persist the current reserved/connected peer set and reputation scores to
+	/// [`Peerset::peer_set_path`], if configured.
+	///
+	/// Intended to be called on graceful shutdown of the protocol so the peer set can be reloaded
+	/// the next time [`Peerset::new`] runs.
+	pub fn persist(&self) {
+		if let Some(path) = &self.peer_set_path {
+			persist::store(path, &self.reserved_peers, &self.scores);
+		}
+	}
+
+	/// Get the reputation score of `peer`.
+	///
+	/// Intended to be read by the metrics subsystem so operators can observe the reputation
+	/// distribution of a protocol's peers. Peers that haven't been scored yet report a score
+	/// of `0.0`.
+	pub fn peer_score(&self, peer: &PeerId) -> f64 {
+		self.scores.get(peer).copied().unwrap_or(GOSSIP_THRESHOLD)
+	}
+
+	/// Adjust the reputation score of `peer` by `delta`.
+	fn adjust_score(&mut self, peer: PeerId, delta: f64) {
+		*self.scores.entry(peer).or_insert(GOSSIP_THRESHOLD) += delta;
+	}
+
+	/// Is `peer`'s score below [`GRAYLIST_THRESHOLD`]?
+	fn is_graylisted(&self, peer: &PeerId) -> bool {
+		self.peer_score(peer) < GRAYLIST_THRESHOLD
+	}
+
 	/// Report to [`Peerset`] that a substream was opened.
 	///
 	/// Slot for the stream was "preallocated" when it was initiated (outbound) or accepted
@@ -456,6 +594,7 @@ impl Peerset {
 
 				*state = PeerState::Connected { direction: *substream_direction };
 				self.connected_peers.fetch_add(1usize, Ordering::Relaxed);
+				self.adjust_score(peer, SCORE_SUBSTREAM_OPENED);
 
 				return OpenResult::Accept { direction: real_direction }
 			},
@@ -562,6 +701,17 @@ impl Peerset {
 			return ValidationResult::Reject;
 		}
 
+		if self.is_graylisted(&peer) {
+			log::debug!(
+				target: LOG_TARGET,
+				"{}: rejecting graylisted peer {peer:?}, score {}",
+				self.protocol,
+				self.peer_score(&peer),
+			);
+
+			return ValidationResult::Reject;
+		}
+
 		let state = self.peers.entry(peer).or_insert(PeerState::Disconnected);
 		let is_reserved_peer = self.reserved_peers.contains(&peer);
 
@@ -721,6 +871,7 @@ impl Peerset {
 			},
 		}
 
+		self.adjust_score(peer, SCORE_OPEN_FAILURE);
 		self.peers.insert(peer, PeerState::Backoff);
 		self.pending_backoffs.push(Box::pin(async move {
 			Delay::new(OPEN_FAILURE_BACKOFF).await;
@@ -732,6 +883,8 @@ impl Peerset {
 	pub fn report_substream_rejected(&mut self, peer: PeerId) {
 		log::trace!(target: LOG_TARGET, "{}: {peer:?} rejected by the protocol", self.protocol);
 
+		self.adjust_score(peer, SCORE_REJECTED);
+
 		match self.peers.remove(&peer) {
 			Some(PeerState::Opening { direction }) => match direction {
 				Direction::Inbound(Reserved::Yes) | Direction::Outbound(Reserved::Yes) => {
@@ -848,6 +1001,32 @@ impl Stream for Peerset {
 	type Item = PeersetNotificationCommand;
 
 	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		if !self.pending_preseed.is_empty() {
+			let available = self.max_out.saturating_sub(self.num_out);
+			let peers = self
+				.pending_preseed
+				.drain(..std::cmp::min(available, self.pending_preseed.len()))
+				.collect::<Vec<_>>();
+
+			if !peers.is_empty() {
+				log::debug!(
+					target: LOG_TARGET,
+					"{}: pre-seed outbound substreams to known-good peers {peers:?}",
+					self.protocol,
+				);
+
+				for peer in &peers {
+					self.num_out += 1;
+					self.peers.insert(
+						*peer,
+						PeerState::Opening { direction: Direction::Outbound(Reserved::No) },
+					);
+				}
+
+				return Poll::Ready(Some(PeersetNotificationCommand::OpenSubstream { peers }))
+			}
+		}
+
 		while let Poll::Ready(Some((peer, reputation))) = self.pending_backoffs.poll_next_unpin(cx)
 		{
 			log::trace!(target: LOG_TARGET, "{}: backoff expired for {peer:?}", self.protocol);
@@ -1343,6 +1522,71 @@ impl Stream for Peerset {
 				PeersetCommand::GetReservedPeers { tx } => {
 					let _ = tx.send(self.reserved_peers.iter().cloned().collect());
 				},
+				PeersetCommand::OpenSubstream { peer, result_tx } => {
+					log::debug!(
+						target: LOG_TARGET,
+						"{}: explicit open substream request for {peer:?}",
+						self.protocol,
+					);
+
+					let reserved = self.reserved_peers.contains(&peer);
+					let can_open = std::matches!(
+						self.peers.get(&peer),
+						None | Some(PeerState::Disconnected) | Some(PeerState::Backoff)
+					) && (reserved || self.num_out < self.max_out);
+
+					if !can_open {
+						log::debug!(
+							target: LOG_TARGET,
+							"{}: cannot open substream to {peer:?}, no free outbound slot or invalid state",
+							self.protocol,
+						);
+
+						let _ = result_tx.send(Err(()));
+					} else {
+						if !reserved {
+							self.num_out += 1;
+						}
+
+						self.peers.insert(
+							peer,
+							PeerState::Opening { direction: Direction::Outbound(reserved.into()) },
+						);
+						let _ = result_tx.send(Ok(()));
+
+						return Poll::Ready(Some(PeersetNotificationCommand::OpenSubstream {
+							peers: vec![peer],
+						}))
+					}
+				},
+				PeersetCommand::CloseSubstream { peer, result_tx } => {
+					log::debug!(
+						target: LOG_TARGET,
+						"{}: explicit close substream request for {peer:?}",
+						self.protocol,
+					);
+
+					match self.peers.get(&peer) {
+						Some(PeerState::Connected { direction }) => {
+							let direction = *direction;
+							self.peers.insert(peer, PeerState::Closing { direction });
+							let _ = result_tx.send(Ok(()));
+
+							return Poll::Ready(Some(PeersetNotificationCommand::CloseSubstream {
+								peers: vec![peer],
+							}))
+						},
+						state => {
+							log::debug!(
+								target: LOG_TARGET,
+								"{}: cannot close substream to {peer:?}, invalid state {state:?}",
+								self.protocol,
+							);
+
+							let _ = result_tx.send(Err(()));
+						},
+					}
+				},
 			}
 		}
 
@@ -1358,7 +1602,8 @@ impl Stream for Peerset {
 				.filter_map(|(peer, state)| {
 					(self.reserved_peers.contains(peer) &&
 						std::matches!(state, PeerState::Disconnected) &&
-						!self.peerstore_handle.is_banned(peer))
+						!self.peerstore_handle.is_banned(peer) &&
+						!self.is_graylisted(peer))
 					.then_some(*peer)
 				})
 				.collect::<Vec<_>>();
@@ -1381,8 +1626,14 @@ impl Stream for Peerset {
 					})
 					.collect();
 
-				let peers: Vec<_> =
-					self.peerstore_handle.outgoing_candidates(self.max_out - self.num_out, ignore);
+				// `Peerstore` is unaware of this protocol's local reputation scores so graylisted
+				// candidates must be filtered out here instead.
+				let peers: Vec<_> = self
+					.peerstore_handle
+					.outgoing_candidates(self.max_out - self.num_out, ignore)
+					.into_iter()
+					.filter(|peer| !self.is_graylisted(peer))
+					.collect();
 
 				if peers.len() > 0 {
 					peers.iter().for_each(|peer| {
@@ -1414,6 +1665,60 @@ impl Stream for Peerset {
 			}
 		}
 
+		// periodically decay every peer's reputation score towards `GOSSIP_THRESHOLD`, reward
+		// peers that have stayed connected since the last tick, and close substreams to peers
+		// that have decayed below `BAN_THRESHOLD`.
+		if let Poll::Ready(()) = Pin::new(&mut self.next_score_decay).poll(cx) {
+			let peers = &self.peers;
+			let mut to_disconnect = Vec::new();
+
+			self.scores.retain(|peer, score| {
+				if std::matches!(peers.get(peer), Some(PeerState::Connected { .. })) {
+					*score += SCORE_SUSTAINED_CONNECTIVITY;
+				}
+
+				*score *= SCORE_DECAY_FACTOR;
+
+				if *score < BAN_THRESHOLD &&
+					std::matches!(peers.get(peer), Some(PeerState::Connected { .. }))
+				{
+					to_disconnect.push(*peer);
+				}
+
+				// bound the map's size by forgetting peers that have decayed back to neutral
+				score.abs() > 0.01
+			});
+
+			self.next_score_decay = Delay::new(SCORE_DECAY_FREQUENCY);
+
+			let peers = to_disconnect
+				.into_iter()
+				.filter(|peer| !self.reserved_peers.contains(peer))
+				.filter_map(|peer| match self.peers.remove(&peer) {
+					Some(PeerState::Connected { direction }) => {
+						log::debug!(
+							target: LOG_TARGET,
+							"{}: {peer:?} banned for low reputation, close connection",
+							self.protocol,
+						);
+
+						self.peers.insert(peer, PeerState::Closing { direction });
+						Some(peer)
+					},
+					state => {
+						if let Some(state) = state {
+							self.peers.insert(peer, state);
+						}
+						None
+					},
+				})
+				.collect::<Vec<_>>();
+
+			if !peers.is_empty() {
+				return Poll::Ready(Some(PeersetNotificationCommand::CloseSubstream { peers }))
+			}
+		}
+
 		Poll::Pending
 	}
 }
@@ -0,0 +1,157 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Persistence of [`Peerset`](super::peerset::Peerset)'s reserved/connected peer set and
+//! accumulated reputation scores across restarts.
+//!
+//! Mirrors how beacon nodes persist their DHT routing table buckets: the peer set is serialized
+//! to a file on graceful shutdown and reloaded on startup, so the node can immediately try
+//! reconnecting to peers it already knew to be good instead of rebuilding connectivity from
+//! scratch. Entries that haven't been refreshed for longer than [`STALE_PEER_TTL`] are treated as
+//! stale and dropped on reload.
+
+use sc_network_types::PeerId;
+use serde::{Deserialize, Serialize};
+
+use std::{
+	collections::HashMap,
+	path::Path,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Logging target for the file.
+const LOG_TARGET: &str = "sub-libp2p::peerset";
+
+/// Entries older than this are considered stale and discarded when the peer set is reloaded.
+const STALE_PEER_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// On-disk representation of a single peer's persisted state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedPeer {
+	/// Peer ID.
+	peer: PeerId,
+
+	/// Whether the peer was part of the reserved set when it was persisted.
+	reserved: bool,
+
+	/// Gossipsub-style reputation score the peer had when it was persisted.
+	score: f64,
+
+	/// Unix timestamp, in seconds, of when this entry was last persisted.
+	last_seen: u64,
+}
+
+/// On-disk representation of a [`Peerset`](super::peerset::Peerset)'s peer set.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedPeerSet {
+	/// Persisted peers.
+	peers: Vec<PersistedPeer>,
+}
+
+/// Peer set reloaded from disk, ready to be fed back into a freshly created
+/// [`Peerset`](super::peerset::Peerset).
+#[derive(Debug, Default)]
+pub struct ReloadedPeerSet {
+	/// Previously-reserved peers, to pre-seed immediate reconnection attempts.
+	pub reserved: Vec<PeerId>,
+
+	/// Reputation score last observed for every non-stale peer, reserved or not.
+	pub scores: HashMap<PeerId, f64>,
+}
+
+/// Load a previously persisted peer set from `path`.
+///
+/// Returns an empty [`ReloadedPeerSet`] if `path` doesn't exist or its contents can't be parsed,
+/// since losing the persisted peer set isn't fatal: `Peerset` simply rebuilds its connectivity
+/// from scratch the way it always did before this persistence hook existed.
+pub fn load(path: &Path) -> ReloadedPeerSet {
+	let bytes = match std::fs::read(path) {
+		Ok(bytes) => bytes,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => return ReloadedPeerSet::default(),
+		Err(error) => {
+			log::debug!(target: LOG_TARGET, "failed to read persisted peer set from {}: {error}", path.display());
+			return ReloadedPeerSet::default()
+		},
+	};
+
+	let persisted = match serde_json::from_slice::<PersistedPeerSet>(&bytes) {
+		Ok(persisted) => persisted,
+		Err(error) => {
+			log::debug!(target: LOG_TARGET, "failed to parse persisted peer set at {}: {error}", path.display());
+			return ReloadedPeerSet::default()
+		},
+	};
+
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+	let mut reloaded = ReloadedPeerSet::default();
+
+	for entry in persisted.peers {
+		if now.saturating_sub(entry.last_seen) > STALE_PEER_TTL.as_secs() {
+			log::trace!(target: LOG_TARGET, "discarding stale persisted peer {:?}", entry.peer);
+			continue
+		}
+
+		if entry.reserved {
+			reloaded.reserved.push(entry.peer);
+		}
+
+		reloaded.scores.insert(entry.peer, entry.score);
+	}
+
+	reloaded
+}
+
+/// Persist the current peer set to `path`, overwriting any previous contents.
+///
+/// Called on graceful shutdown; errors are logged and otherwise ignored, as a failure to persist
+/// the peer set only costs the warm-start benefit on the next restart, not correctness.
+pub fn store(path: &Path, reserved_peers: &std::collections::HashSet<PeerId>, scores: &HashMap<PeerId, f64>) {
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+	let mut peers: Vec<_> = scores
+		.iter()
+		.map(|(peer, score)| PersistedPeer {
+			peer: *peer,
+			reserved: reserved_peers.contains(peer),
+			score: *score,
+			last_seen: now,
+		})
+		.collect();
+
+	// reserved peers might not have an entry in `scores` if they never had their score adjusted,
+	// but they're the most valuable entries to remember, so make sure they're persisted too.
+	for peer in reserved_peers {
+		if !scores.contains_key(peer) {
+			peers.push(PersistedPeer { peer: *peer, reserved: true, score: 0.0, last_seen: now });
+		}
+	}
+
+	let persisted = PersistedPeerSet { peers };
+
+	let bytes = match serde_json::to_vec_pretty(&persisted) {
+		Ok(bytes) => bytes,
+		Err(error) => {
+			log::warn!(target: LOG_TARGET, "failed to serialize peer set for persistence: {error}");
+			return
+		},
+	};
+
+	if let Err(error) = std::fs::write(path, bytes) {
+		log::warn!(target: LOG_TARGET, "failed to persist peer set to {}: {error}", path.display());
+	}
+}
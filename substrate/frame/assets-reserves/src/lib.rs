@@ -22,7 +22,8 @@
 //!
 //! ## Overview
 //!
-//! The AssetsReserves pallet provides means of configuring reserve locations for `pallet-assets`.
+//! The AssetsReserves pallet provides means of configuring reserve locations, and locations
+//! trusted to teleport with, for `pallet-assets`.
 //!
 //! The supported dispatchable functions are documented in the [`Call`] enum.
 //!
@@ -30,13 +31,15 @@
 //!
 //! * **Asset reserve(s)**: The reserve location(s) of a given asset in the context of cross-chain
 //!   reserve-based transfers.
+//! * **Teleport-trusted location(s)**: The location(s) a given asset is allowed to be
+//!   teleported (burned/minted) with.
 //!
 //! ### Goals
 //!
 //! The assets-reserves system in Substrate is designed to make the following possible:
 //!
-//! * Providing means to configure and manage cross-chain reserve locations for assets managed by a
-//!   local `pallet-assets` instance.
+//! * Providing means to configure and manage cross-chain reserve locations, and teleport-trusted
+//!   locations, for assets managed by a local `pallet-assets` instance.
 //!
 //! * Assets can be transferred across chains using either a reserve-based, or teleport transfer.
 //! * A reserve-based transfer implies that a chain acting as a trusted reserve for the transferred
@@ -45,8 +48,8 @@
 //!   is only allowed if both origin and destination chains are trusted reserves for the teleported
 //!   asset.
 //!
-//! * This pallet facilitates reserve locations configurations, and thus cross-chain transfer
-//!   possibilities, for assets managed by a local `pallet-assets` instance.
+//! * This pallet facilitates reserve locations and teleport-trust configurations, and thus
+//!   cross-chain transfer possibilities, for assets managed by a local `pallet-assets` instance.
 //!
 //! ## Interface
 //!
@@ -66,7 +69,10 @@
 extern crate alloc;
 
 use alloc::{boxed::Box, vec::Vec};
-use frame_support::traits::{fungibles::Inspect, EnsureOriginWithArg};
+use core::marker::PhantomData;
+use frame_support::traits::{fungibles::Inspect, ContainsPair, EnsureOriginWithArg};
+use sp_runtime::traits::MaybeEquivalence;
+use xcm::latest::{Asset, Location};
 
 pub use pallet::*;
 pub use weights::WeightInfo;
@@ -76,14 +82,59 @@ pub trait ProvideAssetReserves<A, R> {
 	fn reserves(id: &A) -> Vec<R>;
 }
 
+/// Adapts [`Pallet`] as the reserve-trust source for `xcm_executor::Config::IsReserve`.
+///
+/// `AssetConverter` maps an incoming [`Asset`]'s location to the pallet's `T::AssetId`, and
+/// `origin` is accepted iff it matches one of the configured [`pallet::ReserveLocations`] for
+/// that asset. This lets the asset's trusted reserves be managed on-chain through the pallet's
+/// `update` call, rather than requiring a hard-coded `Contains` implementation in the runtime.
+pub struct ReservesAsXcmFilter<T, I, AssetConverter>(PhantomData<(T, I, AssetConverter)>);
+
+impl<T, I, AssetConverter> ContainsPair<Asset, Location>
+	for ReservesAsXcmFilter<T, I, AssetConverter>
+where
+	T: Config<I>,
+	I: 'static,
+	AssetConverter: MaybeEquivalence<Location, T::AssetId>,
+	T::Reserve: PartialEq<Location>,
+{
+	fn contains(asset: &Asset, origin: &Location) -> bool {
+		let Some(asset_id) = AssetConverter::convert(&asset.id.0) else { return false };
+		ReserveLocations::<T, I>::get(&asset_id).iter().any(|reserve| reserve == origin)
+	}
+}
+
+/// Adapts [`Pallet`] as the teleport-trust source for `xcm_executor::Config::IsTeleporter`.
+///
+/// `AssetConverter` maps an incoming [`Asset`]'s location to the pallet's `T::AssetId`, and
+/// `origin` is accepted iff it matches one of the configured [`pallet::TeleportTrusted`]
+/// locations for that asset. This lets the exact set of chains an asset is teleported with be
+/// managed on-chain through the pallet's `update_teleport` call, separately from its reserve
+/// set.
+pub struct TeleportsAsXcmFilter<T, I, AssetConverter>(PhantomData<(T, I, AssetConverter)>);
+
+impl<T, I, AssetConverter> ContainsPair<Asset, Location>
+	for TeleportsAsXcmFilter<T, I, AssetConverter>
+where
+	T: Config<I>,
+	I: 'static,
+	AssetConverter: MaybeEquivalence<Location, T::AssetId>,
+	T::Reserve: PartialEq<Location>,
+{
+	fn contains(asset: &Asset, origin: &Location) -> bool {
+		let Some(asset_id) = AssetConverter::convert(&asset.id.0) else { return false };
+		TeleportTrusted::<T, I>::get(&asset_id).iter().any(|teleporter| teleporter == origin)
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
-	/// The maximum number of configurable reserve locations for one asset class.
-	const MAX_RESERVES: u32 = 5;
+	/// The maximum number of configurable teleport-trusted locations for one asset class.
+	const MAX_TELEPORTS: u32 = 5;
 
 	#[pallet::pallet]
 	pub struct Pallet<T, I = ()>(_);
@@ -101,6 +152,10 @@ pub mod pallet {
 		/// Identifier for a reserve location for a class of asset.
 		type Reserve: Parameter + MaybeSerializeDeserialize + MaxEncodedLen;
 
+		/// The maximum number of configurable reserve locations for one asset class.
+		#[pallet::constant]
+		type MaxReserves: Get<u32>;
+
 		/// Reserve management is only allowed if the origin attempting it and the asset class are
 		/// in this set.
 		type ManagerOrigin: EnsureOriginWithArg<
@@ -127,7 +182,17 @@ pub mod pallet {
 		_,
 		Blake2_128Concat,
 		T::AssetId,
-		BoundedVec<T::Reserve, ConstU32<MAX_RESERVES>>,
+		BoundedVec<T::Reserve, T::MaxReserves>,
+		ValueQuery,
+	>;
+
+	/// Maps an asset to a list of locations it is trusted to be teleported with.
+	#[pallet::storage]
+	pub type TeleportTrusted<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		BoundedVec<T::Reserve, ConstU32<MAX_TELEPORTS>>,
 		ValueQuery,
 	>;
 
@@ -136,6 +201,8 @@ pub mod pallet {
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
 		/// Genesis assets and their reserves
 		pub reserves: Vec<(T::AssetId, Vec<T::Reserve>)>,
+		/// Genesis assets and their teleport-trusted locations
+		pub teleports: Vec<(T::AssetId, Vec<T::Reserve>)>,
 	}
 
 	#[pallet::genesis_build]
@@ -143,11 +210,17 @@ pub mod pallet {
 		fn build(&self) {
 			for (id, reserves) in &self.reserves {
 				assert!(!ReserveLocations::<T, I>::contains_key(id), "Asset id already in use");
-				let reserves =
-					BoundedVec::<T::Reserve, ConstU32<MAX_RESERVES>>::try_from(reserves.clone())
-						.expect("too many reserves");
+				let reserves = BoundedVec::<T::Reserve, T::MaxReserves>::try_from(reserves.clone())
+					.expect("too many reserves");
 				ReserveLocations::<T, I>::insert(id, reserves);
 			}
+			for (id, teleports) in &self.teleports {
+				assert!(!TeleportTrusted::<T, I>::contains_key(id), "Asset id already in use");
+				let teleports =
+					BoundedVec::<T::Reserve, ConstU32<MAX_TELEPORTS>>::try_from(teleports.clone())
+						.expect("too many teleports");
+				TeleportTrusted::<T, I>::insert(id, teleports);
+			}
 		}
 	}
 
@@ -158,6 +231,14 @@ pub mod pallet {
 		AssetReservesUpdated { asset_id: T::AssetId, reserves: Vec<T::Reserve> },
 		// Reserve locations removed for `asset_id`.
 		AssetReservesRemoved { asset_id: T::AssetId },
+		// A single reserve location was added for `asset_id`.
+		AssetReserveAdded { asset_id: T::AssetId, reserve: T::Reserve },
+		// A single reserve location was removed for `asset_id`.
+		AssetReserveRemoved { asset_id: T::AssetId, reserve: T::Reserve },
+		// Teleport-trusted locations updated for `asset_id`.
+		AssetTeleportsUpdated { asset_id: T::AssetId, teleports: Vec<T::Reserve> },
+		// Teleport-trusted locations removed for `asset_id`.
+		AssetTeleportsRemoved { asset_id: T::AssetId },
 	}
 
 	#[pallet::error]
@@ -166,6 +247,12 @@ pub mod pallet {
 		UnknownAssetId,
 		/// Tried setting too many reserves.
 		TooManyReserves,
+		/// Tried setting too many teleport-trusted locations.
+		TooManyTeleports,
+		/// The given reserve is not configured for the asset.
+		UnknownReserve,
+		/// The given reserve is already configured for the asset.
+		ReserveAlreadyExists,
 	}
 
 	#[pallet::call]
@@ -209,6 +296,92 @@ pub mod pallet {
 			}
 			Ok(())
 		}
+
+		/// Update the teleport-trusted locations for the given asset.
+		///
+		/// ## Complexity
+		/// - O(1)
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::update_teleport())]
+		pub fn update_teleport(
+			origin: OriginFor<T>,
+			id: Box<T::AssetId>,
+			teleports: Vec<T::Reserve>,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin, &id)?;
+			ensure!(T::AssetInspect::asset_exists(*id.clone()), Error::<T, I>::UnknownAssetId);
+			if teleports.is_empty() {
+				TeleportTrusted::<T, I>::remove(id.as_ref());
+				Self::deposit_event(Event::AssetTeleportsRemoved { asset_id: *id });
+			} else {
+				let bounded_teleports =
+					teleports.clone().try_into().map_err(|_| Error::<T, I>::TooManyTeleports)?;
+				TeleportTrusted::<T, I>::set(id.as_ref(), bounded_teleports);
+				Self::deposit_event(Event::AssetTeleportsUpdated { asset_id: *id, teleports });
+			}
+			Ok(())
+		}
+
+		/// Remove teleport-trusted information for destroyed asset classes.
+		///
+		/// ## Complexity
+		/// - O(1)
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::remove_teleport())]
+		pub fn touch_teleport(origin: OriginFor<T>, id: Box<T::AssetId>) -> DispatchResult {
+			ensure_signed(origin)?;
+			if !T::AssetInspect::asset_exists(*id.clone()) {
+				TeleportTrusted::<T, I>::remove(id.as_ref());
+				Self::deposit_event(Event::AssetTeleportsRemoved { asset_id: *id });
+			}
+			Ok(())
+		}
+
+		/// Add a single reserve location to the given asset's reserve list, without disturbing
+		/// the rest of the list.
+		///
+		/// ## Complexity
+		/// - O(n) in the number of currently configured reserves.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::add_reserve())]
+		pub fn add_reserve(
+			origin: OriginFor<T>,
+			id: Box<T::AssetId>,
+			reserve: T::Reserve,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin, &id)?;
+			ensure!(T::AssetInspect::asset_exists(*id.clone()), Error::<T, I>::UnknownAssetId);
+			ReserveLocations::<T, I>::try_mutate(id.as_ref(), |reserves| {
+				ensure!(!reserves.contains(&reserve), Error::<T, I>::ReserveAlreadyExists);
+				reserves.try_push(reserve.clone()).map_err(|_| Error::<T, I>::TooManyReserves)
+			})?;
+			Self::deposit_event(Event::AssetReserveAdded { asset_id: *id, reserve });
+			Ok(())
+		}
+
+		/// Remove a single reserve location from the given asset's reserve list, without
+		/// disturbing the rest of the list.
+		///
+		/// ## Complexity
+		/// - O(n) in the number of currently configured reserves.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::remove_reserve())]
+		pub fn remove_reserve(
+			origin: OriginFor<T>,
+			id: Box<T::AssetId>,
+			reserve: T::Reserve,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin, &id)?;
+			ensure!(T::AssetInspect::asset_exists(*id.clone()), Error::<T, I>::UnknownAssetId);
+			ReserveLocations::<T, I>::try_mutate(id.as_ref(), |reserves| {
+				let position =
+					reserves.iter().position(|r| r == &reserve).ok_or(Error::<T, I>::UnknownReserve)?;
+				reserves.remove(position);
+				Ok::<_, Error<T, I>>(())
+			})?;
+			Self::deposit_event(Event::AssetReserveRemoved { asset_id: *id, reserve });
+			Ok(())
+		}
 	}
 
 	impl<T: Config<I>, I: 'static> ProvideAssetReserves<T::AssetId, T::Reserve> for Pallet<T, I> {
@@ -133,8 +133,14 @@ pub mod pallet {
 	#[pallet::pallet]
 	pub struct Pallet<T, I = ()>(_);
 
-	pub type GenesisVestingSchedule<T, I> =
-		(AssetIdOf<T, I>, AccountIdOf<T>, BlockNumberFor<T>, BlockNumberFor<T>, BalanceOf<T, I>);
+	pub type GenesisVestingSchedule<T, I> = (
+		AssetIdOf<T, I>,
+		AccountIdOf<T>,
+		BlockNumberFor<T>,
+		BlockNumberFor<T>,
+		BalanceOf<T, I>,
+		Option<BlockNumberFor<T>>,
+	);
 
 	#[pallet::genesis_config]
 	#[derive(DefaultNoBound)]
@@ -151,7 +157,8 @@ pub mod pallet {
 			// * begin - Block when the account will start to vest
 			// * length - Number of blocks from `begin` until fully vested
 			// * liquid - Number of units which can be spent before vesting begins
-			for &(ref asset, ref who, begin, length, liquid) in self.vesting.iter() {
+			// * cliff - Optional block before which nothing thaws, even past `begin`
+			for &(ref asset, ref who, begin, length, liquid, cliff) in self.vesting.iter() {
 				let balance = T::Assets::total_balance(asset.clone(), who);
 				assert!(!balance.is_zero(), "Assets must be init'd before vesting");
 
@@ -160,7 +167,14 @@ pub mod pallet {
 				let length_as_balance = T::BlockNumberToBalance::convert(length);
 				let per_block = (frozen / length_as_balance.max(One::one())).max(One::one());
 
-				Pallet::<T, I>::add_vesting_schedule(asset.clone(), who, frozen, per_block, begin)
+				Pallet::<T, I>::add_vesting_schedule_with_cliff(
+					asset.clone(),
+					who,
+					frozen,
+					per_block,
+					begin,
+					cliff,
+				)
 					.map_err(|err| {
 						let DispatchError::Module(ModuleError { message: Some(message), .. }) =
 							err.into()
@@ -480,6 +494,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		};
 
 		let schedule = VestingInfo::new(frozen, per_block, starting_block);
+		// Preserve the later of the two cliffs, if either schedule had one, so merging can't be
+		// used to thaw funds earlier than either original schedule allowed.
+		let schedule = match schedule1.cliff().into_iter().chain(schedule2.cliff()).max() {
+			Some(cliff) => schedule.with_cliff(cliff.max(starting_block)),
+			None => schedule,
+		};
 		debug_assert!(schedule.is_valid(), "merge_vesting_info schedule validation check failed");
 
 		Some(schedule)
@@ -707,20 +727,27 @@ where
 	}
 }
 
-impl<T: Config<I>, I: 'static> VestedMutate<T::AccountId> for Pallet<T, I> {
-	fn add_vesting_schedule(
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// As [`VestedMutate::add_vesting_schedule`], but also attaches `cliff` (if given) to the new
+	/// schedule. Used by genesis config, which (unlike the public trait) can express a cliff.
+	fn add_vesting_schedule_with_cliff(
 		asset: AssetIdOf<T, I>,
 		who: &T::AccountId,
 		locked: BalanceOf<T, I>,
 		per_block: BalanceOf<T, I>,
 		starting_block: BlockNumberFor<T>,
+		cliff: Option<BlockNumberFor<T>>,
 	) -> DispatchResult {
 		if locked.is_zero() {
 			return Ok(())
 		}
 
 		let vesting_schedule = VestingInfo::new(locked, per_block, starting_block);
-		// Check for `per_block` or `locked` of 0.
+		let vesting_schedule = match cliff {
+			Some(cliff) => vesting_schedule.with_cliff(cliff),
+			None => vesting_schedule,
+		};
+		// Check for `per_block` or `locked` of 0, or an invalid cliff.
 		if !vesting_schedule.is_valid() {
 			return Err(Error::<T, I>::InvalidScheduleParams.into())
 		};
@@ -739,6 +766,18 @@ impl<T: Config<I>, I: 'static> VestedMutate<T::AccountId> for Pallet<T, I> {
 
 		Ok(())
 	}
+}
+
+impl<T: Config<I>, I: 'static> VestedMutate<T::AccountId> for Pallet<T, I> {
+	fn add_vesting_schedule(
+		asset: AssetIdOf<T, I>,
+		who: &T::AccountId,
+		locked: BalanceOf<T, I>,
+		per_block: BalanceOf<T, I>,
+		starting_block: BlockNumberFor<T>,
+	) -> DispatchResult {
+		Self::add_vesting_schedule_with_cliff(asset, who, locked, per_block, starting_block, None)
+	}
 
 	fn remove_vesting_schedule(
 		asset: AssetIdOf<T, I>,
@@ -114,7 +114,8 @@ pub(crate) struct AssetsGenesis {
 
 pub struct ExtBuilder {
 	assets: Option<Vec<AssetsGenesis>>,
-	vesting_genesis_config: Option<Vec<(AssetId, AccountId, BlockNumber, BlockNumber, Balance)>>,
+	vesting_genesis_config:
+		Option<Vec<(AssetId, AccountId, BlockNumber, BlockNumber, Balance, Option<BlockNumber>)>>,
 }
 
 impl Default for ExtBuilder {
@@ -134,9 +135,9 @@ impl ExtBuilder {
 				.map(|(who, amount)| (*who, *amount * minimum_balance))
 				.collect(),
 		)
-		.with_vesting_genesis_config((id, 1, 0, 10, 5 * minimum_balance))
-		.with_vesting_genesis_config((id, 2, 10, 20, 0))
-		.with_vesting_genesis_config((id, 12, 10, 20, 5 * minimum_balance))
+		.with_vesting_genesis_config((id, 1, 0, 10, 5 * minimum_balance, None))
+		.with_vesting_genesis_config((id, 2, 10, 20, 0, None))
+		.with_vesting_genesis_config((id, 12, 10, 20, 5 * minimum_balance, None))
 	}
 
 	pub fn with_asset(
@@ -154,7 +155,7 @@ impl ExtBuilder {
 
 	pub fn with_vesting_genesis_config(
 		mut self,
-		config: (AssetId, AccountId, BlockNumber, BlockNumber, Balance),
+		config: (AssetId, AccountId, BlockNumber, BlockNumber, Balance, Option<BlockNumber>),
 	) -> Self {
 		let mut vesting_genesis_config = self.vesting_genesis_config.unwrap_or(vec![]);
 		vesting_genesis_config.push(config);
@@ -210,9 +211,9 @@ impl ExtBuilder {
 			vesting: self
 				.vesting_genesis_config
 				.unwrap_or(vec![
-					(assets[0].id, 1, 0, 10, 5 * assets[0].minimum_balance),
-					(assets[0].id, 2, 10, 20, 0),
-					(assets[0].id, 12, 10, 20, 5 * assets[0].minimum_balance),
+					(assets[0].id, 1, 0, 10, 5 * assets[0].minimum_balance, None),
+					(assets[0].id, 2, 10, 20, 0, None),
+					(assets[0].id, 12, 10, 20, 5 * assets[0].minimum_balance, None),
 				])
 				.into_iter()
 				.collect(),
@@ -1301,17 +1301,17 @@ mod genesis_config {
 				],
 			)
 			// 5 * existential deposit locked.
-			.with_vesting_genesis_config((ASSET_ID, 1, 0, 10, 5 * MINIMUM_BALANCE))
+			.with_vesting_genesis_config((ASSET_ID, 1, 0, 10, 5 * MINIMUM_BALANCE, None))
 			// 1 * existential deposit locked.
-			.with_vesting_genesis_config((ASSET_ID, 2, 10, 20, 19 * MINIMUM_BALANCE))
+			.with_vesting_genesis_config((ASSET_ID, 2, 10, 20, 19 * MINIMUM_BALANCE, None))
 			// 2 * existential deposit locked.
-			.with_vesting_genesis_config((ASSET_ID, 2, 10, 20, 18 * MINIMUM_BALANCE))
+			.with_vesting_genesis_config((ASSET_ID, 2, 10, 20, 18 * MINIMUM_BALANCE, None))
 			// 1 * existential deposit locked.
-			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, 9 * MINIMUM_BALANCE))
+			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, 9 * MINIMUM_BALANCE, None))
 			// 2 * existential deposit locked.
-			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, 8 * MINIMUM_BALANCE))
+			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, 8 * MINIMUM_BALANCE, None))
 			// 3 * existential deposit locked.
-			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, 7 * MINIMUM_BALANCE))
+			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, 7 * MINIMUM_BALANCE, None))
 			.build()
 			.execute_with(|| {
 				let user1_sched1 = VestingInfo::new(5 * MINIMUM_BALANCE, 128, 0u64);
@@ -1341,10 +1341,10 @@ mod genesis_config {
 		// from genesis.
 		ExtBuilder::default()
 			.with_asset(ASSET_ID, 1, MINIMUM_BALANCE, vec![(12, 5 * MINIMUM_BALANCE)])
-			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, MINIMUM_BALANCE))
-			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, MINIMUM_BALANCE))
-			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, MINIMUM_BALANCE))
-			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, MINIMUM_BALANCE))
+			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, MINIMUM_BALANCE, None))
+			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, MINIMUM_BALANCE, None))
+			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, MINIMUM_BALANCE, None))
+			.with_vesting_genesis_config((ASSET_ID, 12, 10, 20, MINIMUM_BALANCE, None))
 			.build();
 	}
 }
@@ -1376,6 +1376,20 @@ mod vesting_info {
 			});
 	}
 
+	#[test]
+	fn merge_vesting_info_carries_the_later_cliff() {
+		ExtBuilder::default()
+			.with_min_balance(ASSET_ID, MINIMUM_BALANCE)
+			.build()
+			.execute_with(|| {
+				let sched0 = VestingInfo::new(MINIMUM_BALANCE, 0, 1).with_cliff(20);
+				let sched1 = VestingInfo::new(MINIMUM_BALANCE * 2, 0, 10);
+
+				let merged = AssetsVesting::merge_vesting_info(5, sched0, sched1).unwrap();
+				assert_eq!(merged.cliff(), Some(20));
+			});
+	}
+
 	#[test]
 	fn vesting_info_validate_works() {
 		let min_transfer = MIN_VESTED_TRANSFER;
@@ -1390,6 +1404,22 @@ mod vesting_info {
 
 		// With valid inputs it does not error.
 		assert_eq!(VestingInfo::new(min_transfer, 1u64, 10u64).is_valid(), true);
+
+		// A cliff at or after `starting_block` is valid.
+		assert_eq!(VestingInfo::new(min_transfer, 1u64, 10u64).with_cliff(10u64).is_valid(), true);
+		assert_eq!(VestingInfo::new(min_transfer, 1u64, 10u64).with_cliff(20u64).is_valid(), true);
+
+		// A cliff before `starting_block` is invalid.
+		assert_eq!(VestingInfo::new(min_transfer, 1u64, 10u64).with_cliff(9u64).is_valid(), false);
+	}
+
+	#[test]
+	fn vesting_info_cliff_blocks_thawing() {
+		// Nothing thaws before the cliff, even though `starting_block` has already passed.
+		let sched = VestingInfo::new(MIN_VESTED_TRANSFER, 1u64, 0u64).with_cliff(50u64);
+		assert_eq!(sched.locked_at::<Identity>(49), MIN_VESTED_TRANSFER);
+		assert_eq!(sched.locked_at::<Identity>(50), MIN_VESTED_TRANSFER - 50);
+		assert_eq!(sched.locked_at::<Identity>(100), MIN_VESTED_TRANSFER - 100);
 	}
 
 	#[test]
@@ -94,6 +94,9 @@ pub struct VestingInfo<Balance, BlockNumber> {
 	per_block: Balance,
 	/// Starting block for unlocking(vesting).
 	starting_block: BlockNumber,
+	/// Block before which nothing unlocks, even though `starting_block` has passed. `None` means
+	/// there is no cliff and thawing follows `starting_block` as usual.
+	cliff: Option<BlockNumber>,
 }
 
 impl<Balance, BlockNumber> VestingInfo<Balance, BlockNumber>
@@ -101,19 +104,33 @@ where
 	Balance: AtLeast32BitUnsigned + Copy,
 	BlockNumber: AtLeast32BitUnsigned + Copy + Bounded,
 {
-	/// Instantiate a new `VestingInfo`.
+	/// Instantiate a new `VestingInfo`, with no cliff.
 	pub fn new(
 		frozen: Balance,
 		per_block: Balance,
 		starting_block: BlockNumber,
 	) -> VestingInfo<Balance, BlockNumber> {
-		VestingInfo { frozen, per_block, starting_block }
+		VestingInfo { frozen, per_block, starting_block, cliff: None }
+	}
+
+	/// Attach a cliff block to this schedule: no funds thaw before `cliff`, even though
+	/// `starting_block` has already passed.
+	pub fn with_cliff(mut self, cliff: BlockNumber) -> Self {
+		self.cliff = Some(cliff);
+		self
+	}
+
+	/// The cliff block, if one was set.
+	pub fn cliff(&self) -> Option<BlockNumber> {
+		self.cliff
 	}
 
 	/// Validate parameters for `VestingInfo`. Note that this does not check
 	/// against `MinVestedTransfer`.
 	pub fn is_valid(&self) -> bool {
-		!self.frozen.is_zero() && !self.raw_per_block().is_zero()
+		!self.frozen.is_zero() &&
+			!self.raw_per_block().is_zero() &&
+			self.cliff.map_or(true, |cliff| cliff >= self.starting_block)
 	}
 
 	/// Locked amount at schedule creation.
@@ -144,6 +161,13 @@ where
 		&self,
 		n: BlockNumber,
 	) -> Balance {
+		if let Some(cliff) = self.cliff {
+			if n < cliff {
+				// Nothing thaws before the cliff, regardless of `starting_block`.
+				return self.frozen
+			}
+		}
+
 		// Number of blocks that count toward vesting;
 		// saturating to 0 when n < starting_block.
 		let vested_block_count = n.saturating_sub(self.starting_block);
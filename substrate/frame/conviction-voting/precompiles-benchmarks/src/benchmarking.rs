@@ -314,6 +314,93 @@ mod benchmarks {
 		assert!(result.is_ok());
 	}
 
+	#[benchmark(pov_mode = Measured)]
+	fn remove_vote(
+		r: Linear<0, { T::MaxVotes::get().min(T::Polls::max_ongoing().1).saturating_sub(1) }>,
+	) {
+		let all_polls = fill_voting::<T, ()>().1;
+		let class = T::Polls::max_ongoing().0;
+		let polls = &all_polls[&class];
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+
+		let vote = Vote { aye: true, conviction: Conviction::Locked1x };
+		let balance: BalanceOf<T, ()> = 10u32.into();
+		let dummy_vote = AccountVote::Standard { vote, balance };
+
+		// Cast `r` filler votes plus the one we are about to remove, to stress the O(R) scan
+		// over the account's existing votes.
+		for i in polls.iter().take((r + 1) as usize) {
+			ConvictionVoting::<T>::vote(RawOrigin::Signed(caller.clone()).into(), *i, dummy_vote)
+				.unwrap();
+		}
+
+		let track_id: u16 = class.clone().try_into().ok().unwrap();
+		let referendum_index: u32 = polls[r as usize].try_into().ok().unwrap();
+
+		let call = IConvictionVoting::IConvictionVotingCalls::removeVote(
+			IConvictionVoting::removeVoteCall { trackId: track_id, referendumIndex: referendum_index },
+		);
+
+		let mut call_setup = CallSetup::<T>::default();
+		call_setup.set_origin(ExecOrigin::<T>::Signed(caller));
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = run_precompile::<ConvictionVotingPrecompile<T>, _>(
+				&mut ext,
+				H160::from_low_u64_be(0xC0000).as_fixed_bytes(),
+				&call,
+			);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn unlock(r: Linear<0, { T::MaxVotes::get().min(T::Polls::max_ongoing().1) }>) {
+		let all_polls = fill_voting::<T, ()>().1;
+		let class = T::Polls::max_ongoing().0;
+		let polls = &all_polls[&class];
+		let target = funded_mapped_account::<T, ()>("target", 0);
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+
+		let vote = Vote { aye: true, conviction: Conviction::Locked1x };
+		let balance: BalanceOf<T, ()> = 10u32.into();
+		let dummy_vote = AccountVote::Standard { vote, balance };
+
+		// Cast `r` votes for `target` in the class so `unlock` has to scan through them to
+		// recompute the lock.
+		for i in polls.iter().take(r as usize) {
+			ConvictionVoting::<T>::vote(RawOrigin::Signed(target.clone()).into(), *i, dummy_vote)
+				.unwrap();
+		}
+
+		let track_id: u16 = class.clone().try_into().ok().unwrap();
+
+		let call = IConvictionVoting::IConvictionVotingCalls::unlock(IConvictionVoting::unlockCall {
+			trackId: track_id,
+			target: T::AddressMapper::to_address(&target).0.into(),
+		});
+
+		let mut call_setup = CallSetup::<T>::default();
+		call_setup.set_origin(ExecOrigin::<T>::Signed(caller));
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = run_precompile::<ConvictionVotingPrecompile<T>, _>(
+				&mut ext,
+				H160::from_low_u64_be(0xC0000).as_fixed_bytes(),
+				&call,
+			);
+		}
+
+		assert!(result.is_ok());
+	}
+
 	impl_benchmark_test_suite!(
 		ConvictionVotingPrecompilesBenchmarks,
 		crate::mock::new_test_ext(),
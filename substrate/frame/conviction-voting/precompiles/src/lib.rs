@@ -301,7 +301,34 @@ where
 						|res| res.abi_encode(),
 					))
 			},
-			_ => todo!(),
+			IConvictionVotingCalls::removeVote(IConvictionVoting::removeVoteCall {
+				trackId,
+				referendumIndex,
+			}) => {
+				let _ = env.charge(<() as WeightInfo>::remove_vote())?;
+
+				pallet_conviction_voting::Pallet::<T>::remove_vote(
+					frame_origin,
+					Some(Self::u16_to_track_id(trackId)?),
+					Self::u32_to_referendum_index(referendumIndex)?,
+				)
+				.map(|_| Vec::new())
+				.map_err(|error| revert(&error, "ConvictionVoting: remove vote failed"))
+			},
+			IConvictionVotingCalls::unlock(IConvictionVoting::unlockCall { trackId, target }) => {
+				let _ = env.charge(<() as WeightInfo>::unlock())?;
+
+				let target_account_id = T::AddressMapper::to_account_id(&H160::from(target.0 .0));
+				let target_source = T::Lookup::unlookup(target_account_id);
+
+				pallet_conviction_voting::Pallet::<T>::unlock(
+					frame_origin,
+					Self::u16_to_track_id(trackId)?,
+					target_source,
+				)
+				.map(|_| Vec::new())
+				.map_err(|error| revert(&error, "ConvictionVoting: unlock failed"))
+			},
 		}
 	}
 }
@@ -126,6 +126,21 @@ fn encode_undelegate(track_id: TrackId) -> Vec<u8> {
 	call.abi_encode()
 }
 
+fn encode_remove_vote(track_id: TrackId, referendum_index: ReferendumIndex) -> Vec<u8> {
+	let call_params =
+		IConvictionVoting::removeVoteCall { trackId: track_id, referendumIndex: referendum_index };
+	let call = IConvictionVoting::IConvictionVotingCalls::removeVote(call_params);
+	call.abi_encode()
+}
+
+fn encode_unlock(track_id: TrackId, target: AccountId) -> Vec<u8> {
+	let mapped_target = <Test as pallet_revive::Config>::AddressMapper::to_address(&target);
+	let call_params =
+		IConvictionVoting::unlockCall { trackId: track_id, target: mapped_target.0.into() };
+	let call = IConvictionVoting::IConvictionVotingCalls::unlock(call_params);
+	call.abi_encode()
+}
+
 fn encode_get_voting(
 	who: AccountId,
 	track_id: TrackId,
@@ -558,6 +573,81 @@ fn test_undelegate_not_delegating_error() {
 	});
 }
 
+#[test]
+fn test_remove_vote_encoding() {
+	let track_id = 2u16;
+	let referendum_index = 3u32;
+
+	let encoded_call = encode_remove_vote(track_id, referendum_index);
+
+	let decoded_call = IConvictionVoting::removeVoteCall::abi_decode(&encoded_call).unwrap();
+
+	assert_eq!(decoded_call.referendumIndex, referendum_index);
+}
+
+#[test]
+fn test_remove_vote_precompile_works() {
+	new_test_ext().execute_with(|| {
+		let referendum_index = 3u32;
+		let balance = 2u128;
+		let conviction = 5u8;
+
+		assert!(call_and_check_revert(
+			ALICE,
+			encode_standard(referendum_index, true, balance, conviction)
+		));
+		assert_eq!(tally(referendum_index), Tally::from_parts(10, 0, 2));
+
+		assert!(call_and_check_revert(ALICE, encode_remove_vote(class(referendum_index), referendum_index)));
+
+		assert_eq!(tally(referendum_index), Tally::from_parts(0, 0, 0));
+	});
+}
+
+#[test]
+fn test_remove_vote_no_vote_error() {
+	new_test_ext().execute_with(|| {
+		let track_id = 0u16;
+		let referendum_index = 3u32;
+		assert!(!call_and_check_revert(ALICE, encode_remove_vote(track_id, referendum_index)));
+	});
+}
+
+#[test]
+fn test_unlock_encoding() {
+	let track_id = 2u16;
+
+	let encoded_call = encode_unlock(track_id, BOB);
+
+	let decoded_call = IConvictionVoting::unlockCall::abi_decode(&encoded_call).unwrap();
+
+	assert_eq!(decoded_call.trackId, track_id);
+}
+
+#[test]
+fn test_unlock_precompile_works() {
+	new_test_ext().execute_with(|| {
+		let referendum_index = 3u32;
+		let balance = 2u128;
+		let conviction = 5u8;
+
+		assert!(call_and_check_revert(
+			ALICE,
+			encode_standard(referendum_index, true, balance, conviction)
+		));
+
+		let prev_balance = Balances::usable_balance(ALICE);
+		assert!(call_and_check_revert(
+			ALICE,
+			encode_remove_vote(class(referendum_index), referendum_index)
+		));
+
+		assert!(call_and_check_revert(ALICE, encode_unlock(class(referendum_index), ALICE)));
+
+		assert!(Balances::usable_balance(ALICE) >= prev_balance);
+	});
+}
+
 #[test]
 fn test_get_voting_encoding() {
 	let who = ALICE;
@@ -74,13 +74,17 @@ mod tests;
 mod benchmarking;
 
 use sp_application_crypto::RuntimeAppPublic;
-use codec::{Encode, Decode};
+use codec::{Encode, Decode, MaxEncodedLen};
 use sp_core::offchain::OpaqueNetworkState;
 use sp_std::prelude::*;
 use sp_std::convert::TryInto;
 use pallet_session::historical::IdentificationTuple;
 use sp_runtime::{
-	offchain::storage::StorageValueRef,
+	offchain::{
+		http,
+		storage::StorageValueRef,
+		storage_lock::{BlockAndTime, StorageLock},
+	},
 	RuntimeDebug,
 	traits::{Convert, Member, Saturating, AtLeast32BitUnsigned}, Perbill,
 	transaction_validity::{
@@ -94,14 +98,19 @@ use sp_staking::{
 };
 use frame_support::{
 	decl_module, decl_event, decl_storage, Parameter, debug, decl_error,
-	traits::Get,
+	traits::{Get, EstimateNextSessionRotation},
 	weights::Weight,
+	BoundedVec, WeakBoundedVec, PalletId,
 };
+#[cfg(feature = "rewards")]
+use frame_support::traits::fungible::{self, Mutate, Preservation};
 use frame_system::{self as system, ensure_none};
 use frame_system::offchain::{
 	SendTransactionTypes,
 	SubmitTransaction,
 };
+#[cfg(feature = "rewards")]
+use sp_runtime::traits::AccountIdConversion;
 
 pub mod sr25519 {
 	mod app_sr25519 {
@@ -143,6 +152,12 @@ const DB_PREFIX: &[u8] = b"parity/im-online-heartbeat/";
 /// How many blocks do we wait for heartbeat transaction to be included
 /// before sending another one.
 const INCLUDE_THRESHOLD: u32 = 3;
+/// Wall-clock timeout for the per-authority send lock, on top of the block-number deadline.
+/// The lock expires only once both this timeout and the `INCLUDE_THRESHOLD`-block deadline
+/// have passed, so a worker that crashes mid-send doesn't hold it forever.
+const LOCK_TIMEOUT_MS: u64 = 60_000;
+/// How long to wait for the optional liveness probe before giving up on it.
+const LIVENESS_PROBE_DEADLINE_MS: u64 = 2_000;
 
 /// Status of the offchain worker code.
 ///
@@ -188,6 +203,9 @@ enum OffchainErr<BlockNumber> {
 	FailedToAcquireLock,
 	NetworkState,
 	SubmitTransaction,
+	/// The configured `LivenessEndpoint` did not respond successfully within its deadline, so
+	/// the heartbeat for this authority was skipped rather than sent.
+	Unreachable,
 }
 
 impl<BlockNumber: sp_std::fmt::Debug> sp_std::fmt::Debug for OffchainErr<BlockNumber> {
@@ -203,12 +221,26 @@ impl<BlockNumber: sp_std::fmt::Debug> sp_std::fmt::Debug for OffchainErr<BlockNu
 			OffchainErr::FailedToAcquireLock => write!(fmt, "Failed to acquire lock"),
 			OffchainErr::NetworkState => write!(fmt, "Failed to fetch network state"),
 			OffchainErr::SubmitTransaction => write!(fmt, "Failed to submit transaction"),
+			OffchainErr::Unreachable =>
+				write!(fmt, "Liveness endpoint did not respond within its deadline"),
 		}
 	}
 }
 
 pub type AuthIndex = u32;
 
+/// The outcome of the optional HTTP reachability probe run before a heartbeat is sent.
+///
+/// Only present when `Trait::LivenessEndpoint` is configured; absent otherwise, so that nodes
+/// without the feature enabled behave exactly as they did before it was introduced.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LivenessCheck {
+	/// The HTTP status code returned by the configured endpoint.
+	pub http_status: u16,
+	/// How long, in milliseconds, the probe took to complete.
+	pub latency_ms: u64,
+}
+
 /// Heartbeat which is sent/received.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 pub struct Heartbeat<BlockNumber>
@@ -224,22 +256,24 @@ pub struct Heartbeat<BlockNumber>
 	pub authority_index: AuthIndex,
 	/// The length of session validator set
 	pub validators_len: u32,
+	/// The outcome of the liveness probe run before this heartbeat was sent, if any.
+	pub liveness_check: Option<LivenessCheck>,
 }
 
 pub trait Trait: SendTransactionTypes<Call<Self>> + pallet_session::historical::Trait {
 	/// The identifier type for an authority.
-	type AuthorityId: Member + Parameter + RuntimeAppPublic + Default + Ord;
+	type AuthorityId: Member + Parameter + RuntimeAppPublic + Default + Ord + MaxEncodedLen;
 
 	/// The overarching event type.
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
-	/// An expected duration of the session.
+	/// An estimate of the next session rotation, used to determine a rough time when we should
+	/// start considering sending heartbeats, and the longevity of the `heartbeat` transaction.
 	///
-	/// This parameter is used to determine the longevity of `heartbeat` transaction
-	/// and a rough time when we should start considering sending heartbeats,
-	/// since the workers avoids sending them at the very beginning of the session, assuming
-	/// there is a chance the authority will produce a block and they won't be necessary.
-	type SessionDuration: Get<Self::BlockNumber>;
+	/// Sessions aren't always a fixed length (forced rotations, era changes, governance-driven
+	/// length changes), so this is re-estimated on every `on_new_session` rather than assumed
+	/// constant the way a fixed `SessionDuration` would.
+	type NextSessionRotation: EstimateNextSessionRotation<Self::BlockNumber>;
 
 	/// A type that gives us the ability to submit unresponsiveness offence reports.
 	type ReportUnresponsiveness:
@@ -254,19 +288,116 @@ pub trait Trait: SendTransactionTypes<Call<Self>> + pallet_session::historical::
 	/// This is exposed so that it can be tuned for particular runtime, when
 	/// multiple pallets send unsigned transactions.
 	type UnsignedPriority: Get<TransactionPriority>;
+
+	/// The maximum number of keys that can be added.
+	///
+	/// `Keys` is bounded with this *weakly*: a validator set that temporarily exceeds
+	/// `MaxKeys` is still stored in full (with a warning logged), since truncating
+	/// consensus-critical authority keys would be far worse than a PoV size that's
+	/// occasionally larger than expected.
+	type MaxKeys: Get<u32>;
+
+	/// The maximum size of the encoded `network_state` blob carried by a heartbeat.
+	///
+	/// Unlike `Keys`, this isn't consensus-critical, so it's bounded *strictly*: a
+	/// heartbeat whose network state doesn't fit is rejected outright.
+	type MaxPeerDataEncodingSize: Get<u32>;
+
+	/// An optional URL to probe for external reachability before sending a heartbeat.
+	///
+	/// When `Some`, `send_single_heartbeat` issues a bounded-deadline HTTP request to this
+	/// endpoint and only sends the heartbeat if it succeeds, since a validator can author
+	/// blocks and gossip heartbeats while its public RPC/p2p endpoints are unreachable from
+	/// the outside. When `None` (the default expectation), no probe is run and behavior is
+	/// unchanged from before this was introduced.
+	type LivenessEndpoint: Get<Option<&'static str>>;
+
+	/// The currency used to fund and pay session-reward payouts from the pallet's pot account.
+	///
+	/// Only required with the `rewards` feature enabled; chains that only want offence
+	/// reporting don't need to configure a currency at all.
+	#[cfg(feature = "rewards")]
+	type Currency: fungible::Inspect<Self::AccountId> + fungible::Mutate<Self::AccountId>;
+
+	/// The reward paid out of the pot to each validator that stayed online for the session.
+	#[cfg(feature = "rewards")]
+	type RewardPerSession: Get<BalanceOf<Self>>;
+
+	/// Resolves a session `ValidatorId` to the `AccountId` that should receive reward payouts.
+	#[cfg(feature = "rewards")]
+	type RewardAccountOf: Convert<Self::ValidatorId, Option<Self::AccountId>>;
+
+	/// Identifies the pallet's pot account, which funds `RewardPerSession` payouts.
+	#[cfg(feature = "rewards")]
+	type PalletId: Get<PalletId>;
+
+	/// Fraction of the validator set that may be offline before any slash applies.
+	///
+	/// Defaults to 10%, matching the pallet's original hardcoded `n / 10 + 1` grace band.
+	type OfflineGraceFraction: Get<Perbill>;
+
+	/// Slope of the unresponsiveness slash curve past the grace band.
+	///
+	/// Defaults to `3`, matching the pallet's original hardcoded curve.
+	type OfflineSlashSlope: Get<u32>;
+
+	/// Maximum slash fraction the unresponsiveness curve can reach.
+	///
+	/// Defaults to 7%, matching the pallet's original hardcoded curve.
+	type MaxOfflineSlash: Get<Perbill>;
+
+	/// Number of sessions that may pass since a validator's last unresponsiveness strike
+	/// before its strike count decays back to zero.
+	///
+	/// Keeps a validator that had one bad session years ago from being treated the same as
+	/// one that's chronically unresponsive.
+	type ReputationDecaySessions: Get<SessionIndex>;
+
+	/// Percentage points added to the escalation factor for each undecayed strike a
+	/// validator has accrued.
+	///
+	/// E.g. `25` means a validator on its second consecutive strike has its slash scaled by
+	/// `125%`, its third by `150%`, and so on, up to `MaxEscalationPercent`.
+	type EscalationStepPercent: Get<u32>;
+
+	/// Ceiling on the escalation factor described above, expressed as a percentage (`100`
+	/// means no escalation at all; `300` means repeat offenders can be slashed at up to 3x
+	/// the base curve).
+	type MaxEscalationPercent: Get<u32>;
+
+	/// Cap on how many consecutive unresponsive sessions count towards an offender's weight in
+	/// the slash curve.
+	///
+	/// A validator that just missed its first heartbeat after a long healthy streak is weighted
+	/// as `1`, the same as under the flat-count curve; one that's been unresponsive for many
+	/// sessions in a row ramps towards this cap rather than without bound.
+	type MaxWeightSessions: Get<u32>;
 }
 
+/// Balance type used by the optional rewards subsystem, derived from `Trait::Currency`.
+#[cfg(feature = "rewards")]
+pub type BalanceOf<T> =
+	<<T as Trait>::Currency as fungible::Inspect<<T as frame_system::Trait>::AccountId>>::Balance;
+
 decl_event!(
 	pub enum Event<T> where
 		<T as Trait>::AuthorityId,
 		IdentificationTuple = IdentificationTuple<T>,
+		#[cfg(feature = "rewards")]
+		Balance = BalanceOf<T>,
 	{
 		/// A new heartbeat was received from `AuthorityId`
 		HeartbeatReceived(AuthorityId),
-		/// At the end of the session, no offence was committed.
-		AllGood,
-		/// At the end of the session, at least one validator was found to be offline.
-		SomeOffline(Vec<IdentificationTuple>),
+		/// At the end of the session, every validator sent a heartbeat.
+		AllGood(SessionIndex),
+		/// At the end of the session, the given offenders were found to be offline and reported
+		/// with the given slash fraction (computed against the given validator set size), for
+		/// indexers/dashboards to reconstruct the resulting penalty without re-deriving the
+		/// escalation curve.
+		SomeOffline(SessionIndex, u32, Vec<IdentificationTuple>, Perbill),
+		/// At the end of the session, a validator that stayed online was paid a reward.
+		#[cfg(feature = "rewards")]
+		Rewarded(IdentificationTuple, Balance),
 	}
 );
 
@@ -281,19 +412,34 @@ decl_storage! {
 		HeartbeatAfter get(fn heartbeat_after): T::BlockNumber;
 
 		/// The current set of keys that may issue a heartbeat.
-		Keys get(fn keys): Vec<T::AuthorityId>;
+		Keys get(fn keys): WeakBoundedVec<T::AuthorityId, T::MaxKeys>;
 
-		/// For each session index, we keep a mapping of `AuthIndex` to
-		/// `offchain::OpaqueNetworkState`.
+		/// For each session index, we keep a mapping of `AuthIndex` to the (bounded)
+		/// encoded `offchain::OpaqueNetworkState` reported in the heartbeat.
 		ReceivedHeartbeats get(fn received_heartbeats):
 			double_map hasher(twox_64_concat) SessionIndex, hasher(twox_64_concat) AuthIndex
-			=> Option<Vec<u8>>;
+			=> Option<BoundedVec<u8, T::MaxPeerDataEncodingSize>>;
 
 		/// For each session index, we keep a mapping of `T::ValidatorId` to the
 		/// number of blocks authored by the given authority.
 		AuthoredBlocks get(fn authored_blocks):
 			double_map hasher(twox_64_concat) SessionIndex, hasher(twox_64_concat) T::ValidatorId
 			=> u32;
+
+		/// The current storage version, used to gate the one-shot migration to bounded
+		/// `Keys`/`ReceivedHeartbeats` storage.
+		StorageVersion get(fn storage_version) build(|_| Releases::V2_0_0): Releases;
+
+		/// For each validator, the session of its most recent unresponsiveness strike and the
+		/// running strike count accrued since the last decay, used to escalate the slash for
+		/// repeat offenders.
+		OffenceReputation get(fn offence_reputation):
+			map hasher(twox_64_concat) T::ValidatorId => (SessionIndex, u32);
+
+		/// For each validator, the number of consecutive sessions it has just ended without
+		/// sending a heartbeat. Reset to zero as soon as it sends one again.
+		ConsecutiveMisses get(fn consecutive_misses):
+			map hasher(twox_64_concat) T::ValidatorId => u32;
 	}
 	add_extra_genesis {
 		config(keys): Vec<T::AuthorityId>;
@@ -308,6 +454,24 @@ decl_error! {
 		InvalidKey,
 		/// Duplicated heartbeat.
 		DuplicatedHeartbeat,
+		/// The encoded network state in the heartbeat exceeds `MaxPeerDataEncodingSize`.
+		NetworkStateTooLarge,
+	}
+}
+
+/// A value placed in storage that represents the current version of the im-online storage.
+///
+/// This is used to gate the migration that moves `Keys`/`ReceivedHeartbeats` from unbounded
+/// to bounded encodings, which otherwise have the same wire format.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	V1_0_0,
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
 	}
 }
 
@@ -317,6 +481,10 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			Self::migrate_to_bounded_storage()
+		}
+
 		/// # <weight>
 		/// - Complexity: `O(K + E)` where K is length of `Keys` and E is length of
 		///   `Heartbeat.network_state.external_address`
@@ -354,7 +522,9 @@ decl_module! {
 			if let (false, Some(public)) = (exists, public) {
 				Self::deposit_event(Event::<T>::HeartbeatReceived(public.clone()));
 
-				let network_state = heartbeat.network_state.encode();
+				let network_state: BoundedVec<u8, T::MaxPeerDataEncodingSize> =
+					heartbeat.network_state.encode().try_into()
+						.map_err(|_| Error::<T>::NetworkStateTooLarge)?;
 				<ReceivedHeartbeats>::insert(
 					&current_session,
 					&heartbeat.authority_index,
@@ -486,12 +656,14 @@ impl<T: Trait> Module<T> {
 		let prepare_heartbeat = || -> OffchainResult<T, Call<T>> {
 			let network_state = sp_io::offchain::network_state()
 				.map_err(|_| OffchainErr::NetworkState)?;
+			let liveness_check = Self::probe_liveness()?;
 			let heartbeat_data = Heartbeat {
 				block_number,
 				network_state,
 				session_index,
 				authority_index,
 				validators_len,
+				liveness_check,
 			};
 
 			let signature = key.sign(&heartbeat_data.encode()).ok_or(OffchainErr::FailedSigning)?;
@@ -528,13 +700,40 @@ impl<T: Trait> Module<T> {
 		)
 	}
 
+	/// Probe `T::LivenessEndpoint`, if configured, and report whether it's reachable.
+	///
+	/// Returns `Ok(None)` unchanged from before this feature existed when no endpoint is
+	/// configured. When one is configured, returns `Ok(Some(_))` on a successful probe, or
+	/// `Err(OffchainErr::Unreachable)` on any `HttpError`/timeout so the heartbeat is skipped
+	/// rather than sent on behalf of a validator that's unreachable from the outside.
+	fn probe_liveness() -> OffchainResult<T, Option<LivenessCheck>> {
+		let url = match T::LivenessEndpoint::get() {
+			Some(url) => url,
+			None => return Ok(None),
+		};
+
+		let start = sp_io::offchain::timestamp();
+		let deadline = start.add(sp_std::time::Duration::from_millis(LIVENESS_PROBE_DEADLINE_MS));
+
+		let request = http::Request::get(url).deadline(deadline);
+		let pending = request.send().map_err(|_| OffchainErr::Unreachable)?;
+		let response = pending
+			.try_wait(deadline)
+			.map_err(|_| OffchainErr::Unreachable)?
+			.map_err(|_: http::Error| OffchainErr::Unreachable)?;
+
+		let latency_ms = sp_io::offchain::timestamp().diff(&start).millis();
+
+		Ok(Some(LivenessCheck { http_status: response.code, latency_ms }))
+	}
+
 	fn local_authority_keys() -> impl Iterator<Item=(u32, T::AuthorityId)> {
 		// on-chain storage
 		//
 		// At index `idx`:
 		// 1. A (ImOnline) public key to be used by a validator at index `idx` to send im-online
 		//          heartbeats.
-		let authorities = Keys::<T>::get();
+		let authorities = Keys::<T>::get().into_inner();
 
 		// local keystore
 		//
@@ -563,34 +762,32 @@ impl<T: Trait> Module<T> {
 			key.extend(authority_index.encode());
 			key
 		};
-		let storage = StorageValueRef::persistent(&key);
-		let res = storage.mutate(|status: Option<Option<HeartbeatStatus<T::BlockNumber>>>| {
-			// Check if there is already a lock for that particular block.
-			// This means that the heartbeat has already been sent, and we are just waiting
-			// for it to be included. However if it doesn't get included for INCLUDE_THRESHOLD
-			// we will re-send it.
-			match status {
-				// we are still waiting for inclusion.
-				Some(Some(status)) if status.is_recent(session_index, now) => {
-					Err(OffchainErr::WaitingForInclusion(status.sent_at))
-				},
-				// attempt to set new status
-				_ => Ok(HeartbeatStatus {
-					session_index,
-					sent_at: now,
-				}),
+
+		// The "have we already sent (and are waiting for inclusion of) a heartbeat for this
+		// session" status is genuine business state, kept separate from the mutual-exclusion
+		// lock below: it should persist across successful runs, not expire on its own.
+		let status = StorageValueRef::persistent(&key);
+		if let Ok(Some(status)) = status.get::<HeartbeatStatus<T::BlockNumber>>() {
+			if status.is_recent(session_index, now) {
+				return Err(OffchainErr::WaitingForInclusion(status.sent_at));
 			}
-		})?;
+		}
 
-		let mut new_status = res.map_err(|_| OffchainErr::FailedToAcquireLock)?;
+		// Guard the actual send with a block-and-time bounded lock: unlike the status above,
+		// this is allowed to expire on its own (once both the block deadline and the wall-clock
+		// timeout have passed), so a worker that panics mid-send can't wedge every future run
+		// behind a lock that's never released.
+		let mut lock = StorageLock::<BlockAndTime<frame_system::Module<T>>>::with_block_and_time_deadline(
+			&key,
+			INCLUDE_THRESHOLD,
+			core::time::Duration::from_millis(LOCK_TIMEOUT_MS),
+		);
+		let _guard = lock.try_lock().map_err(|_| OffchainErr::FailedToAcquireLock)?;
 
-		// we got the lock, let's try to send the heartbeat.
 		let res = f();
 
-		// clear the lock in case we have failed to send transaction.
-		if res.is_err() {
-			new_status.sent_at = 0.into();
-			storage.set(&new_status);
+		if res.is_ok() {
+			status.set(&HeartbeatStatus { session_index, sent_at: now });
 		}
 
 		res
@@ -599,14 +796,118 @@ impl<T: Trait> Module<T> {
 	fn initialize_keys(keys: &[T::AuthorityId]) {
 		if !keys.is_empty() {
 			assert!(Keys::<T>::get().is_empty(), "Keys are already initialized!");
-			Keys::<T>::put(keys);
+			Keys::<T>::put(WeakBoundedVec::force_from(
+				keys.to_vec(),
+				Some("im-online Keys (initialize_keys)"),
+			));
 		}
 	}
 
 	#[cfg(test)]
 	fn set_keys(keys: Vec<T::AuthorityId>) {
-		Keys::<T>::put(&keys)
+		Keys::<T>::put(WeakBoundedVec::force_from(keys, Some("im-online Keys (set_keys)")))
+	}
+
+	/// Migrate `Keys`/`ReceivedHeartbeats` from unbounded to bounded storage.
+	///
+	/// `WeakBoundedVec`/`BoundedVec` encode identically to `Vec`, so the existing on-chain
+	/// bytes decode straight into the new bounded types without any re-encoding; this just
+	/// bumps the storage version and, for a validator set that's grown past `MaxKeys`, logs a
+	/// warning rather than losing keys.
+	fn migrate_to_bounded_storage() -> Weight {
+		if StorageVersion::get() == Releases::V1_0_0 {
+			let keys = Keys::<T>::get();
+			if keys.len() as u32 > T::MaxKeys::get() {
+				debug::warn!(
+					target: "imonline",
+					"Keys is {} items long, exceeding MaxKeys ({}); keeping all keys, but the \
+					PoV/weight bounds derived from MaxKeys no longer hold.",
+					keys.len(),
+					T::MaxKeys::get(),
+				);
+			}
+			StorageVersion::put(Releases::V2_0_0);
+			T::DbWeight::get().reads_writes(1, 1)
+		} else {
+			T::DbWeight::get().reads(1)
+		}
+	}
+
+	/// The pallet's pot account, which funds `RewardPerSession` payouts.
+	#[cfg(feature = "rewards")]
+	fn pot_account() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
 	}
+
+	/// Pay `RewardPerSession` to every validator that stayed online during the session, out of
+	/// the pallet's pot account.
+	///
+	/// Validators whose `ValidatorId` can't be resolved to a payable `AccountId`, or whose
+	/// payout fails (e.g. an underfunded pot), are simply skipped rather than blocking the
+	/// session from ending.
+	#[cfg(feature = "rewards")]
+	fn reward_online_validators(current_validators: &[T::ValidatorId]) {
+		let pot = Self::pot_account();
+		let reward = T::RewardPerSession::get();
+
+		for (index, id) in current_validators.iter().cloned().enumerate() {
+			if !Self::is_online_aux(index as u32, &id) {
+				continue;
+			}
+
+			let account = match T::RewardAccountOf::convert(id.clone()) {
+				Some(account) => account,
+				None => continue,
+			};
+			let full_id = match T::FullIdentificationOf::convert(id.clone()) {
+				Some(full_id) => full_id,
+				None => continue,
+			};
+
+			if T::Currency::transfer(&pot, &account, reward, Preservation::Expendable).is_ok() {
+				Self::deposit_event(RawEvent::Rewarded((id, full_id), reward));
+			}
+		}
+	}
+
+	/// Record a fresh unresponsiveness strike for `validator_id` at `session_index`, decaying
+	/// the running count first if `ReputationDecaySessions` have passed since its last one, and
+	/// return the resulting escalation factor as a percentage (`100` meaning no escalation).
+	fn record_offence_strike(validator_id: &T::ValidatorId, session_index: SessionIndex) -> u32 {
+		let (last_session, strikes) = OffenceReputation::<T>::get(validator_id);
+		let decay_sessions = T::ReputationDecaySessions::get();
+		let strikes = if strike_has_decayed(last_session, session_index, decay_sessions) {
+			0
+		} else {
+			strikes
+		}
+		.saturating_add(1);
+		OffenceReputation::<T>::insert(validator_id, (session_index, strikes));
+
+		escalation_percent_for_strikes(
+			strikes,
+			T::EscalationStepPercent::get(),
+			T::MaxEscalationPercent::get(),
+		)
+	}
+}
+
+/// Whether a strike recorded at `last_session` has decayed by `session_index`, i.e. more than
+/// `decay_sessions` have passed since. Kept free of storage/`Trait` so it can be unit tested
+/// directly.
+fn strike_has_decayed(
+	last_session: SessionIndex,
+	session_index: SessionIndex,
+	decay_sessions: SessionIndex,
+) -> bool {
+	session_index.saturating_sub(last_session) > decay_sessions
+}
+
+/// Escalation factor, as a percentage, for a validator on its `strikes`-th undecayed
+/// unresponsiveness strike. Kept free of storage/`Trait` so it can be unit tested directly.
+fn escalation_percent_for_strikes(strikes: u32, step_percent: u32, max_percent: u32) -> u32 {
+	let escalation = step_percent.saturating_mul(strikes.saturating_sub(1));
+	(100u32.saturating_add(escalation)).min(max_percent)
 }
 
 impl<T: Trait> sp_runtime::BoundToRuntimeAppPublic for Module<T> {
@@ -630,11 +931,23 @@ impl<T: Trait> pallet_session::OneSessionHandler<T::AccountId> for Module<T> {
 		// Since we consider producing blocks as being online,
 		// the heartbeat is deferred a bit to prevent spamming.
 		let block_number = <frame_system::Module<T>>::block_number();
-		let half_session = T::SessionDuration::get() / 2.into();
-		<HeartbeatAfter<T>>::put(block_number + half_session);
+		let heartbeat_after = match T::NextSessionRotation::estimate_next_session_rotation(block_number) {
+			// Aim for the middle of the (estimated) remaining session, rather than the
+			// fixed `SessionDuration / 2` offset from *this* session's start.
+			Some(next_rotation) if next_rotation > block_number =>
+				block_number + (next_rotation - block_number) / 2.into(),
+			// The estimate is unavailable (or already in the past): don't defer, since it's
+			// better to risk an extra heartbeat than to stall waiting on an estimate we
+			// can't trust.
+			_ => block_number,
+		};
+		<HeartbeatAfter<T>>::put(heartbeat_after);
 
 		// Remember who the authorities are for the new session.
-		Keys::<T>::put(validators.map(|x| x.1).collect::<Vec<_>>());
+		Keys::<T>::put(WeakBoundedVec::force_from(
+			validators.map(|x| x.1).collect::<Vec<_>>(),
+			Some("im-online Keys (on_new_session)"),
+		));
 	}
 
 	fn on_before_session_ending() {
@@ -642,13 +955,26 @@ impl<T: Trait> pallet_session::OneSessionHandler<T::AccountId> for Module<T> {
 		let keys = Keys::<T>::get();
 		let current_validators = <pallet_session::Module<T>>::validators();
 
-		let offenders = current_validators.into_iter().enumerate()
+		let offenders = current_validators.iter().cloned().enumerate()
 			.filter(|(index, id)|
 				!Self::is_online_aux(*index as u32, id)
 			).filter_map(|(_, id)|
 				T::FullIdentificationOf::convert(id.clone()).map(|full_id| (id, full_id))
 			).collect::<Vec<IdentificationTuple<T>>>();
 
+		// Track each validator's current run of consecutive unresponsive sessions, resetting it
+		// for anyone who did send a heartbeat this session.
+		for (index, id) in current_validators.iter().enumerate() {
+			if Self::is_online_aux(index as u32, id) {
+				ConsecutiveMisses::<T>::remove(id);
+			} else {
+				ConsecutiveMisses::<T>::mutate(id, |misses| *misses = misses.saturating_add(1));
+			}
+		}
+
+		#[cfg(feature = "rewards")]
+		Self::reward_online_validators(&current_validators);
+
 		// Remove all received heartbeats and number of authored blocks from the
 		// current session, they have already been processed and won't be needed
 		// anymore.
@@ -656,12 +982,45 @@ impl<T: Trait> pallet_session::OneSessionHandler<T::AccountId> for Module<T> {
 		<AuthoredBlocks<T>>::remove_prefix(&<pallet_session::Module<T>>::current_index());
 
 		if offenders.is_empty() {
-			Self::deposit_event(RawEvent::AllGood);
+			Self::deposit_event(RawEvent::AllGood(session_index));
 		} else {
-			Self::deposit_event(RawEvent::SomeOffline(offenders.clone()));
+			// `slash_fraction` is evaluated once per `Offence` and applied uniformly to every
+			// offender it carries, so a single batched offence escalates on the worst repeat
+			// offender in the batch rather than per-offender.
+			let escalation_percent = offenders
+				.iter()
+				.map(|(id, _)| Self::record_offence_strike(id, session_index))
+				.max()
+				.unwrap_or(100);
+
+			let consecutive_sessions = offenders
+				.iter()
+				.map(|(id, _)| ConsecutiveMisses::<T>::get(id))
+				.collect::<Vec<_>>();
 
 			let validator_set_count = keys.len() as u32;
-			let offence = UnresponsivenessOffence { session_index, validator_set_count, offenders };
+			let offence = UnresponsivenessOffence {
+				session_index,
+				validator_set_count,
+				offenders: offenders.clone(),
+				grace_fraction: T::OfflineGraceFraction::get(),
+				slash_slope: T::OfflineSlashSlope::get(),
+				max_slash: T::MaxOfflineSlash::get(),
+				escalation_percent,
+				consecutive_sessions: Some(consecutive_sessions),
+				max_weight_sessions: T::MaxWeightSessions::get(),
+			};
+
+			// Computed against the same offenders count the offence itself will be reported
+			// with, so the event reflects the exact fraction the curve produced.
+			let slash_fraction = offence.slash_fraction(offenders.len() as u32);
+			Self::deposit_event(RawEvent::SomeOffline(
+				session_index,
+				validator_set_count,
+				offenders,
+				slash_fraction,
+			));
+
 			if let Err(e) = T::ReportUnresponsiveness::report_offence(vec![], offence) {
 				sp_runtime::print(e);
 			}
@@ -714,12 +1073,21 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
 				return InvalidTransaction::BadProof.into();
 			}
 
+			// Expire the heartbeat roughly when the session it was sent for does, so it
+			// isn't still propagating (or worse, included) well into the next session.
+			let longevity = match T::NextSessionRotation::estimate_next_session_rotation(
+				heartbeat.block_number,
+			) {
+				Some(next_rotation) if next_rotation > heartbeat.block_number =>
+					TryInto::<u64>::try_into(next_rotation - heartbeat.block_number)
+						.unwrap_or(64_u64),
+				_ => 64_u64,
+			};
+
 			ValidTransaction::with_tag_prefix("ImOnline")
 				.priority(T::UnsignedPriority::get())
 				.and_provides((current_session, authority_id))
-				.longevity(TryInto::<u64>::try_into(
-					T::SessionDuration::get() / 2.into()
-				).unwrap_or(64_u64))
+				.longevity(longevity)
 				.propagate(true)
 				.build()
 		} else {
@@ -741,6 +1109,31 @@ pub struct UnresponsivenessOffence<Offender> {
 	pub validator_set_count: u32,
 	/// Authorities that were unresponsive during the current era.
 	pub offenders: Vec<Offender>,
+	/// Fraction of the validator set that may be offline before any slash applies, resolved
+	/// from `Trait::OfflineGraceFraction` at construction time (mirrors the original hardcoded
+	/// `n / 10 + 1` grace band).
+	pub grace_fraction: Perbill,
+	/// Slope of the slash curve past the grace band, resolved from `Trait::OfflineSlashSlope`.
+	pub slash_slope: u32,
+	/// Maximum slash fraction the curve can reach, resolved from `Trait::MaxOfflineSlash`.
+	pub max_slash: Perbill,
+	/// Escalation factor for repeat offenders, as a percentage (`100` meaning no escalation),
+	/// resolved from the worst offender's strike count in `OffenceReputation` at construction
+	/// time.
+	pub escalation_percent: u32,
+	/// The number of consecutive sessions each offender in `offenders` has just failed to send
+	/// a heartbeat for, in the same order, if duration-weighting is in effect.
+	///
+	/// When `None`, every offender weighs `1` in the curve, exactly like before this field
+	/// existed. When `Some`, each offender instead weighs
+	/// `consecutive_sessions.min(max_weight_sessions)`, so a validator that's been unresponsive
+	/// for a long streak of sessions drives the slash further than a single missed heartbeat
+	/// would.
+	pub consecutive_sessions: Option<Vec<u32>>,
+	/// Cap applied to each entry of `consecutive_sessions`, resolved from
+	/// `Trait::MaxWeightSessions` at construction time. Unused when `consecutive_sessions` is
+	/// `None`.
+	pub max_weight_sessions: u32,
 }
 
 impl<Offender: Clone> Offence<Offender> for UnresponsivenessOffence<Offender> {
@@ -763,13 +1156,32 @@ impl<Offender: Clone> Offence<Offender> for UnresponsivenessOffence<Offender> {
 		self.session_index
 	}
 
-	fn slash_fraction(offenders: u32, validator_set_count: u32) -> Perbill {
-		// the formula is min((3 * (k - (n / 10 + 1))) / n, 1) * 0.07
-		// basically, 10% can be offline with no slash, but after that, it linearly climbs up to 7%
-		// when 13/30 are offline (around 5% when 1/3 are offline).
-		if let Some(threshold) = offenders.checked_sub(validator_set_count / 10 + 1) {
-			let x = Perbill::from_rational_approximation(3 * threshold, validator_set_count);
-			x.saturating_mul(Perbill::from_percent(7))
+	fn slash_fraction(&self, offenders_count: u32) -> Perbill {
+		// Duration-weighted mode replaces the flat per-offender count of `1` with how many
+		// consecutive sessions each offender has been unresponsive for, capped at
+		// `max_weight_sessions`, so a long-running outage drives the curve further than a single
+		// missed heartbeat would.
+		let weighted_count = match &self.consecutive_sessions {
+			Some(sessions) =>
+				sessions.iter().map(|s| (*s).min(self.max_weight_sessions)).sum(),
+			None => offenders_count,
+		};
+
+		// the formula is min(slash_slope * (k - grace_count), 1) * max_slash, where
+		// grace_count = grace_fraction * n + 1. With the default parameters (10% grace, slope
+		// 3, 7% max) this is exactly the original hardcoded curve.
+		let grace_count =
+			sp_runtime::PerThing::mul_floor(self.grace_fraction, self.validator_set_count)
+				.saturating_add(1);
+		if let Some(threshold) = weighted_count.checked_sub(grace_count) {
+			let x = Perbill::from_rational(self.slash_slope * threshold, self.validator_set_count);
+			let base = x.saturating_mul(self.max_slash);
+
+			// Scale by the escalation factor for repeat offenders, saturating at a full slash
+			// rather than overflowing `Perbill`'s `[0, 1]` range.
+			let escalated = (base.deconstruct() as u64)
+				.saturating_mul(self.escalation_percent as u64) / 100;
+			Perbill::from_parts(escalated.min(Perbill::one().deconstruct() as u64) as u32)
 		} else {
 			Perbill::default()
 		}
@@ -0,0 +1,185 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the configurable unresponsiveness slash curve.
+//!
+//! These construct `UnresponsivenessOffence` directly with explicit curve parameters, rather
+//! than going through a mock runtime, since `slash_fraction` only depends on the fields
+//! resolved onto the offence at construction time.
+
+#![cfg(test)]
+
+use super::*;
+
+fn offence(validator_set_count: u32) -> UnresponsivenessOffence<u64> {
+	UnresponsivenessOffence {
+		session_index: 0,
+		validator_set_count,
+		offenders: vec![],
+		grace_fraction: Perbill::from_percent(10),
+		slash_slope: 3,
+		max_slash: Perbill::from_percent(7),
+		escalation_percent: 100,
+		consecutive_sessions: None,
+		max_weight_sessions: 10,
+	}
+}
+
+#[test]
+fn grace_band_is_not_slashed() {
+	let offence = offence(30);
+	// n / 10 + 1 == 4, so up to 4 offenders should incur no slash at all.
+	assert_eq!(offence.slash_fraction(0), Perbill::default());
+	assert_eq!(offence.slash_fraction(4), Perbill::default());
+}
+
+#[test]
+fn slash_ramps_linearly_past_the_grace_band() {
+	let offence = offence(30);
+	// One offender past the grace band: 3 * 1 / 30 == 10%, scaled by the 7% max.
+	let expected = Perbill::from_rational(3u32, 30u32).saturating_mul(Perbill::from_percent(7));
+	assert_eq!(offence.slash_fraction(5), expected);
+}
+
+#[test]
+fn slash_saturates_at_the_configured_maximum() {
+	let offence = offence(30);
+	// Every validator offline should saturate the curve at `max_slash`.
+	assert_eq!(offence.slash_fraction(30), Perbill::from_percent(7));
+}
+
+#[test]
+fn custom_curve_parameters_are_honored() {
+	let offence = UnresponsivenessOffence {
+		session_index: 0,
+		validator_set_count: 100,
+		offenders: vec![],
+		grace_fraction: Perbill::from_percent(20),
+		slash_slope: 5,
+		max_slash: Perbill::from_percent(50),
+		escalation_percent: 100,
+		consecutive_sessions: None,
+		max_weight_sessions: 10,
+	};
+
+	// 20% grace band on 100 validators is 21, so nothing is slashed up to that point.
+	assert_eq!(offence.slash_fraction(21), Perbill::default());
+	// One offender past the grace band, with the steeper slope and higher ceiling.
+	let expected = Perbill::from_rational(5u32, 100u32).saturating_mul(Perbill::from_percent(50));
+	assert_eq!(offence.slash_fraction(22), expected);
+}
+
+#[test]
+fn escalation_factor_scales_the_base_slash() {
+	let base = offence(30);
+	let base_fraction = base.slash_fraction(5);
+
+	let mut escalated = offence(30);
+	escalated.escalation_percent = 150;
+	assert_eq!(escalated.slash_fraction(5), Perbill::from_parts(
+		((base_fraction.deconstruct() as u64) * 150 / 100) as u32,
+	));
+}
+
+#[test]
+fn escalation_factor_saturates_at_a_full_slash() {
+	let mut offence = offence(30);
+	// Every offender past the grace band, scaled by a wildly oversized escalation factor.
+	offence.escalation_percent = 10_000;
+	assert_eq!(offence.slash_fraction(30), Perbill::one());
+}
+
+#[test]
+fn escalation_percent_ramps_with_consecutive_strikes() {
+	// First strike: no prior history, so no escalation yet.
+	assert_eq!(escalation_percent_for_strikes(1, 25, 300), 100);
+	// Each further consecutive strike escalates by another step.
+	assert_eq!(escalation_percent_for_strikes(2, 25, 300), 125);
+	assert_eq!(escalation_percent_for_strikes(3, 25, 300), 150);
+}
+
+#[test]
+fn escalation_percent_saturates_at_the_configured_ceiling() {
+	assert_eq!(escalation_percent_for_strikes(100, 25, 300), 300);
+}
+
+#[test]
+fn strike_decay_boundary_is_exclusive() {
+	// Exactly `decay_sessions` later, the strike is still live.
+	assert!(!strike_has_decayed(10, 20, 10));
+	// One session further, it has decayed.
+	assert!(strike_has_decayed(10, 21, 10));
+}
+
+// `on_before_session_ending` computes the `SomeOffline` event's slash fraction the same way it
+// computes the one given to `ReportUnresponsiveness`: by calling `slash_fraction` with the
+// offence's own `offenders.len()`. These pin that computation down at the two ends of interest.
+
+#[test]
+fn event_fraction_is_zero_within_the_grace_band() {
+	let mut offence = offence(30);
+	offence.offenders = vec![1, 2, 3, 4];
+	// 4 offenders sits exactly on the grace boundary computed earlier, so the fraction the
+	// `SomeOffline` event would carry is zero — nothing slashed, nothing to escalate either.
+	assert_eq!(offence.slash_fraction(offence.offenders.len() as u32), Perbill::default());
+}
+
+#[test]
+fn event_fraction_matches_the_reported_offence_past_the_grace_band() {
+	let mut offence = offence(30);
+	offence.offenders = vec![1, 2, 3, 4, 5];
+	let expected = Perbill::from_rational(3u32, 30u32).saturating_mul(Perbill::from_percent(7));
+	assert_eq!(offence.slash_fraction(offence.offenders.len() as u32), expected);
+}
+
+#[test]
+fn a_single_missed_session_weighs_the_same_as_the_flat_count() {
+	// Five offenders, each on their first missed session, should weigh exactly as much as the
+	// flat-count curve would for five offenders.
+	let mut weighted = offence(30);
+	weighted.consecutive_sessions = Some(vec![1; 5]);
+
+	let mut flat = offence(30);
+	flat.consecutive_sessions = None;
+
+	assert_eq!(weighted.slash_fraction(0), flat.slash_fraction(5));
+}
+
+#[test]
+fn longer_outages_weigh_more_than_a_flat_count() {
+	// One offender that's been unresponsive for three sessions in a row should drive the
+	// curve further than a single offender weighed flatly at `1`.
+	let mut weighted = offence(10);
+	weighted.consecutive_sessions = Some(vec![3]);
+
+	let mut flat = offence(10);
+	flat.consecutive_sessions = None;
+
+	assert!(weighted.slash_fraction(0) > flat.slash_fraction(1));
+}
+
+#[test]
+fn consecutive_session_weight_clamps_at_the_configured_maximum() {
+	let mut clamped = offence(30);
+	clamped.consecutive_sessions = Some(vec![1_000]);
+
+	let mut at_cap = offence(30);
+	at_cap.consecutive_sessions = Some(vec![at_cap.max_weight_sessions]);
+
+	// An outage far beyond `max_weight_sessions` weighs no more than one that's exactly at it.
+	assert_eq!(clamped.slash_fraction(0), at_cap.slash_fraction(0));
+}
@@ -188,6 +188,12 @@ pub mod pallet {
 	pub type Parameters<T: Config> =
 		StorageMap<_, Blake2_128Concat, KeyOf<T>, ValueOf<T>, OptionQuery>;
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The new value failed the key's `Key::validate` check.
+		InvalidValue,
+	}
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -196,7 +202,8 @@ pub mod pallet {
 		/// Set the value of a parameter.
 		///
 		/// The dispatch origin of this call must be `AdminOrigin` for the given `key`. Values be
-		/// deleted by setting them to `None`.
+		/// deleted by setting them to `None`. A new value that fails its key's validator is
+		/// rejected with [`Error::InvalidValue`] instead of being stored.
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::set_parameter())]
 		pub fn set_parameter(
@@ -206,6 +213,13 @@ pub mod pallet {
 			let (key, new) = key_value.into_parts();
 			T::AdminOrigin::ensure_origin(origin, &key)?;
 
+			if let Some(new) = &new {
+				ensure!(
+					<T::RuntimeParameters as AggregratedKeyValue>::validate_value(new),
+					Error::<T>::InvalidValue
+				);
+			}
+
 			let mut old = None;
 			Parameters::<T>::mutate(&key, |v| {
 				old = v.clone();
@@ -247,6 +261,10 @@ pub mod pallet {
 impl<T: Config> RuntimeParameterStore for Pallet<T> {
 	type AggregratedKeyValue = T::RuntimeParameters;
 
+	fn get_aggregated(key: KeyOf<T>) -> Option<ValueOf<T>> {
+		Parameters::<T>::get(key)
+	}
+
 	fn get<KV, K>(key: K) -> Option<K::Value>
 	where
 		KV: AggregratedKeyValue,
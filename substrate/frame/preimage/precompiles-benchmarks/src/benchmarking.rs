@@ -0,0 +1,135 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for pallet-preimage's precompile.
+#![cfg(feature = "runtime-benchmarks")]
+
+extern crate alloc;
+
+use super::*;
+use alloc::vec::Vec;
+use frame_benchmarking::v2::*;
+use frame_support::{assert_ok, traits::fungible::Inspect};
+use frame_system::RawOrigin;
+use pallet_preimage_precompiles::IPreimage;
+use pallet_revive::{
+	precompiles::alloy::{hex, sol_types::SolInterface},
+	ExecConfig, ExecReturnValue, Weight, H160, U256,
+};
+use sp_runtime::traits::Saturating;
+
+fn call_precompile<T: Config>(
+	from: T::AccountId,
+	encoded_call: Vec<u8>,
+) -> Result<ExecReturnValue, sp_runtime::DispatchError> {
+	let precompile_addr =
+		H160::from(hex::const_decode_to_array(b"00000000000000000000000000000000000D0000").unwrap());
+
+	let result = pallet_revive::Pallet::<T>::bare_call(
+		<T as frame_system::Config>::RuntimeOrigin::signed(from),
+		precompile_addr,
+		U256::zero(),
+		Weight::MAX,
+		<T as pallet_revive::Config>::Currency::minimum_balance().saturating_mul(1000u32.into()),
+		encoded_call,
+		ExecConfig::new_substrate_tx(),
+	);
+
+	result.result
+}
+
+fn funded_mapped_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	use frame_support::traits::fungible::Mutate;
+
+	let account: T::AccountId = account(name, index, 0u32);
+
+	let min_balance = <T as pallet_revive::Config>::Currency::minimum_balance();
+	let deposit_per_byte = <T as pallet_revive::Config>::DepositPerByte::get();
+	let deposit_per_item = <T as pallet_revive::Config>::DepositPerItem::get();
+	let mapping_deposit =
+		deposit_per_byte.saturating_mul(52u32.into()).saturating_add(deposit_per_item);
+
+	let funding_amount = min_balance
+		.saturating_add(mapping_deposit)
+		.saturating_add(min_balance.saturating_mul(1000u32.into()))
+		.saturating_add(min_balance.saturating_mul(100_000u32.into()));
+
+	<T as pallet_revive::Config>::Currency::set_balance(&account, funding_amount);
+
+	assert_ok!(pallet_revive::Pallet::<T>::map_account(RawOrigin::Signed(account.clone()).into()));
+
+	account
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	// Worst case: note a maximum-length preimage, capturing the per-byte storage-deposit and
+	// hashing cost.
+	#[benchmark(pov_mode = Measured)]
+	fn note_preimage() {
+		let caller = funded_mapped_account::<T>("caller", 0);
+		let preimage = alloc::vec![0u8; pallet_preimage::MAX_SIZE as usize];
+
+		let encoded_call =
+			IPreimage::IPreimageCalls::notePreimage(IPreimage::notePreimageCall {
+				preImage: preimage.into(),
+			})
+			.abi_encode();
+
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn unnote_preimage() {
+		let caller = funded_mapped_account::<T>("caller", 0);
+		let preimage = alloc::vec![0u8; pallet_preimage::MAX_SIZE as usize];
+
+		let note_call = IPreimage::IPreimageCalls::notePreimage(IPreimage::notePreimageCall {
+			preImage: preimage.into(),
+		})
+		.abi_encode();
+		let noted = call_precompile::<T>(caller.clone(), note_call).unwrap();
+		let hash: [u8; 32] = noted.data[..32].try_into().expect("notePreimage returns a 32-byte hash");
+
+		let encoded_call =
+			IPreimage::IPreimageCalls::unnotePreimage(IPreimage::unnotePreimageCall {
+				hash: hash.into(),
+			})
+			.abi_encode();
+
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	impl_benchmark_test_suite!(
+		PreimagePrecompilesBenchmarks,
+		crate::mock::new_test_ext(),
+		crate::mock::Test
+	);
+}
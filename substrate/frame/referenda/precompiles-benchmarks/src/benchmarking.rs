@@ -7,10 +7,13 @@ use alloc::{boxed::Box, vec::Vec};
 use frame_benchmarking::v2::*;
 use frame_support::{
 	assert_ok,
-	traits::{fungible::Inspect, schedule::DispatchTime, Get, OriginTrait, StorePreimage},
+	traits::{fungible::Inspect, schedule::DispatchTime, Get, OriginTrait, StorePreimage, VoteTally},
 };
 use frame_system::RawOrigin;
-use pallet_referenda::{BoundedCallOf, Pallet as Referenda, ReferendumCount, TracksInfo};
+use pallet_referenda::{
+	BoundedCallOf, Pallet as Referenda, ReferendumCount, ReferendumInfo, ReferendumInfoFor,
+	TracksInfo,
+};
 use pallet_referenda_precompiles::IReferenda;
 use pallet_revive::{
 	precompiles::alloy::{hex, sol_types::SolInterface},
@@ -91,7 +94,7 @@ fn create_referendum_helper<T: Config<I> + pallet_referenda::Config<I>, I: 'stat
 #[benchmarks]
 mod benchmarks {
 	use super::*;
-	use codec::Encode;
+	use codec::{Decode, Encode};
 
 	#[benchmark(pov_mode = Measured)]
 	fn submission_deposit() {
@@ -359,14 +362,68 @@ mod benchmarks {
 		assert!(result.is_ok());
 	}
 
+	// Advances the block number past `referendum_index`'s track `prepare_period`, so the next
+	// `place_decision_deposit` call is eligible to start deciding instead of staying Preparing.
+	fn advance_past_prepare_period<T: Config<I>, I: 'static>(referendum_index: u32) {
+		use sp_runtime::traits::BlockNumberProvider;
+
+		let status = Referenda::<T, I>::ensure_ongoing(referendum_index).unwrap();
+		let track = <T as pallet_referenda::Config<I>>::Tracks::info(status.track).unwrap();
+		let target_block = status.submitted.saturating_add(track.prepare_period);
+		<T as pallet_referenda::Config<I>>::BlockNumberProvider::set_block_number(target_block);
+	}
+
+	// Creates a referendum on the (capacity-1) root track, advances it past the prepare period
+	// and places its decision deposit. The first call occupies the track's only deciding slot;
+	// every call after that lands in the deciding queue instead, since the slot stays taken.
+	fn fill_root_track_slot_or_queue<T: Config<I>, I: 'static>(index: u32) {
+		let submitter = funded_mapped_account::<T, I>("slot-filler", index);
+		let depositor = funded_mapped_account::<T, I>("slot-depositor", index);
+		let referendum_index = create_referendum_helper::<T, I>(submitter);
+		advance_past_prepare_period::<T, I>(referendum_index);
+		assert_ok!(Referenda::<T, I>::place_decision_deposit(
+			RawOrigin::Signed(depositor).into(),
+			referendum_index
+		));
+	}
+
 	#[benchmark(pov_mode = Measured)]
-	fn place_decision_deposit_best_case() {
-		// Best case: Referendum in AwaitingDeposit phase (simple state)
+	fn place_decision_deposit_preparing() {
+		// Preparing: deposit placed before the track's prepare_period has elapsed, so
+		// service_referendum leaves the referendum in Preparing without touching the deciding
+		// queue or evaluating the tally. Cheapest branch.
 		let caller = funded_mapped_account::<T, ()>("caller", 0);
 		let submitter = funded_mapped_account::<T, ()>("submitter", 1);
+		let referendum_index = create_referendum_helper::<T, ()>(submitter);
 
-		// Create referendum WITHOUT decision deposit
+		let encoded_call = IReferenda::IReferendaCalls::placeDecisionDeposit(
+			IReferenda::placeDecisionDepositCall { referendumIndex: referendum_index },
+		)
+		.abi_encode();
+
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T, ()>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn place_decision_deposit_passing() {
+		// Passing: a deciding slot is free and the tally already clears the track's confirm
+		// threshold, so the referendum begins deciding and is scheduled straight into confirming.
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+		let submitter = funded_mapped_account::<T, ()>("submitter", 1);
 		let referendum_index = create_referendum_helper::<T, ()>(submitter);
+		advance_past_prepare_period::<T, ()>(referendum_index);
+
+		ReferendumInfoFor::<T, ()>::mutate(referendum_index, |info| {
+			if let Some(ReferendumInfo::Ongoing(status)) = info {
+				status.tally = <T as pallet_referenda::Config<()>>::Tally::unanimity(status.track);
+			}
+		});
 
 		let encoded_call = IReferenda::IReferendaCalls::placeDecisionDeposit(
 			IReferenda::placeDecisionDepositCall { referendumIndex: referendum_index },
@@ -383,44 +440,69 @@ mod benchmarks {
 	}
 
 	#[benchmark(pov_mode = Measured)]
-	fn place_decision_deposit_worst_case() {
-		// Worst case: Place deposit when referendum is ready to start deciding immediately
-		// This triggers BeginDecidingPassing/Failing branch (complex state transition)
-		//
-		// Note: The precompile calls env.charge() with max weight BEFORE executing:
-		//   let max_weight = Weight::zero()
-		//       .max(place_decision_deposit_preparing())  // ~45M
-		//       .max(place_decision_deposit_queued())     // ~65M
-		//       .max(place_decision_deposit_not_queued()) // ~66M (heaviest)
-		//       .max(place_decision_deposit_passing())    // ~53M
-		//       .max(place_decision_deposit_failing());    // ~51M
-		//   env.charge(max_weight)?;
-		//
-		// This benchmark measures the full execution path including:
-		//   1. env.charge() overhead (max weight calculation + gas meter update)
-		//   2. Actual pallet execution (BeginDecidingPassing/Failing branch)
-		// Users always pay max weight (~66M), but execution time varies by branch
+	fn place_decision_deposit_failing() {
+		// Failing: a deciding slot is free but the (default, zero) tally misses the track's
+		// confirm threshold, so the referendum begins deciding without entering confirming.
 		let caller = funded_mapped_account::<T, ()>("caller", 0);
 		let submitter = funded_mapped_account::<T, ()>("submitter", 1);
+		let referendum_index = create_referendum_helper::<T, ()>(submitter);
+		advance_past_prepare_period::<T, ()>(referendum_index);
 
-		use pallet_referenda::Pallet as Referenda;
-		use sp_runtime::traits::BlockNumberProvider;
+		let encoded_call = IReferenda::IReferendaCalls::placeDecisionDeposit(
+			IReferenda::placeDecisionDepositCall { referendumIndex: referendum_index },
+		)
+		.abi_encode();
 
-		// Create referendum
-		let referendum_index = create_referendum_helper::<T, ()>(submitter.clone());
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T, ()>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn place_decision_deposit_queued() {
+		// Queued: the root track's single deciding slot is already occupied, so the referendum
+		// is inserted into the (currently empty) deciding queue instead. Cheap insert.
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+		let submitter = funded_mapped_account::<T, ()>("submitter", 1);
+
+		fill_root_track_slot_or_queue::<T, ()>(0);
+
+		let referendum_index = create_referendum_helper::<T, ()>(submitter);
+		advance_past_prepare_period::<T, ()>(referendum_index);
+
+		let encoded_call = IReferenda::IReferendaCalls::placeDecisionDeposit(
+			IReferenda::placeDecisionDepositCall { referendumIndex: referendum_index },
+		)
+		.abi_encode();
 
-		// Get prepare period and advance blocks so referendum is ready to start deciding
-		let status = Referenda::<T>::ensure_ongoing(referendum_index).unwrap();
-		let track = <T as pallet_referenda::Config<()>>::Tracks::info(status.track).unwrap();
-		let prepare_period = track.prepare_period;
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T, ()>(caller, encoded_call);
+		}
 
-		// Advance blocks past prepare period so it's ready to start deciding
-		let submitted = status.submitted;
-		let target_block = submitted.saturating_add(prepare_period);
-		<T as pallet_referenda::Config<()>>::BlockNumberProvider::set_block_number(target_block);
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn place_decision_deposit_not_queued() {
+		// NotQueued: the deciding slot is occupied and the queue already holds several entries,
+		// so the referendum must be scanned into its sorted position among them. Heaviest branch.
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+		let submitter = funded_mapped_account::<T, ()>("submitter", 1);
+
+		fill_root_track_slot_or_queue::<T, ()>(0);
+		for i in 0..5u32 {
+			fill_root_track_slot_or_queue::<T, ()>(i.saturating_add(1));
+		}
+
+		let referendum_index = create_referendum_helper::<T, ()>(submitter);
+		advance_past_prepare_period::<T, ()>(referendum_index);
 
-		// Now place deposit - this will trigger service_referendum which will
-		// result in BeginDecidingPassing or BeginDecidingFailing branch (most complex)
 		let encoded_call = IReferenda::IReferendaCalls::placeDecisionDeposit(
 			IReferenda::placeDecisionDepositCall { referendumIndex: referendum_index },
 		)
@@ -435,6 +517,198 @@ mod benchmarks {
 		assert!(result.is_ok());
 	}
 
+	#[benchmark(pov_mode = Measured)]
+	fn refund_submission_deposit_best_case() {
+		// Best case: the referendum has completed and still has a submission deposit to refund.
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+		let submitter = funded_mapped_account::<T, ()>("submitter", 1);
+
+		let referendum_index = create_referendum_helper::<T, ()>(submitter);
+		assert_ok!(Referenda::<T>::cancel(RawOrigin::Root.into(), referendum_index));
+
+		let encoded_call = IReferenda::IReferendaCalls::refundSubmissionDeposit(
+			IReferenda::refundSubmissionDepositCall { referendumIndex: referendum_index },
+		)
+		.abi_encode();
+
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T, ()>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn refund_submission_deposit_error_case() {
+		// Error case: the referendum is still ongoing, so there is nothing to refund yet.
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+		let submitter = funded_mapped_account::<T, ()>("submitter", 1);
+
+		let referendum_index = create_referendum_helper::<T, ()>(submitter);
+
+		let encoded_call = IReferenda::IReferendaCalls::refundSubmissionDeposit(
+			IReferenda::refundSubmissionDepositCall { referendumIndex: referendum_index },
+		)
+		.abi_encode();
+
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T, ()>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn refund_decision_deposit_best_case() {
+		// Best case: the referendum has completed and still has a decision deposit to refund.
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+		let submitter = funded_mapped_account::<T, ()>("submitter", 1);
+		let depositor = funded_mapped_account::<T, ()>("depositor", 2);
+
+		let referendum_index = create_referendum_helper::<T, ()>(submitter);
+		assert_ok!(Referenda::<T>::place_decision_deposit(
+			RawOrigin::Signed(depositor).into(),
+			referendum_index
+		));
+		assert_ok!(Referenda::<T>::cancel(RawOrigin::Root.into(), referendum_index));
+
+		let encoded_call = IReferenda::IReferendaCalls::refundDecisionDeposit(
+			IReferenda::refundDecisionDepositCall { referendumIndex: referendum_index },
+		)
+		.abi_encode();
+
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T, ()>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn refund_decision_deposit_error_case() {
+		// Error case: the referendum is still ongoing and within its deciding window, so there is
+		// nothing to refund yet.
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+		let submitter = funded_mapped_account::<T, ()>("submitter", 1);
+
+		let referendum_index = create_referendum_helper::<T, ()>(submitter);
+
+		let encoded_call = IReferenda::IReferendaCalls::refundDecisionDeposit(
+			IReferenda::refundDecisionDepositCall { referendumIndex: referendum_index },
+		)
+		.abi_encode();
+
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T, ()>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn referendum_status_ongoing() {
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+		let submitter = funded_mapped_account::<T, ()>("submitter", 1);
+
+		let referendum_index = create_referendum_helper::<T, ()>(submitter);
+
+		let encoded_call = IReferenda::IReferendaCalls::referendumStatus(
+			IReferenda::referendumStatusCall { referendumIndex: referendum_index },
+		)
+		.abi_encode();
+
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T, ()>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn referendum_status_not_found_or_completed() {
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+
+		let encoded_call = IReferenda::IReferendaCalls::referendumStatus(
+			IReferenda::referendumStatusCall { referendumIndex: 999u32 },
+		)
+		.abi_encode();
+
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T, ()>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn referendum_count() {
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+
+		let encoded_call =
+			IReferenda::IReferendaCalls::referendumCount(IReferenda::referendumCountCall {})
+				.abi_encode();
+
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T, ()>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn track_info_found() {
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+
+		// Track 0 ("root") always exists in the runtime's `TracksInfo` implementation.
+		let track_id = <T as pallet_referenda::Config>::Tracks::tracks()
+			.next()
+			.expect("at least one track is configured")
+			.id;
+		let track = u16::decode(&mut &track_id.encode()[..]).expect("track id fits in a u16");
+
+		let encoded_call =
+			IReferenda::IReferendaCalls::trackInfo(IReferenda::trackInfoCall { track }).abi_encode();
+
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T, ()>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn track_info_not_found() {
+		let caller = funded_mapped_account::<T, ()>("caller", 0);
+
+		let encoded_call =
+			IReferenda::IReferendaCalls::trackInfo(IReferenda::trackInfoCall { track: u16::MAX })
+				.abi_encode();
+
+		let result;
+		#[block]
+		{
+			result = call_precompile::<T, ()>(caller, encoded_call);
+		}
+
+		assert!(result.is_ok());
+	}
+
 	impl_benchmark_test_suite!(
 		ReferendaPrecompilesBenchmarks,
 		crate::mock::new_test_ext(),
@@ -20,14 +20,18 @@
 extern crate alloc;
 
 use alloc::{boxed::Box, vec::Vec};
-use codec::Decode;
+use codec::{Decode, Encode};
 
 use core::{convert::TryFrom, fmt, marker::PhantomData, num::NonZero};
-use frame_support::traits::{schedule::DispatchTime, BoundedInline, Get};
-use sp_runtime::traits::SaturatedConversion;
+use frame_support::traits::{schedule::DispatchTime, Bounded, BoundedInline, Currency, Get};
+use sp_runtime::{
+	traits::{Hash, SaturatedConversion},
+	PerThing, Perbill,
+};
 
 use pallet_referenda::{
-	BlockNumberFor, BoundedCallOf, PalletsOriginOf, ReferendumCount, ReferendumInfoFor, TracksInfo,
+	BlockNumberFor, BoundedCallOf, Curve, PalletsOriginOf, ReferendumCount, ReferendumInfo,
+	ReferendumInfoFor, TracksInfo,
 };
 use pallet_revive::{
 	frame_system,
@@ -35,7 +39,7 @@ use pallet_revive::{
 		alloy::{self, sol_types::SolValue},
 		AddressMatcher, Error, Ext, Precompile,
 	},
-	ExecOrigin,
+	AddressMapper, ExecOrigin,
 };
 
 use tracing::{error, info};
@@ -45,6 +49,10 @@ use frame_support::{dispatch::DispatchInfo, weights::Weight};
 use IReferenda::IReferendaCalls;
 const LOG_TARGET: &str = "referenda::precompiles";
 pub type RuntimeOriginFor<T> = <T as frame_system::Config>::RuntimeOrigin;
+pub type BalanceOf<T> =
+	<<T as pallet_referenda::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type TrackIdOf<T> =
+	<<T as pallet_referenda::Config>::Tracks as TracksInfo<BalanceOf<T>, BlockNumberFor<T>>>::Id;
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -89,6 +97,58 @@ where
 	}
 }
 
+/// Convert a dispatched `DispatchTime` back into the `(Timing, enactmentMoment)` pair used by
+/// the Solidity interface.
+fn convert_dispatch_to_timing<T, I>(
+	dispatch_time: DispatchTime<BlockNumberFor<T, I>>,
+) -> (IReferenda::Timing, u32)
+where
+	T: pallet_referenda::Config<I>,
+	I: 'static,
+{
+	match dispatch_time {
+		DispatchTime::At(moment) => (IReferenda::Timing::AtBlock, moment.saturated_into()),
+		DispatchTime::After(moment) => (IReferenda::Timing::AfterBlock, moment.saturated_into()),
+	}
+}
+
+/// Recover the proposal hash and length from a bounded call, hashing inline proposals on the
+/// fly since they were never registered as a preimage.
+fn proposal_hash_and_len<T, I>(proposal: &BoundedCallOf<T, I>) -> ([u8; 32], u32)
+where
+	T: frame_system::Config,
+	I: 'static,
+{
+	let mut hash_bytes = [0u8; 32];
+	match proposal {
+		Bounded::Lookup { hash, len } => {
+			let encoded = hash.encode();
+			let n = encoded.len().min(32);
+			hash_bytes[..n].copy_from_slice(&encoded[..n]);
+			(hash_bytes, *len)
+		},
+		Bounded::Inline(data) => {
+			let hash = <T as frame_system::Config>::Hashing::hash(data.as_ref());
+			let encoded = hash.encode();
+			let n = encoded.len().min(32);
+			hash_bytes[..n].copy_from_slice(&encoded[..n]);
+			(hash_bytes, data.len() as u32)
+		},
+		Bounded::Legacy { hash, .. } => {
+			let encoded = hash.encode();
+			let n = encoded.len().min(32);
+			hash_bytes[..n].copy_from_slice(&encoded[..n]);
+			(hash_bytes, 0)
+		},
+	}
+}
+
+/// Sample a `min_approval`/`min_support` curve at 0%, 25%, 50%, 75% and 100% of the deciding
+/// period elapsed, matching `IReferenda.TrackInfo`'s fixed-size arrays.
+fn sample_curve(curve: &Curve) -> [u32; 5] {
+	[0, 25, 50, 75, 100].map(|p| curve.threshold(Perbill::from_percent(p)).deconstruct())
+}
+
 /// Dispatch a referenda submit call and extract actual weight
 ///
 /// # Parameters
@@ -150,6 +210,8 @@ where
 			IReferendaCalls::submitLookup(_)
 			| IReferendaCalls::submitInline(_)
 			| IReferendaCalls::placeDecisionDeposit(_)
+			| IReferendaCalls::refundSubmissionDeposit(_)
+			| IReferendaCalls::refundDecisionDeposit(_)
 				if env.is_read_only() =>
 			{
 				Err(Error::Error(pallet_revive::Error::<Self::T>::StateChangeDenied.into()))
@@ -256,9 +318,16 @@ where
 					ExecOrigin::Root => frame_system::RawOrigin::Root.into(),
 				};
 
-				// 2. Pre-charge worst-case weight
-				let weight_to_charge =
-					<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::place_decision_deposit_worst_case();
+				// 2. Pre-charge the conservative maximum over the five branches
+				// `place_decision_deposit` can take (preparing/queued/not_queued/passing/failing).
+				// The branch actually taken depends on state only the pallet call itself resolves
+				// (deciding-slot occupancy, queue depth, tally), so it cannot be predicted here.
+				let weight_to_charge = Weight::zero()
+					.max(<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::place_decision_deposit_preparing())
+					.max(<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::place_decision_deposit_queued())
+					.max(<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::place_decision_deposit_not_queued())
+					.max(<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::place_decision_deposit_passing())
+					.max(<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::place_decision_deposit_failing());
 				let charged_amount = env.charge(weight_to_charge)?;
 
 				// 3. Place deposit
@@ -280,6 +349,78 @@ where
 					Err(e) => Err(revert(&e, "Referenda Precompile: Place decision deposit failed")),
 				}
 			},
+			IReferendaCalls::refundSubmissionDeposit(IReferenda::refundSubmissionDepositCall {
+				referendumIndex: index,
+			}) => {
+				info!(target: LOG_TARGET, ?index, "refundSubmissionDeposit");
+				// 1. Convert EVM caller to transaction origin
+				let origin: RuntimeOriginFor<Runtime> = match &&exec_origin {
+					ExecOrigin::Signed(account_id) => {
+						frame_system::RawOrigin::Signed(account_id.clone()).into()
+					},
+					ExecOrigin::Root => frame_system::RawOrigin::Root.into(),
+				};
+
+				// 2. Charge the best-case weight upfront, since a completed referendum with a
+				// refundable deposit is the common path.
+				let best_case_weight =
+					<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::refund_submission_deposit_best_case();
+				let charged_amount = env.charge(best_case_weight)?;
+
+				// 3. Refund the submission deposit
+				let result =
+					pallet_referenda::Pallet::<Runtime>::refund_submission_deposit(origin, *index);
+
+				// 4. Adjust gas for the branch actually taken
+				let actual_weight = if result.is_ok() {
+					best_case_weight
+				} else {
+					<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::refund_submission_deposit_error_case()
+				};
+				env.adjust_gas(charged_amount, actual_weight);
+
+				// 5. Handle result
+				match result {
+					Ok(_) => Ok(Vec::new()),
+					Err(e) => Err(revert(&e, "Referenda Precompile: Refund submission deposit failed")),
+				}
+			},
+			IReferendaCalls::refundDecisionDeposit(IReferenda::refundDecisionDepositCall {
+				referendumIndex: index,
+			}) => {
+				info!(target: LOG_TARGET, ?index, "refundDecisionDeposit");
+				// 1. Convert EVM caller to transaction origin
+				let origin: RuntimeOriginFor<Runtime> = match &&exec_origin {
+					ExecOrigin::Signed(account_id) => {
+						frame_system::RawOrigin::Signed(account_id.clone()).into()
+					},
+					ExecOrigin::Root => frame_system::RawOrigin::Root.into(),
+				};
+
+				// 2. Charge the best-case weight upfront, since a refundable deposit is the
+				// common path.
+				let best_case_weight =
+					<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::refund_decision_deposit_best_case();
+				let charged_amount = env.charge(best_case_weight)?;
+
+				// 3. Refund the decision deposit
+				let result =
+					pallet_referenda::Pallet::<Runtime>::refund_decision_deposit(origin, *index);
+
+				// 4. Adjust gas for the branch actually taken
+				let actual_weight = if result.is_ok() {
+					best_case_weight
+				} else {
+					<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::refund_decision_deposit_error_case()
+				};
+				env.adjust_gas(charged_amount, actual_weight);
+
+				// 5. Handle result
+				match result {
+					Ok(_) => Ok(Vec::new()),
+					Err(e) => Err(revert(&e, "Referenda Precompile: Refund decision deposit failed")),
+				}
+			},
 			IReferendaCalls::submissionDeposit(IReferenda::submissionDepositCall) => {
 				// Charge gas for submissionDeposit (read-only operation)
 				env.charge(
@@ -341,6 +482,145 @@ where
 
 				Ok(decision_deposit_amount.abi_encode())
 			},
+			IReferendaCalls::referendumStatus(IReferenda::referendumStatusCall {
+				referendumIndex: index,
+			}) => {
+				// Charge the worst case (an ongoing referendum, which does the most work to
+				// assemble) upfront and refund the difference for the lighter branches.
+				let max_charge =
+					<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::referendum_status_ongoing();
+				let charged_amount = env.charge(max_charge)?;
+
+				let referendum_info = ReferendumInfoFor::<Runtime, ()>::get(*index);
+				let (actual_weight, exists, status) = match referendum_info {
+					Some(ReferendumInfo::Ongoing(status)) => {
+						let (hash, proposal_len) = proposal_hash_and_len::<Runtime, ()>(&status.proposal);
+						let (enactment_timing, enactment_moment) =
+							convert_dispatch_to_timing::<Runtime, ()>(status.enactment);
+
+						let submission_who =
+							<Runtime as pallet_revive::Config>::AddressMapper::to_address(
+								&status.submission_deposit.who,
+							);
+						let submission_deposit = IReferenda::Deposit {
+							who: submission_who.0.into(),
+							amount: status.submission_deposit.amount.saturated_into(),
+						};
+
+						let (has_decision_deposit, decision_deposit) =
+							match &status.decision_deposit {
+								Some(deposit) => {
+									let who = <Runtime as pallet_revive::Config>::AddressMapper::to_address(
+										&deposit.who,
+									);
+									(
+										true,
+										IReferenda::Deposit {
+											who: who.0.into(),
+											amount: deposit.amount.saturated_into(),
+										},
+									)
+								},
+								None => (
+									false,
+									IReferenda::Deposit {
+										who: Default::default(),
+										amount: 0,
+									},
+								),
+							};
+
+						let deciding = match &status.deciding {
+							Some(deciding) => IReferenda::DecidingStatus {
+								isDeciding: true,
+								since: deciding.since.saturated_into(),
+								isConfirming: deciding.confirming.is_some(),
+								confirming: deciding.confirming.map(|c| c.saturated_into()).unwrap_or(0),
+							},
+							None => IReferenda::DecidingStatus {
+								isDeciding: false,
+								since: 0,
+								isConfirming: false,
+								confirming: 0,
+							},
+						};
+
+						let ayes: u128 = status.tally.ayes(status.track).saturated_into();
+						let approval = status.tally.approval(status.track);
+						let total_votes =
+							if approval.is_zero() { ayes } else { approval.saturating_reciprocal_mul(ayes) };
+						let nays = total_votes.saturating_sub(ayes);
+						let support = status.tally.support(status.track).deconstruct();
+						let tally = IReferenda::Tally { ayes, nays, support };
+
+						let status = IReferenda::ReferendumStatus {
+							track: u16::decode(&mut &status.track.encode()[..]).unwrap_or_default(),
+							origin: status.origin.encode().into(),
+							proposalHash: hash.into(),
+							proposalLen: proposal_len,
+							enactmentTiming: enactment_timing,
+							enactmentMoment: enactment_moment,
+							submitted: status.submitted.saturated_into(),
+							submissionDeposit: submission_deposit,
+							hasDecisionDeposit: has_decision_deposit,
+							decisionDeposit: decision_deposit,
+							deciding,
+							tally,
+							inQueue: status.in_queue,
+						};
+
+						let weight =
+							<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::referendum_status_ongoing();
+						(weight, true, status)
+					},
+					_ => {
+						let weight = <crate::weights::SubstrateWeight<Runtime> as WeightInfo>::referendum_status_not_found_or_completed();
+						(weight, false, IReferenda::ReferendumStatus::default())
+					},
+				};
+
+				env.adjust_gas(charged_amount, actual_weight);
+				Ok((exists, status).abi_encode())
+			},
+			IReferendaCalls::referendumCount(IReferenda::referendumCountCall) => {
+				env.charge(<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::referendum_count())?;
+
+				let count = ReferendumCount::<Runtime, ()>::get();
+				Ok(count.abi_encode())
+			},
+			IReferendaCalls::trackInfo(IReferenda::trackInfoCall { track }) => {
+				// Charge the worst case (a track that exists) upfront.
+				let max_charge =
+					<crate::weights::SubstrateWeight<Runtime> as WeightInfo>::track_info_found();
+				let charged_amount = env.charge(max_charge)?;
+
+				let track_id = <TrackIdOf<Runtime> as Decode>::decode(&mut &track.encode()[..])
+					.map_err(|e| revert(&e, "Referenda Precompile: Invalid track id"))?;
+
+				let (actual_weight, exists, info) =
+					match <Runtime as pallet_referenda::Config>::Tracks::info(track_id) {
+						Some(track) => {
+							let info = IReferenda::TrackInfo {
+								preparePeriod: track.prepare_period.saturated_into(),
+								decisionPeriod: track.decision_period.saturated_into(),
+								confirmPeriod: track.confirm_period.saturated_into(),
+								minEnactmentPeriod: track.min_enactment_period.saturated_into(),
+								decisionDeposit: track.decision_deposit.saturated_into(),
+								minApproval: sample_curve(&track.min_approval),
+								minSupport: sample_curve(&track.min_support),
+							};
+							let weight = <crate::weights::SubstrateWeight<Runtime> as WeightInfo>::track_info_found();
+							(weight, true, info)
+						},
+						None => {
+							let weight = <crate::weights::SubstrateWeight<Runtime> as WeightInfo>::track_info_not_found();
+							(weight, false, IReferenda::TrackInfo::default())
+						},
+					};
+
+				env.adjust_gas(charged_amount, actual_weight);
+				Ok((exists, info).abi_encode())
+			},
 			_ => Ok(Vec::new()),
 		}
 	}
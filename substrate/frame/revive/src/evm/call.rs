@@ -22,8 +22,8 @@ use crate::{
 		fees::{compute_max_integer_quotient, InfoT},
 		runtime::SetWeightLimit,
 	},
-	extract_code_and_data, BalanceOf, CallOf, Config, GenericTransaction, Pallet, Weight, Zero,
-	LOG_TARGET, RUNTIME_PALLETS_ADDR,
+	extract_code_and_data, storage::AccountInfo, BalanceOf, CallOf, Config, GenericTransaction,
+	Pallet, Weight, Zero, LOG_TARGET, RUNTIME_PALLETS_ADDR,
 };
 use alloc::{boxed::Box, vec::Vec};
 use codec::DecodeLimit;
@@ -47,6 +47,12 @@ pub struct CallInfo<T: Config> {
 	pub storage_deposit: BalanceOf<T>,
 	/// The ethereum gas limit of the transaction.
 	pub eth_gas_limit: U256,
+	/// The priority fee (tip) of an EIP-1559 transaction, in native currency.
+	///
+	/// This is the portion of [`Self::tx_fee`] that is owed to the block author rather than
+	/// burned, i.e. `(effective_gas_price - base_fee) * gas`. It is zero for legacy and
+	/// type-2 transactions that don't specify a priority fee.
+	pub priority_fee: BalanceOf<T>,
 }
 
 /// Mode for creating a call from an ethereum transaction.
@@ -80,14 +86,6 @@ where
 		return Err(InvalidTransaction::Call);
 	};
 
-	// Currently, effective_gas_price will always be the same as base_fee
-	// Because all callers of `create_call` will prepare `tx` that way. Some of the subsequent
-	// logic will not work correctly anymore if we change that assumption.
-	let Some(effective_gas_price) = tx.gas_price else {
-		log::debug!(target: LOG_TARGET, "No gas_price provided.");
-		return Err(InvalidTransaction::Payment);
-	};
-
 	let chain_id = tx.chain_id.unwrap_or_default();
 
 	if chain_id != <T as Config>::ChainId::get().into() {
@@ -95,13 +93,50 @@ where
 		return Err(InvalidTransaction::Call);
 	}
 
-	if effective_gas_price < base_fee {
-		log::debug!(
-			target: LOG_TARGET,
-			"Specified gas_price is too low. effective_gas_price={effective_gas_price} base_fee={base_fee}"
-		);
-		return Err(InvalidTransaction::Payment);
-	}
+	// Type-2 (EIP-1559) transactions carry `max_fee_per_gas`/`max_priority_fee_per_gas` instead
+	// of a flat `gas_price`. The price actually paid is capped at `max_fee_per_gas` but never
+	// exceeds `base_fee + max_priority_fee_per_gas`; the portion above `base_fee` is the tip
+	// owed to the block author, the rest is burned.
+	let (effective_gas_price, priority_fee_per_gas) =
+		if let Some(max_fee_per_gas) = tx.max_fee_per_gas {
+			let max_priority_fee_per_gas = tx.max_priority_fee_per_gas.unwrap_or_default();
+
+			if max_fee_per_gas < base_fee {
+				log::debug!(
+					target: LOG_TARGET,
+					"max_fee_per_gas is too low. max_fee_per_gas={max_fee_per_gas} base_fee={base_fee}"
+				);
+				return Err(InvalidTransaction::Payment);
+			}
+			if max_fee_per_gas < max_priority_fee_per_gas {
+				log::debug!(
+					target: LOG_TARGET,
+					"max_priority_fee_per_gas exceeds max_fee_per_gas. max_fee_per_gas={max_fee_per_gas} \
+					max_priority_fee_per_gas={max_priority_fee_per_gas}"
+				);
+				return Err(InvalidTransaction::Payment);
+			}
+
+			let effective_gas_price =
+				max_fee_per_gas.min(base_fee.saturating_add(max_priority_fee_per_gas));
+			let priority_fee_per_gas = effective_gas_price.saturating_sub(base_fee);
+			(effective_gas_price, priority_fee_per_gas)
+		} else {
+			let Some(gas_price) = tx.gas_price else {
+				log::debug!(target: LOG_TARGET, "No gas_price provided.");
+				return Err(InvalidTransaction::Payment);
+			};
+
+			if gas_price < base_fee {
+				log::debug!(
+					target: LOG_TARGET,
+					"Specified gas_price is too low. gas_price={gas_price} base_fee={base_fee}"
+				);
+				return Err(InvalidTransaction::Payment);
+			}
+
+			(gas_price, U256::zero())
+		};
 
 	let (encoded_len, transaction_encoded) =
 		if let CreateCallMode::ExtrinsicExecution(encoded_len, transaction_encoded) = mode {
@@ -125,6 +160,16 @@ where
 			(<T as Config>::FeeInfo::encoded_len(eth_transact_call.into()), transaction_encoded)
 		};
 
+	// EIP-3607: reject transactions whose sender is itself a deployed contract. This closes
+	// an impersonation/forwarding vector where a contract address is used as if it were an
+	// externally owned account.
+	if let Some(from) = tx.from {
+		if <AccountInfo<T>>::is_contract(&from) {
+			log::debug!(target: LOG_TARGET, "Sender {from:?} is a contract");
+			return Err(InvalidTransaction::BadSigner);
+		}
+	}
+
 	let value = tx.value.unwrap_or_default();
 	let data = tx.input.to_vec();
 
@@ -234,5 +279,18 @@ where
 		InvalidTransaction::Payment
 	})?.saturated_into();
 
-	Ok(CallInfo { call, weight_limit, encoded_len, tx_fee, storage_deposit, eth_gas_limit: gas })
+	// the portion of eth_fee that is owed to the block author rather than burned
+	let priority_fee = (priority_fee_per_gas.saturating_mul(gas) /
+		<T as Config>::NativeToEthRatio::get())
+	.saturated_into();
+
+	Ok(CallInfo {
+		call,
+		weight_limit,
+		encoded_len,
+		tx_fee,
+		storage_deposit,
+		eth_gas_limit: gas,
+		priority_fee,
+	})
 }
@@ -294,7 +294,7 @@ pub trait EthExtra {
 
 		let signer = <Self::Config as Config>::AddressMapper::to_fallback_account_id(&signer_addr);
 		let base_fee = <Pallet<Self::Config>>::evm_base_fee();
-		let tx = GenericTransaction::from_signed(tx, base_fee, None);
+		let tx = GenericTransaction::from_signed(tx, base_fee, Some(signer_addr));
 		let nonce = tx.nonce.unwrap_or_default().try_into().map_err(|_| {
 			log::debug!(target: LOG_TARGET, "Failed to convert nonce");
 			InvalidTransaction::Call
@@ -17,8 +17,9 @@
 
 use super::{deposit_limit, GAS_LIMIT};
 use crate::{
-	address::AddressMapper, AccountIdOf, BalanceOf, BumpNonce, Code, Config, ContractResult,
-	DepositLimit, ExecReturnValue, InstantiateReturnValue, OriginFor, Pallet, Weight, U256,
+	address::AddressMapper, AccountIdOf, BalanceOf, BumpNonce, Code, CodeUploadResult,
+	CodeUploadReturnValue, Config, ContractResult, DepositLimit, ExecReturnValue,
+	InstantiateReturnValue, OriginFor, Pallet, Weight, U256,
 };
 use alloc::{vec, vec::Vec};
 use frame_support::pallet_prelude::DispatchResultWithPostInfo;
@@ -256,3 +257,146 @@ builder!(
 		}
 	}
 );
+
+/// A builder to construct an eth-style contract instantiation, going through the same
+/// `bare_instantiate` path `eth_transact` ends up dispatching to, so tests can assert on the
+/// created address and account mapping the same way they do for [`BareInstantiateBuilder`].
+pub struct EthInstantiateBuilder<T: Config> {
+	origin: OriginFor<T>,
+	value: U256,
+	gas_limit: Weight,
+	storage_deposit_limit: DepositLimit<BalanceOf<T>>,
+	code: Vec<u8>,
+	data: Vec<u8>,
+	salt: Option<[u8; 32]>,
+}
+
+#[allow(dead_code)]
+impl<T: Config> EthInstantiateBuilder<T>
+where
+	BalanceOf<T>: Into<sp_core::U256> + TryFrom<sp_core::U256>,
+	crate::MomentOf<T>: Into<sp_core::U256>,
+	T::Hash: frame_support::traits::IsType<sp_core::H256>,
+{
+	/// Set the origin
+	pub fn origin(mut self, value: OriginFor<T>) -> Self {
+		self.origin = value;
+		self
+	}
+
+	/// Set the value
+	pub fn value(mut self, value: U256) -> Self {
+		self.value = value;
+		self
+	}
+
+	/// Set the gas_limit
+	pub fn gas_limit(mut self, value: Weight) -> Self {
+		self.gas_limit = value;
+		self
+	}
+
+	/// Set the storage_deposit_limit
+	pub fn storage_deposit_limit(mut self, value: DepositLimit<BalanceOf<T>>) -> Self {
+		self.storage_deposit_limit = value;
+		self
+	}
+
+	/// Set the code
+	pub fn code(mut self, value: Vec<u8>) -> Self {
+		self.code = value;
+		self
+	}
+
+	/// Set the data
+	pub fn data(mut self, value: Vec<u8>) -> Self {
+		self.data = value;
+		self
+	}
+
+	/// Set the salt
+	pub fn salt(mut self, value: Option<[u8; 32]>) -> Self {
+		self.salt = value;
+		self
+	}
+
+	/// Set the call's value using a native_value amount.
+	pub fn native_value(mut self, value: BalanceOf<T>) -> Self {
+		self.value = Pallet::<T>::convert_native_to_evm(value);
+		self
+	}
+
+	/// Build the eth-style instantiate call.
+	pub fn build(self) -> ContractResult<InstantiateReturnValue, BalanceOf<T>> {
+		Pallet::<T>::bare_instantiate(
+			self.origin,
+			self.value,
+			self.gas_limit,
+			self.storage_deposit_limit,
+			Code::Upload(self.code),
+			self.data,
+			self.salt,
+			BumpNonce::Yes,
+		)
+	}
+
+	/// Build the instantiate call and unwrap the result.
+	pub fn build_and_unwrap_result(self) -> InstantiateReturnValue {
+		self.build().result.unwrap()
+	}
+
+	/// Build the instantiate call and unwrap the created contract.
+	pub fn build_and_unwrap_contract(self) -> Contract<T> {
+		let result = self.build().result.unwrap();
+		assert!(!result.result.did_revert(), "instantiation did revert");
+
+		let addr = result.addr;
+		let account_id = T::AddressMapper::to_account_id(&addr);
+		Contract { account_id, addr }
+	}
+
+	/// Create an [`EthInstantiateBuilder`] with default values.
+	pub fn eth_instantiate(origin: OriginFor<T>, code: Vec<u8>) -> Self {
+		Self {
+			origin,
+			value: U256::zero(),
+			gas_limit: GAS_LIMIT,
+			storage_deposit_limit: DepositLimit::Balance(deposit_limit::<T>()),
+			code,
+			data: vec![],
+			salt: Some([0; 32]),
+		}
+	}
+}
+
+builder!(
+	UploadCodeBuilder,
+	bare_upload_code(
+		origin: OriginFor<T>,
+		code: Vec<u8>,
+		storage_deposit_limit: BalanceOf<T>,
+	) -> CodeUploadResult<BalanceOf<T>>;
+
+	/// Build the upload call and unwrap the result.
+	pub fn build_and_unwrap_result(self) -> CodeUploadReturnValue<BalanceOf<T>> {
+		self.build().unwrap()
+	}
+
+	/// Create an [`UploadCodeBuilder`] with default values.
+	pub fn upload_code(origin: OriginFor<T>, code: Vec<u8>) -> Self {
+		Self { origin, code, storage_deposit_limit: deposit_limit::<T>() }
+	}
+);
+
+builder!(
+	RemoveCodeBuilder,
+	remove_code(
+		origin: OriginFor<T>,
+		code_hash: sp_core::H256,
+	) -> DispatchResultWithPostInfo;
+
+	/// Create a [`RemoveCodeBuilder`] with default values.
+	pub fn remove_code(origin: OriginFor<T>, code_hash: sp_core::H256) -> Self {
+		Self { origin, code_hash }
+	}
+);
@@ -185,6 +185,15 @@ pub mod pallet {
 	#[pallet::getter(fn current_slot)]
 	pub type CurrentSlot<T> = StorageValue<_, Slot, ValueQuery>;
 
+	/// Number of blocks authored so far within the current epoch.
+	///
+	/// Slots are not guaranteed to be filled with a block, so this can lag behind the current
+	/// slot index. Used to predict the next session rotation from the observed block production
+	/// rate rather than assuming every slot produces a block.
+	#[pallet::storage]
+	#[pallet::getter(fn blocks_this_epoch)]
+	pub type BlocksThisEpoch<T> = StorageValue<_, u32, ValueQuery>;
+
 	/// Current epoch randomness.
 	#[pallet::storage]
 	#[pallet::getter(fn randomness)]
@@ -320,7 +329,11 @@ pub mod pallet {
 				.find_map(|item| item.pre_runtime_try_to::<SlotClaim>(&SASSAFRAS_ENGINE_ID))
 				.expect("Valid block must have a slot claim. qed");
 
+			let is_new_slot = CurrentSlot::<T>::get() != claim.slot;
 			CurrentSlot::<T>::put(claim.slot);
+			if is_new_slot {
+				BlocksThisEpoch::<T>::mutate(|count| *count = count.saturating_add(1));
+			}
 
 			if block_num == One::one() {
 				Self::post_genesis_initialize(claim.slot);
@@ -607,6 +620,9 @@ impl<T: Config> Pallet<T> {
 			Self::update_ring_verifier(&next_authorities);
 		}
 
+		// The current block is the first of the new epoch.
+		BlocksThisEpoch::<T>::put(1);
+
 		// Update authorities
 		Authorities::<T>::put(&authorities);
 		NextAuthorities::<T>::put(&next_authorities);
@@ -80,23 +80,37 @@ impl<T: Config> EstimateNextSessionRotation<BlockNumberFor<T>> for Pallet<T> {
 	}
 
 	fn estimate_current_session_progress(_now: BlockNumberFor<T>) -> (Option<Permill>, Weight) {
-		let elapsed_slots = Self::current_slot_index() + 1;
-		let progress = Permill::from_rational(elapsed_slots, T::EpochDuration::get());
-		// DB-Reads: CurrentSlot, GenesisSlot, EpochIndex, EpochDuration
-		(Some(progress), T::DbWeight::get().reads(4))
+		// Report progress in terms of authored blocks versus the blocks expected for a full
+		// epoch, rather than the raw slot index, so that missed slots don't make the session
+		// look more advanced than it actually is.
+		let authored_blocks = Self::blocks_this_epoch();
+		let progress = Permill::from_rational(authored_blocks, T::EpochDuration::get());
+		// DB-Reads: BlocksThisEpoch
+		(Some(progress), T::DbWeight::get().reads(1))
 	}
 
 	/// Return the best guess block number at which the next epoch change is predicted to happen.
 	///
-	/// This is only accurate if no slots are missed. Given missed slots, the slot number will grow
-	/// while the block number will not. Hence, the result can be interpreted as an upper bound.
+	/// The prediction scales the remaining slots in the epoch by the block production rate
+	/// observed so far this epoch, so that missed slots don't make the estimate overshoot.
 	fn estimate_next_session_rotation(
 		now: BlockNumberFor<T>,
 	) -> (Option<BlockNumberFor<T>>, Weight) {
 		let current_slot = Self::current_slot_index();
-		let remaining = T::EpochDuration::get().saturating_sub(current_slot);
-		let upper_bound: BlockNumberFor<T> = now.saturating_add(remaining.saturated_into());
-		// DB-Reads: CurrentSlot, GenesisSlot, EpochIndex, EpochDuration
-		(Some(upper_bound), T::DbWeight::get().reads(4))
+		let elapsed_slots = current_slot + 1;
+		let authored_blocks = Self::blocks_this_epoch();
+
+		let r = if elapsed_slots == 0 {
+			Permill::one()
+		} else {
+			Permill::from_rational(authored_blocks, elapsed_slots)
+				.clamp(Permill::from_parts(1), Permill::one())
+		};
+
+		let remaining_slots = T::EpochDuration::get().saturating_sub(current_slot);
+		let remaining_blocks: BlockNumberFor<T> = (r * remaining_slots).saturated_into();
+		let estimate = now.saturating_add(remaining_blocks);
+		// DB-Reads: CurrentSlot, GenesisSlot, EpochIndex, BlocksThisEpoch
+		(Some(estimate), T::DbWeight::get().reads(4))
 	}
 }
@@ -665,6 +665,7 @@ mod tests {
 	use remote_externalities::{
 		Builder, Mode, OfflineConfig, OnlineConfig, SnapshotConfig, Transport,
 	};
+	use sp_staking::ExposurePage;
 	use std::env::var;
 
 	fn weight_diff(block: Weight, op: Weight) {
@@ -823,4 +824,97 @@ mod tests {
 			}
 		});
 	}
+
+	/// Replays the outcome of a real paged election against production-scale data, and re-checks
+	/// the invariants that `EraElectionPlanner::do_elect_paged_inner`/`store_stakers_info` (in
+	/// `pallet-staking-async`) are supposed to uphold: the elected set fits within
+	/// `ValidatorCount`, every stored exposure page fits within `MaxExposurePageSize`, and the
+	/// stake recorded across a validator's pages adds up to its full exposure (mirrors the
+	/// in-pallet `can_page_exposure` check, just against live state).
+	///
+	/// Note: `do_elect_paged_inner`/`store_stakers_info` themselves are `pub(crate)` to
+	/// `pallet-staking-async` and are not reachable from this runtime crate, so rather than
+	/// re-driving that paging loop from scratch, this replays its already-committed output by
+	/// reading `ElectableStashes` and `ErasStakersPaged` back from chain state.
+	///
+	/// Run like:
+	///
+	/// ```text
+	/// RUST_LOG=remote-ext=info,runtime::staking-async=debug \
+	/// 	REMOTE_TESTS=1 \
+	/// 	WS=wss://westend-rpc.polkadot.io:443 \
+	/// 	cargo test --release -p pallet-staking-async-parachain-runtime \
+	/// 	--features try-runtime election_replay_invariants_hold
+	/// ```
+	#[tokio::test]
+	async fn election_replay_invariants_hold() {
+		if var("REMOTE_TESTS").is_err() {
+			return;
+		}
+		sp_tracing::try_init_simple();
+
+		let transport: Transport =
+			var("WS").unwrap_or("wss://westend-rpc.polkadot.io:443".to_string()).into();
+
+		let mut ext = Builder::<Block>::default()
+			.mode(Mode::Online(OnlineConfig {
+				pallets: vec!["Staking".to_string()],
+				transport,
+				..Default::default()
+			}))
+			.build()
+			.await
+			.unwrap();
+
+		ext.execute_with(|| {
+			let era = pallet_staking_async::ActiveEra::<Runtime>::get()
+				.expect("a live chain must have an active era")
+				.index;
+			let validator_count = pallet_staking_async::ValidatorCount::<Runtime>::get();
+			let electable_stashes = pallet_staking_async::ElectableStashes::<Runtime>::get();
+			let max_exposure_page_size =
+				<Runtime as pallet_staking_async::Config>::MaxExposurePageSize::get();
+
+			assert!(
+				electable_stashes.len() as u32 <= validator_count,
+				"elected set of {} must not exceed ValidatorCount of {}",
+				electable_stashes.len(),
+				validator_count,
+			);
+
+			for stash in electable_stashes.iter() {
+				let full_exposure = pallet_staking_async::Pallet::<Runtime>::eras_stakers(era, stash);
+				let mut total_from_pages: pallet_staking_async::BalanceOf<Runtime> = 0;
+				let mut page = 0u32;
+				while let Some(bounded_page) =
+					pallet_staking_async::pallet::ErasStakersPaged::<Runtime>::get((era, stash, page))
+				{
+					let exposure_page: ExposurePage<_, _> = bounded_page.into();
+					assert!(
+						exposure_page.others.len() as u32 <= max_exposure_page_size,
+						"page {} of {:?} has {} backers, exceeding MaxExposurePageSize of {}",
+						page,
+						stash,
+						exposure_page.others.len(),
+						max_exposure_page_size,
+					);
+					total_from_pages += exposure_page.page_total;
+					page += 1;
+				}
+				assert_eq!(
+					total_from_pages, full_exposure.total,
+					"stake recorded across the pages of {:?} must conserve its full exposure",
+					stash,
+				);
+			}
+
+			log::info!(
+				target: "runtime",
+				"replayed era {} election: {} validators (of {} allowed), invariants hold",
+				era,
+				electable_stashes.len(),
+				validator_count,
+			);
+		});
+	}
 }
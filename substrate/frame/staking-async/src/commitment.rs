@@ -0,0 +1,47 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Time-locked commitment bonding.
+//!
+//! [`Config::CommitmentMultiplier`](crate::Config::CommitmentMultiplier) maps a commitment's term
+//! (in eras, via [`Pallet::bond_with_commitment`](crate::pallet::Pallet::bond_with_commitment)) to
+//! a bonus factor, in the spirit of Darwinia's time-deposit model.
+//!
+//! NOTE: this is deliberately scoped down to the bonding, penalty and lapsing primitives. Actually
+//! multiplying a committed stash's voter weight fed into
+//! [`ElectionDataProvider::electing_voters`](frame_election_provider_support::ElectionDataProvider::electing_voters)
+//! and its share of exposure pages by [`CommitmentMultiplier::multiplier_for_term`] is left as
+//! follow-up work, for the same reason noted in [`crate::power`]: those code paths are read from
+//! many places across this pallet and by downstream election providers.
+
+use sp_runtime::Perbill;
+use sp_staking::EraIndex;
+
+/// Maps a commitment term to a bonus factor; the factor is not applied to voter weight or reward
+/// shares (see [`crate::commitment`]).
+pub trait CommitmentMultiplier {
+	/// The bonus factor applied to a commitment locked for `term` eras.
+	///
+	/// A value of [`Perbill::one`] means no bonus at all.
+	fn multiplier_for_term(term: EraIndex) -> Perbill;
+}
+
+impl CommitmentMultiplier for () {
+	fn multiplier_for_term(_term: EraIndex) -> Perbill {
+		Perbill::one()
+	}
+}
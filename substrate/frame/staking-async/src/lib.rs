@@ -188,9 +188,11 @@ pub(crate) mod mock;
 mod tests;
 
 pub mod asset;
+pub mod commitment;
 pub mod election_size_tracker;
 pub mod ledger;
 mod pallet;
+pub mod power;
 pub mod session_rotation;
 pub mod slashing;
 pub mod weights;
@@ -219,8 +221,10 @@ pub use sp_staking::{Exposure, IndividualExposure, StakerStatus};
 pub use weights::WeightInfo;
 
 // public exports
+pub use commitment::CommitmentMultiplier;
 pub use ledger::{StakingLedger, UnlockChunk};
 pub use pallet::{pallet::*, UseNominatorsAndValidatorsMap, UseValidatorsMap};
+pub use power::PowerFunction;
 
 pub(crate) const STAKING_ID: LockIdentifier = *b"staking ";
 pub(crate) const LOG_TARGET: &str = "runtime::staking-async";
@@ -281,6 +285,40 @@ pub struct ActiveEraInfo {
 	pub start: Option<u64>,
 }
 
+/// A governance-set override of [`Config::PlanningEraOffset`] (and, optionally,
+/// [`Config::SessionsPerEra`]), scheduled to take effect from a given era.
+///
+/// Deferring activation to [`Self::effective_from_era`] lets an operator raise or lower the
+/// offset without disturbing a planning window that has already opened for the active era; see
+/// [`crate::pallet::Pallet::set_planning_era_offset`].
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone)]
+pub struct PlanningEraOffsetSchedule {
+	/// The new value of `PlanningEraOffset`.
+	pub offset: SessionIndex,
+	/// The new value of `SessionsPerEra`, if it is being changed too.
+	pub sessions_per_era: Option<SessionIndex>,
+	/// The first active era from which this schedule applies.
+	pub effective_from_era: EraIndex,
+}
+
+/// A portion of a stash's active stake locked for a fixed term. The bonus factor of
+/// [`Config::CommitmentMultiplier`](crate::pallet::Config::CommitmentMultiplier) is not applied
+/// to the stash's voter weight or reward share.
+///
+/// Recorded via [`Pallet::bond_with_commitment`](crate::pallet::Pallet::bond_with_commitment);
+/// `value` cannot be unbonded before `unlock_era` except through
+/// [`Pallet::release_commitment_early`](crate::pallet::Pallet::release_commitment_early), which
+/// charges a penalty.
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone)]
+pub struct Commitment<Balance: HasCompact + MaxEncodedLen> {
+	/// The amount of the stash's active stake that is locked under this commitment.
+	#[codec(compact)]
+	pub value: Balance,
+	/// The era at which `value` is no longer subject to the commitment and can be unbonded
+	/// normally.
+	pub unlock_era: EraIndex,
+}
+
 /// Reward points of an era. Used to split era total payout between validators.
 ///
 /// This points will be used to reward validators and their respective nominators.
@@ -388,6 +388,10 @@ impl crate::pallet::pallet::Config for Test {
 	type MaxExposurePageSize = MaxExposurePageSize;
 	type MaxValidatorSet = MaxValidatorSet;
 	type ElectionProvider = TestElectionProvider;
+	// reuses the same mock provider as the primary one; a real chain would configure this with
+	// tighter `DataProviderBounds` so it can still produce a (smaller) result when the primary
+	// provider fails to bound a page.
+	type PageFallback = TestElectionProvider;
 	type VoterList = VoterBagsList;
 	type TargetList = UseValidatorsMap<Self>;
 	type NominationsQuota = WeightedNominationsQuota<16>;
@@ -405,6 +409,11 @@ impl crate::pallet::pallet::Config for Test {
 	type CurrencyToVote = SaturatingCurrencyToVote;
 	type Slash = ();
 	type WeightInfo = ();
+	// reuses the same `Balances` pallet as `Currency`; a real chain wanting a distinct secondary
+	// asset would plug in another `pallet_balances`/`pallet_assets` instance here instead.
+	type PowerAsset = Balances;
+	type PowerFunction = crate::power::LinearPowerFunction<ConstU32<50>>;
+	type CommitmentMultiplier = ();
 }
 
 pub struct WeightedNominationsQuota<const MAX: u32>;
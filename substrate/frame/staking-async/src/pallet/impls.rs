@@ -186,6 +186,27 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// The amount of `stash`'s active bond still locked under an unmatured [`Commitment`](crate::Commitment).
+	///
+	/// If `stash`'s commitment's term has elapsed, it is removed from storage (emitting
+	/// [`Event::CommitmentLapsed`]) and `Zero` is returned.
+	pub(super) fn committed_amount(stash: &T::AccountId) -> BalanceOf<T> {
+		let Some(commitment) = Commitments::<T>::get(stash) else {
+			return Zero::zero();
+		};
+
+		if session_rotation::Rotator::<T>::active_era() >= commitment.unlock_era {
+			Commitments::<T>::remove(stash);
+			Self::deposit_event(Event::<T>::CommitmentLapsed {
+				stash: stash.clone(),
+				value: commitment.value,
+			});
+			Zero::zero()
+		} else {
+			commitment.value
+		}
+	}
+
 	pub(super) fn do_withdraw_unbonded(
 		controller: &T::AccountId,
 		num_slashing_spans: u32,
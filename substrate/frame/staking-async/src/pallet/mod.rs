@@ -18,9 +18,10 @@
 //! `pallet-staking-async`'s main `pallet` module.
 
 use crate::{
-	asset, slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, BalanceOf, EraPayout,
-	EraRewardPoints, ExposurePage, Forcing, LedgerIntegrityState, MaxNominationsOf,
-	NegativeImbalanceOf, Nominations, NominationsQuota, PositiveImbalanceOf, RewardDestination,
+	asset, slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, BalanceOf, Commitment,
+	CommitmentMultiplier, EraPayout, EraRewardPoints, ExposurePage, Forcing, LedgerIntegrityState,
+	MaxNominationsOf, NegativeImbalanceOf, Nominations, NominationsQuota,
+	PlanningEraOffsetSchedule, PositiveImbalanceOf, PowerFunction, RewardDestination,
 	StakingLedger, UnappliedSlash, UnlockChunk, ValidatorPrefs,
 };
 use alloc::{format, vec::Vec};
@@ -32,8 +33,9 @@ use frame_support::{
 	traits::{
 		fungible::{
 			hold::{Balanced as FunHoldBalanced, Mutate as FunHoldMutate},
-			Mutate, Mutate as FunMutate,
+			Inspect as FunInspect, Mutate, Mutate as FunMutate,
 		},
+		tokens::Precision,
 		Contains, Defensive, DefensiveSaturating, EnsureOrigin, Get, InspectLockableCurrency,
 		Nothing, OnUnbalanced,
 	},
@@ -49,7 +51,7 @@ use rand_chacha::{
 };
 use sp_core::{sr25519::Pair as SrPair, Pair};
 use sp_runtime::{
-	traits::{StaticLookup, Zero},
+	traits::{Saturating, StaticLookup, Zero},
 	ArithmeticError, Perbill, Percent,
 };
 use sp_staking::{
@@ -155,6 +157,26 @@ pub mod pallet {
 		#[pallet::no_default_bounds]
 		type CurrencyToVote: sp_staking::currency_to_vote::CurrencyToVote<BalanceOf<Self>>;
 
+		/// A secondary asset that can optionally be bonded (see [`Call::bond_extra_power`]) to
+		/// boost a stash's staking power, in addition to its [`Config::Currency`] stake.
+		#[pallet::no_default]
+		type PowerAsset: FunHoldMutate<
+				Self::AccountId,
+				Reason = Self::RuntimeHoldReason,
+				Balance = Self::CurrencyBalance,
+			> + FunMutate<Self::AccountId, Balance = Self::CurrencyBalance>
+			+ FunHoldBalanced<Self::AccountId, Balance = Self::CurrencyBalance>;
+
+		/// Combines a stash's [`Config::Currency`] stake with its bonded [`Config::PowerAsset`]
+		/// amount into a single "power" score (see [`crate::power::power_of`]). The score is not
+		/// used as the stash's voter weight.
+		#[pallet::no_default_bounds]
+		type PowerFunction: PowerFunction<BalanceOf<Self>>;
+
+		/// Maps the term of a [`Call::bond_with_commitment`] to its reward-weight bonus.
+		#[pallet::no_default_bounds]
+		type CommitmentMultiplier: CommitmentMultiplier;
+
 		/// Something that provides the election functionality.
 		#[pallet::no_default]
 		type ElectionProvider: ElectionProvider<
@@ -164,6 +186,22 @@ pub mod pallet {
 			DataProvider = Pallet<Self>,
 		>;
 
+		/// A fallback election provider, tried for a page when [`Config::ElectionProvider`] fails
+		/// to produce a result for it (e.g. it returns `onchain::Error::FailedToBound`).
+		///
+		/// This is typically configured with tighter `DataProviderBounds` than the primary
+		/// provider, so that a reduced (but non-empty) set of supports can still be collected for
+		/// that page rather than discarding its backing weight entirely. See
+		/// [`session_rotation::EraElectionPlanner::do_elect_paged`].
+		#[pallet::no_default]
+		type PageFallback: ElectionProvider<
+			AccountId = Self::AccountId,
+			BlockNumber = BlockNumberFor<Self>,
+			DataProvider = Pallet<Self>,
+			MaxWinnersPerPage = <Self::ElectionProvider as ElectionProvider>::MaxWinnersPerPage,
+			MaxBackersPerWinner = <Self::ElectionProvider as ElectionProvider>::MaxBackersPerWinner,
+		>;
+
 		/// Something that defines the maximum number of nominations per nominator.
 		#[pallet::no_default_bounds]
 		type NominationsQuota: NominationsQuota<BalanceOf<Self>>;
@@ -370,6 +408,9 @@ pub mod pallet {
 		/// Funds on stake by a nominator or a validator.
 		#[codec(index = 0)]
 		Staking,
+		/// Funds of the secondary [`Config::PowerAsset`] bonded to boost staking power.
+		#[codec(index = 1)]
+		PowerStaking,
 	}
 
 	/// Default implementations of [`DefaultConfig`], which can be used to implement [`Config`].
@@ -412,6 +453,8 @@ pub mod pallet {
 			type EventListeners = ();
 			type Filter = Nothing;
 			type WeightInfo = ();
+			type PowerFunction = ();
+			type CommitmentMultiplier = ();
 		}
 	}
 
@@ -450,6 +493,19 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type MinCommission<T: Config> = StorageValue<_, Perbill, ValueQuery>;
 
+	/// The active time-locked [`Commitment`] of a stash, if any (see [`crate::commitment`] and
+	/// [`Call::bond_with_commitment`]). Keyed by stash.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type Commitments<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Commitment<BalanceOf<T>>, OptionQuery>;
+
+	/// The fraction of a commitment's value taken as a penalty on
+	/// [`Call::release_commitment_early`], routed to [`Config::RewardRemainder`].
+	#[pallet::storage]
+	pub type CommitmentEarlyReleasePenalty<T> = StorageValue<_, Perbill, ValueQuery>;
+
 	/// Map from all (unlocked) "controller" accounts to the info regarding the staking.
 	///
 	/// Note: All the reads and mutations to this storage *MUST* be done through the methods exposed
@@ -464,6 +520,14 @@ pub mod pallet {
 	pub type Payee<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, RewardDestination<T::AccountId>, OptionQuery>;
 
+	/// The amount of [`Config::PowerAsset`] bonded by a stash to boost its staking power (see
+	/// [`crate::power`]). Keyed by stash.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type PowerBonded<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
 	/// The map from (wannabe) validator stash key to the preferences of that validator.
 	///
 	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
@@ -531,6 +595,16 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type ActiveEra<T> = StorageValue<_, ActiveEraInfo>;
 
+	/// A pending governance override of [`Config::PlanningEraOffset`] (and, optionally,
+	/// [`Config::SessionsPerEra`]).
+	///
+	/// Set via [`Pallet::set_planning_era_offset`]. Read through
+	/// [`crate::session_rotation::Rotator::planning_era_offset`] and
+	/// [`crate::session_rotation::Rotator::sessions_per_era`], which fall back to the compile-time
+	/// `Config` values until [`PlanningEraOffsetSchedule::effective_from_era`] is reached.
+	#[pallet::storage]
+	pub type PlanningEraOffsetOverride<T> = StorageValue<_, PlanningEraOffsetSchedule>;
+
 	/// Custom bound for [`BondedEras`] which is equal to [`Config::BondingDuration`] + 1.
 	pub struct BondedErasBound<T>(core::marker::PhantomData<T>);
 	impl<T: Config> Get<u32> for BondedErasBound<T> {
@@ -1172,6 +1246,19 @@ pub mod pallet {
 			page: PageIndex,
 			result: Result<u32, u32>,
 		},
+		/// [`Config::ElectionProvider`] failed to produce a result for `page`, and
+		/// [`Config::PageFallback`] was invoked for it instead.
+		///
+		/// `primary_err` mirrors the `0` placeholder used by [`Self::PagedElectionProceeded`]'s
+		/// `Err` case: the primary provider's actual error type isn't required to be encodable,
+		/// so only the fact that it failed is recorded here.
+		PagedElectionFellBack {
+			page: PageIndex,
+			primary_err: u32,
+		},
+		/// A multi-page election concluded with no winners at all, from either
+		/// [`Config::ElectionProvider`] or [`Config::PageFallback`], across every page.
+		StakingElectionFailed,
 		/// An offence for the given validator, for the given percentage of their stake, at the
 		/// given era as been reported.
 		OffenceReported {
@@ -1213,6 +1300,47 @@ pub mod pallet {
 		EraPruned {
 			index: EraIndex,
 		},
+		/// The planning era offset (and, optionally, sessions-per-era) has been scheduled to
+		/// change. The new values only take effect from `effective_from_era` onwards, so that a
+		/// planning window which has already opened for the active era is not disturbed.
+		PlanningEraOffsetUpdated {
+			old_offset: SessionIndex,
+			new_offset: SessionIndex,
+			old_sessions_per_era: SessionIndex,
+			new_sessions_per_era: Option<SessionIndex>,
+			effective_from_era: EraIndex,
+		},
+		/// An account has bonded this amount of the secondary [`Config::PowerAsset`], contributing
+		/// towards its staking power.
+		PowerBonded {
+			stash: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// An account has unbonded this amount of the secondary [`Config::PowerAsset`].
+		PowerUnbonded {
+			stash: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A stash has locked `value` of its active bond under a time-locked commitment until
+		/// `unlock_era`.
+		Committed {
+			stash: T::AccountId,
+			value: BalanceOf<T>,
+			unlock_era: EraIndex,
+		},
+		/// A stash's commitment has fully matured and been removed; its funds are unbonded
+		/// normally.
+		CommitmentLapsed {
+			stash: T::AccountId,
+			value: BalanceOf<T>,
+		},
+		/// A stash released its still-active commitment early, paying `penalty` (routed to
+		/// [`Config::RewardRemainder`]) out of the committed `value`.
+		CommitmentReleasedEarly {
+			stash: T::AccountId,
+			value: BalanceOf<T>,
+			penalty: BalanceOf<T>,
+		},
 	}
 
 	/// Represents unexpected or invariant-breaking conditions encountered during execution.
@@ -1309,6 +1437,22 @@ pub mod pallet {
 		EraNotPrunable,
 		/// The slash has been cancelled and cannot be applied.
 		CancelledSlash,
+		/// The planning era offset must be between 1 and the (new, if also being changed)
+		/// `SessionsPerEra`.
+		InvalidPlanningEraOffset,
+		/// Cannot unbond more of the secondary [`Config::PowerAsset`] than is currently bonded.
+		NotEnoughPowerBonded,
+		/// The stash already has an active commitment; it must lapse or be released first.
+		AlreadyCommitted,
+		/// There is no active commitment to release for this stash.
+		NoActiveCommitment,
+		/// The commitment's term has already elapsed; unbond normally instead of releasing early.
+		CommitmentAlreadyMatured,
+		/// The value requested to commit is more than the stash's uncommitted active bond.
+		NotEnoughBondedToCommit,
+		/// This unbond would dip into a stash's still-committed stake; wait for the commitment's
+		/// term to elapse, or use [`Call::release_commitment_early`].
+		CommittedFundsLocked,
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -1600,6 +1744,14 @@ pub mod pallet {
 			);
 
 			if !value.is_zero() {
+				// a time-locked commitment (see `Call::bond_with_commitment`) keeps its portion of
+				// `active` out of reach of a plain `unbond` until it matures.
+				let committed = Self::committed_amount(&stash);
+				ensure!(
+					ledger.active.saturating_sub(value) >= committed,
+					Error::<T>::CommittedFundsLocked
+				);
+
 				ledger.active -= value;
 
 				// Avoid there being a dust balance left in the staking system.
@@ -2719,5 +2871,215 @@ pub mod pallet {
 				pays_fee: frame_support::dispatch::Pays::No,
 			})
 		}
+
+		/// Override [`Config::PlanningEraOffset`] (and, optionally, [`Config::SessionsPerEra`]) at
+		/// runtime.
+		///
+		/// The dispatch origin must be Root.
+		///
+		/// If the planning window for the currently active era has already opened (i.e. a new era
+		/// has already been planned), the new values are deferred to the era after the one being
+		/// planned so that the in-flight election is not disturbed. Otherwise they apply starting
+		/// from the active era's own planning window.
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::set_planning_era_offset())]
+		pub fn set_planning_era_offset(
+			origin: OriginFor<T>,
+			new_offset: SessionIndex,
+			new_sessions_per_era: Option<SessionIndex>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let sessions_per_era = new_sessions_per_era.unwrap_or_else(T::SessionsPerEra::get);
+			ensure!(
+				new_offset >= 1 && new_offset <= sessions_per_era,
+				Error::<T>::InvalidPlanningEraOffset
+			);
+
+			let active_era = crate::session_rotation::Rotator::<T>::active_era();
+			let planning_era = crate::session_rotation::Rotator::<T>::planning_era();
+			// If planning for the next era has already started, the new schedule must not disturb
+			// it; defer activation to the era after the one already being planned.
+			let effective_from_era =
+				if planning_era > active_era { planning_era + 1 } else { active_era };
+
+			let old_offset = crate::session_rotation::Rotator::<T>::planning_era_offset(active_era);
+			let old_sessions_per_era =
+				crate::session_rotation::Rotator::<T>::sessions_per_era(active_era);
+
+			PlanningEraOffsetOverride::<T>::put(PlanningEraOffsetSchedule {
+				offset: new_offset,
+				sessions_per_era: new_sessions_per_era,
+				effective_from_era,
+			});
+
+			Self::deposit_event(Event::<T>::PlanningEraOffsetUpdated {
+				old_offset,
+				new_offset,
+				old_sessions_per_era,
+				new_sessions_per_era,
+				effective_from_era,
+			});
+
+			Ok(())
+		}
+
+		/// Bond some amount of [`Config::PowerAsset`] to boost the staking power of `stash`'s
+		/// already-bonded stake (see [`crate::power`]).
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stash.
+		///
+		/// Unlike [`bond_extra`](Self::bond_extra), this does not interact with
+		/// [`Config::Currency`] at all, and the bonded amount is held immediately with no
+		/// unlocking-chunk queue: see [`unbond_power`](Self::unbond_power).
+		///
+		/// Emits `PowerBonded`.
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::bond_extra())]
+		pub fn bond_extra_power(
+			origin: OriginFor<T>,
+			#[pallet::compact] max_additional: BalanceOf<T>,
+		) -> DispatchResult {
+			let stash = ensure_signed(origin)?;
+			ensure!(!T::Filter::contains(&stash), Error::<T>::Restricted);
+			let _ = Self::ledger(StakingAccount::Stash(stash.clone()))?;
+
+			let additional = max_additional.min(T::PowerAsset::reducible_balance(
+				&stash,
+				frame_support::traits::tokens::Preservation::Expendable,
+				frame_support::traits::tokens::Fortitude::Polite,
+			));
+			ensure!(!additional.is_zero(), Error::<T>::NotEnoughPowerBonded);
+
+			T::PowerAsset::hold(&HoldReason::PowerStaking.into(), &stash, additional)?;
+			PowerBonded::<T>::mutate(&stash, |bonded| *bonded += additional);
+
+			if T::VoterList::contains(&stash) {
+				let _ = T::VoterList::on_update(&stash, Self::weight_of(&stash));
+			}
+
+			Self::deposit_event(Event::<T>::PowerBonded { stash, amount: additional });
+			Ok(())
+		}
+
+		/// Release some amount of [`Config::PowerAsset`] previously bonded via
+		/// [`bond_extra_power`](Self::bond_extra_power).
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stash.
+		///
+		/// The held amount is released immediately; unlike [`unbond`](Self::unbond), there is no
+		/// bonding-duration delay for the secondary asset.
+		///
+		/// Emits `PowerUnbonded`.
+		#[pallet::call_index(35)]
+		#[pallet::weight(T::WeightInfo::unbond())]
+		pub fn unbond_power(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T>,
+		) -> DispatchResult {
+			let stash = ensure_signed(origin)?;
+
+			let bonded = PowerBonded::<T>::get(&stash);
+			let value = value.min(bonded);
+			ensure!(!value.is_zero(), Error::<T>::NotEnoughPowerBonded);
+
+			T::PowerAsset::release(&HoldReason::PowerStaking.into(), &stash, value, Precision::Exact)?;
+			let remaining = bonded - value;
+			if remaining.is_zero() {
+				PowerBonded::<T>::remove(&stash);
+			} else {
+				PowerBonded::<T>::insert(&stash, remaining);
+			}
+
+			if T::VoterList::contains(&stash) {
+				let _ = T::VoterList::on_update(&stash, Self::weight_of(&stash));
+			}
+
+			Self::deposit_event(Event::<T>::PowerUnbonded { stash, amount: value });
+			Ok(())
+		}
+
+		/// Lock `value` of the stash's already-bonded active stake under a commitment for `term`
+		/// eras. The bonus factor of [`Config::CommitmentMultiplier`] for the term is not applied to
+		/// the stash's voter weight or reward share.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stash.
+		///
+		/// A stash may only have one active commitment at a time. The committed `value` cannot be
+		/// unbonded via [`Call::unbond`] until the commitment matures at the active era plus
+		/// `term`; see [`Call::release_commitment_early`] for releasing it sooner.
+		///
+		/// Emits `Committed`.
+		#[pallet::call_index(36)]
+		#[pallet::weight(T::WeightInfo::bond_extra())]
+		pub fn bond_with_commitment(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T>,
+			term: EraIndex,
+		) -> DispatchResult {
+			let stash = ensure_signed(origin)?;
+			ensure!(!T::Filter::contains(&stash), Error::<T>::Restricted);
+			ensure!(Commitments::<T>::get(&stash).is_none(), Error::<T>::AlreadyCommitted);
+
+			let ledger = Self::ledger(StakingAccount::Stash(stash.clone()))?;
+			let already_committed = Self::committed_amount(&stash);
+			ensure!(
+				ledger.active.saturating_sub(already_committed) >= value,
+				Error::<T>::NotEnoughBondedToCommit
+			);
+
+			let unlock_era = session_rotation::Rotator::<T>::active_era().saturating_add(term);
+			Commitments::<T>::insert(&stash, Commitment { value, unlock_era });
+
+			Self::deposit_event(Event::<T>::Committed { stash, value, unlock_era });
+			Ok(())
+		}
+
+		/// Release a stash's still-active commitment before its term has elapsed, paying
+		/// [`CommitmentEarlyReleasePenalty`] of its value as a penalty routed to
+		/// [`Config::RewardRemainder`].
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stash.
+		///
+		/// Fails if the stash has no active commitment, or if its term has already matured (in
+		/// which case its funds are already free to unbond normally, with no call needed here).
+		///
+		/// Emits `CommitmentReleasedEarly`.
+		#[pallet::call_index(37)]
+		#[pallet::weight(T::WeightInfo::bond_extra())]
+		pub fn release_commitment_early(origin: OriginFor<T>) -> DispatchResult {
+			let stash = ensure_signed(origin)?;
+
+			let commitment = Commitments::<T>::get(&stash).ok_or(Error::<T>::NoActiveCommitment)?;
+			ensure!(
+				session_rotation::Rotator::<T>::active_era() < commitment.unlock_era,
+				Error::<T>::CommitmentAlreadyMatured
+			);
+
+			let penalty = CommitmentEarlyReleasePenalty::<T>::get() * commitment.value;
+			if !penalty.is_zero() {
+				let (imbalance, _) = asset::slash::<T>(&stash, penalty);
+				T::RewardRemainder::on_unbalanced(imbalance);
+
+				// the penalty leaves the stash's stake, so the ledger must shrink to match.
+				let mut ledger = Self::ledger(StakingAccount::Stash(stash.clone()))?;
+				ledger.active = ledger.active.saturating_sub(penalty);
+				ledger.total = ledger.total.saturating_sub(penalty);
+				ledger.update()?;
+
+				if T::VoterList::contains(&stash) {
+					let _ = T::VoterList::on_update(&stash, Self::weight_of(&stash));
+				}
+			}
+
+			Commitments::<T>::remove(&stash);
+
+			Self::deposit_event(Event::<T>::CommitmentReleasedEarly {
+				stash,
+				value: commitment.value,
+				penalty,
+			});
+			Ok(())
+		}
 	}
 }
@@ -0,0 +1,84 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Secondary "power asset" bonding.
+//!
+//! Alongside the primary [`Config::Currency`](crate::Config::Currency) stake, a stash may
+//! optionally bond some amount of [`Config::PowerAsset`](crate::Config::PowerAsset) via
+//! [`Call::bond_extra_power`](crate::pallet::Call::bond_extra_power) to boost its staking power,
+//! in the spirit of Darwinia's RING/KTON model.
+//!
+//! [`Config::PowerFunction`](crate::Config::PowerFunction) decides how the two balances combine
+//! into a single score. [`power_of`] reads both balances for a stash and applies it.
+//!
+//! NOTE: this is deliberately scoped down to the bonding primitives and the score calculation.
+//! Feeding [`power_of`] into [`Config::VoterList`](crate::pallet::Config::VoterList)'s scoring,
+//! [`ElectionDataProvider::electing_voters`](frame_election_provider_support::ElectionDataProvider::electing_voters)'s
+//! emitted weight, and exposure-based reward splitting is left as follow-up work: those are read
+//! in many places throughout this pallet and by downstream election providers, and repointing them
+//! all at a second, independently-bondable balance is a large, separate change.
+
+use crate::{BalanceOf, Config, PowerBonded};
+use sp_core::Get;
+
+/// Combines a stash's primary stake with its secondary [`Config::PowerAsset`] bond into a single
+/// "power" score, as computed by [`power_of`]. The score is not used for voter weight, election
+/// weight or reward splitting.
+///
+/// Implementations decide how the two balances are combined; for example, splitting the score
+/// evenly between a share of the main asset and a share of the power asset, as sketched in the
+/// RING/KTON model this is inspired by. [`LinearPowerFunction`] is a simple default that does not
+/// require any pool-wide context.
+pub trait PowerFunction<Balance> {
+	/// Combine `main` (the bonded [`Config::Currency`] stake) and `extra` (the bonded
+	/// [`Config::PowerAsset`] amount) into a single power score.
+	fn power(main: Balance, extra: Balance) -> Balance;
+}
+
+impl<Balance> PowerFunction<Balance> for () {
+	fn power(main: Balance, _extra: Balance) -> Balance {
+		main
+	}
+}
+
+/// A [`PowerFunction`] that gives the power asset a fixed weight relative to the main stake,
+/// expressed as a [`Perbill`](sp_runtime::Perbill) of `extra` added on top of `main`.
+///
+/// For example, `LinearPowerFunction<ConstU32<50>>` (50%) turns a stash bonding `1000` main and
+/// `1000` power asset into a power of `1000 + 50% * 1000 = 1500`.
+pub struct LinearPowerFunction<ExtraWeight>(core::marker::PhantomData<ExtraWeight>);
+
+impl<Balance, ExtraWeight> PowerFunction<Balance> for LinearPowerFunction<ExtraWeight>
+where
+	Balance: sp_runtime::traits::AtLeast32BitUnsigned + Clone,
+	ExtraWeight: Get<u32>,
+{
+	fn power(main: Balance, extra: Balance) -> Balance {
+		let weighted_extra = sp_runtime::Perbill::from_percent(ExtraWeight::get()) * extra;
+		main.saturating_add(weighted_extra)
+	}
+}
+
+/// The stash's combined staking power: its primary stake plus its bonded
+/// [`Config::PowerAsset`](crate::Config::PowerAsset) amount, combined via
+/// [`Config::PowerFunction`](crate::Config::PowerFunction).
+///
+/// Returns just the primary stake for stashes that have not bonded any power asset.
+pub fn power_of<T: Config>(stash: &T::AccountId, main: BalanceOf<T>) -> BalanceOf<T> {
+	let extra = PowerBonded::<T>::get(stash);
+	T::PowerFunction::power(main, extra)
+}
@@ -512,6 +512,27 @@ impl<T: Config> Rotator<T> {
 		ActiveEra::<T>::get().map(|a| a.index).defensive_unwrap_or(0)
 	}
 
+	/// The `PlanningEraOffset` in effect for `active_era`.
+	///
+	/// Returns `Config::PlanningEraOffset` unless a governance override has been scheduled (see
+	/// [`crate::pallet::Pallet::set_planning_era_offset`]) that has already become effective for
+	/// `active_era`.
+	pub(crate) fn planning_era_offset(active_era: EraIndex) -> SessionIndex {
+		match PlanningEraOffsetOverride::<T>::get() {
+			Some(schedule) if active_era >= schedule.effective_from_era => schedule.offset,
+			_ => T::PlanningEraOffset::get(),
+		}
+	}
+
+	/// The `SessionsPerEra` in effect for `active_era`, mirroring [`Self::planning_era_offset`].
+	pub(crate) fn sessions_per_era(active_era: EraIndex) -> SessionIndex {
+		match PlanningEraOffsetOverride::<T>::get() {
+			Some(schedule) if active_era >= schedule.effective_from_era =>
+				schedule.sessions_per_era.unwrap_or_else(T::SessionsPerEra::get),
+			_ => T::SessionsPerEra::get(),
+		}
+	}
+
 	/// End the session and start the next one.
 	pub(crate) fn end_session(end_index: SessionIndex, activation_timestamp: Option<(u64, u32)>) {
 		let Some(active_era) = ActiveEra::<T>::get() else {
@@ -702,9 +723,10 @@ impl<T: Config> Rotator<T> {
 
 	/// Returns whether we are at the session where we should plan the new era.
 	fn is_plan_era_deadline(start_session: SessionIndex, active_era: EraIndex) -> bool {
-		let planning_era_offset = T::PlanningEraOffset::get().min(T::SessionsPerEra::get());
+		let sessions_per_era = Self::sessions_per_era(active_era);
+		let planning_era_offset = Self::planning_era_offset(active_era).min(sessions_per_era);
 		// session at which we should plan the new era.
-		let target_plan_era_session = T::SessionsPerEra::get().saturating_sub(planning_era_offset);
+		let target_plan_era_session = sessions_per_era.saturating_sub(planning_era_offset);
 		let era_start_session = ErasStartSessionIndex::<T>::get(&active_era).unwrap_or(0);
 
 		// progress of the active era in sessions.
@@ -806,6 +828,13 @@ impl<T: Config> EraElectionPlanner<T> {
 				use pallet_staking_async_rc_client::RcClientInterface;
 				let id = CurrentEra::<T>::get().defensive_unwrap_or(0);
 				let prune_up_to = Self::get_prune_up_to();
+				let electable_stashes = ElectableStashes::<T>::take();
+
+				if electable_stashes.is_empty() {
+					// every page, including any `Config::PageFallback` attempts, yielded zero
+					// winners.
+					Pallet::<T>::deposit_event(Event::StakingElectionFailed);
+				}
 
 				crate::log!(
 					info,
@@ -815,7 +844,7 @@ impl<T: Config> EraElectionPlanner<T> {
 				);
 
 				T::RcClientInterface::validator_set(
-					ElectableStashes::<T>::take().into_iter().collect(),
+					electable_stashes.into_iter().collect(),
 					id,
 					prune_up_to,
 				);
@@ -848,30 +877,55 @@ impl<T: Config> EraElectionPlanner<T> {
 	/// the result of the election. We ensure that only the winners that are part of the
 	/// electable stashes have exposures collected for the next era.
 	pub(crate) fn do_elect_paged(page: PageIndex) {
-		let election_result = T::ElectionProvider::elect(page);
-		match election_result {
-			Ok(supports) => {
-				let inner_processing_results = Self::do_elect_paged_inner(supports);
-				if let Err(not_included) = inner_processing_results {
-					defensive!(
-						"electable stashes exceeded limit, unexpected but election proceeds.\
-                		{} stashes from election result discarded",
-						not_included
-					);
-				};
-
-				Pallet::<T>::deposit_event(Event::PagedElectionProceeded {
-					page,
-					result: inner_processing_results.map(|x| x as u32).map_err(|x| x as u32),
-				});
-			},
+		match T::ElectionProvider::elect(page) {
+			Ok(supports) => Self::do_elect_paged_inner_and_report(page, supports),
 			Err(e) => {
 				log!(warn, "election provider page failed due to {:?} (page: {})", e, page);
-				Pallet::<T>::deposit_event(Event::PagedElectionProceeded { page, result: Err(0) });
+				Pallet::<T>::deposit_event(Event::PagedElectionFellBack { page, primary_err: 0 });
+
+				match T::PageFallback::elect(page) {
+					Ok(supports) => {
+						log!(warn, "fallback election provider recovered page {}", page);
+						Self::do_elect_paged_inner_and_report(page, supports);
+					},
+					Err(fallback_err) => {
+						log!(
+							warn,
+							"fallback election provider also failed for page {} due to {:?}",
+							page,
+							fallback_err
+						);
+						Pallet::<T>::deposit_event(Event::PagedElectionProceeded {
+							page,
+							result: Err(0),
+						});
+					},
+				}
 			},
 		}
 	}
 
+	/// Feed a successfully-fetched page's `supports` into [`Self::do_elect_paged_inner`] and
+	/// report the outcome via [`Event::PagedElectionProceeded`].
+	fn do_elect_paged_inner_and_report(
+		page: PageIndex,
+		supports: BoundedSupportsOf<T::ElectionProvider>,
+	) {
+		let inner_processing_results = Self::do_elect_paged_inner(supports);
+		if let Err(not_included) = inner_processing_results {
+			defensive!(
+				"electable stashes exceeded limit, unexpected but election proceeds.\
+                {} stashes from election result discarded",
+				not_included
+			);
+		};
+
+		Pallet::<T>::deposit_event(Event::PagedElectionProceeded {
+			page,
+			result: inner_processing_results.map(|x| x as u32).map_err(|x| x as u32),
+		});
+	}
+
 	/// Inner implementation of [`Self::do_elect_paged`].
 	///
 	/// Returns an error if adding election winners to the electable stashes storage fails due
@@ -116,6 +116,89 @@ fn planning_era_offset_more_works() {
 		});
 }
 
+#[test]
+fn set_planning_era_offset_before_deadline_applies_this_era() {
+	use crate::session_rotation::Rotator;
+
+	ExtBuilder::default()
+		.session_per_era(6)
+		.planning_era_offset(1)
+		.no_flush_events()
+		.build_and_execute(|| {
+			// with the default offset of 1, the planning deadline for era 0 would be session 4.
+			// raise it to 2 well before that, so it should apply to era 0's own planning window.
+			Session::roll_until_session(2);
+			assert_ok!(Staking::set_planning_era_offset(RuntimeOrigin::root(), 2, None));
+
+			assert_eq!(
+				staking_events_since_last_call().last(),
+				Some(&Event::PlanningEraOffsetUpdated {
+					old_offset: 1,
+					new_offset: 2,
+					old_sessions_per_era: 6,
+					new_sessions_per_era: None,
+					effective_from_era: 0,
+				})
+			);
+			assert_eq!(Rotator::<Test>::planning_era_offset(0), 2);
+
+			// the new, lower target session (6 - 2 = 4) is hit one session earlier than the
+			// default (6 - 1 = 5) would have been.
+			assert_eq!(
+				staking_events_since_last_call(),
+				vec![
+					Event::SessionRotated { starting_session: 3, active_era: 0, planned_era: 1 },
+					Event::PagedElectionProceeded { page: 0, result: Ok(2) },
+				]
+			);
+		});
+}
+
+#[test]
+fn set_planning_era_offset_after_deadline_defers_to_next_era() {
+	use crate::session_rotation::Rotator;
+
+	ExtBuilder::default()
+		.session_per_era(6)
+		.planning_era_offset(1)
+		.no_flush_events()
+		.build_and_execute(|| {
+			// roll past era 0's planning deadline (session 4), so era 1 is already planned.
+			Session::roll_until_session(5);
+			assert_eq!(Rotator::<Test>::planning_era(), 1);
+			assert_eq!(active_era(), 0);
+			let _ = staking_events_since_last_call();
+
+			assert_ok!(Staking::set_planning_era_offset(RuntimeOrigin::root(), 3, None));
+			assert_eq!(
+				staking_events_since_last_call(),
+				vec![Event::PlanningEraOffsetUpdated {
+					old_offset: 1,
+					new_offset: 3,
+					old_sessions_per_era: 6,
+					new_sessions_per_era: None,
+					effective_from_era: 2,
+				}]
+			);
+
+			// the already-planned election for era 1 is not disturbed: it still rotates in on
+			// schedule, with no skipped or duplicated election.
+			Session::roll_until_active_era(1);
+			assert_eq!(
+				staking_events_since_last_call(),
+				vec![
+					Event::SessionRotated { starting_session: 5, active_era: 0, planned_era: 1 },
+					Event::EraPaid { era_index: 0, validator_payout: 17500, remainder: 17500 },
+					Event::SessionRotated { starting_session: 6, active_era: 1, planned_era: 1 },
+				]
+			);
+
+			// the override only takes hold from era 2 onwards.
+			assert_eq!(Rotator::<Test>::planning_era_offset(1), 1);
+			assert_eq!(Rotator::<Test>::planning_era_offset(2), 3);
+		});
+}
+
 #[test]
 fn new_era_elects_correct_number_of_validators() {
 	ExtBuilder::default().nominate(true).validator_count(1).build_and_execute(|| {
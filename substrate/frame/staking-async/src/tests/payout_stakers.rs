@@ -160,6 +160,41 @@ fn rewards_with_nominator_should_work() {
 	});
 }
 
+#[test]
+fn uneven_era_points_yield_proportional_validator_payout() {
+	// `ErasRewardPoints` is populated per-validator from the points the relay chain reports
+	// alongside each session (see `pallet_staking_async_rc_client::SessionReport::validator_points`,
+	// ingested via `Eras::reward_active_era`); `payout_stakers` then splits `EraPaid`'s
+	// `validator_payout` in proportion to them, so an uneven points map must yield an uneven
+	// payout rather than an equal split.
+	ExtBuilder::default().nominate(false).session_per_era(3).build_and_execute(|| {
+		assert_eq_uvec!(Session::validators(), vec![11, 21]);
+
+		let init_balance_11 = asset::total_balance::<T>(&11);
+		let init_balance_21 = asset::total_balance::<T>(&21);
+
+		// 11 earns twice as many points as 21 over the era.
+		Eras::<T>::reward_active_era(vec![(11, 20)]);
+		Eras::<T>::reward_active_era(vec![(21, 10)]);
+
+		assert_eq!(
+			ErasRewardPoints::<T>::get(active_era()),
+			EraRewardPoints { total: 30, individual: vec![(11, 20), (21, 10)].into_iter().collect() }
+		);
+
+		let validator_payout = validator_payout_for(time_per_era());
+		Session::roll_until_active_era(2);
+		make_all_reward_payment(1);
+
+		let payout_11 = asset::total_balance::<T>(&11) - init_balance_11;
+		let payout_21 = asset::total_balance::<T>(&21) - init_balance_21;
+
+		assert_eq_error_rate!(payout_11, Perbill::from_rational(20u32, 30u32) * validator_payout, 2);
+		assert_eq_error_rate!(payout_21, Perbill::from_rational(10u32, 30u32) * validator_payout, 2);
+		assert!(payout_11 > payout_21, "validator with more points must be paid more");
+	});
+}
+
 #[test]
 fn rewards_no_nominator_should_work() {
 	ExtBuilder::default().nominate(false).build_and_execute(|| {
@@ -17,6 +17,7 @@
 
 //! Fuzzing for staking pallet.
 
+use arbitrary::{Arbitrary, Unstructured};
 use honggfuzz::fuzz;
 
 use mock::Test;
@@ -30,9 +31,8 @@ use sp_core::offchain::{testing::TestOffchainExt, OffchainExt};
 
 mod mock;
 
-#[repr(u32)]
-#[allow(dead_code)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The three submission scenarios this harness exercises.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Arbitrary)]
 enum Mode {
 	/// Initial submission. This will be rather cheap.
 	InitialSubmission,
@@ -42,125 +42,172 @@ enum Mode {
 	WeakerSubmission,
 }
 
-pub fn new_test_ext(iterations: u32) -> sp_io::TestExternalities {
+/// A fully-decoded fuzzing scenario.
+///
+/// Replaces the old `(u32, u32, u32, u32, u32)` tuple, which derived its `Mode` via an `unsafe
+/// transmute` of the last integer and its counts via modulo reduction of the rest. Decoding a
+/// structured type instead lets honggfuzz's coverage feedback steer mutation toward interesting
+/// election topologies rather than blindly hashing five integers.
+#[derive(Debug, Clone, Arbitrary)]
+struct ElectionScenario {
+	num_validators: u32,
+	num_nominators: u32,
+	edge_per_voter: u32,
+	to_elect: u32,
+	mode: Mode,
+	seed: u32,
+}
+
+impl ElectionScenario {
+	const MIN_VALIDATORS: u32 = 50;
+	const MAX_VALIDATORS: u32 = 1000;
+	const MIN_NOMINATORS: u32 = 50;
+	const MAX_NOMINATORS: u32 = 2000;
+	const MIN_EDGES: u32 = 1;
+	const MAX_EDGES: u32 = 16;
+	const MIN_TO_ELECT: u32 = 20;
+
+	/// Clamp the raw `Arbitrary`-generated counts into the ranges the harness wants to exercise.
+	fn bounded(self) -> Self {
+		let clamp = |x: u32, lo: u32, hi: u32| lo + (x % (hi - lo + 1));
+		let num_validators = clamp(self.num_validators, Self::MIN_VALIDATORS, Self::MAX_VALIDATORS);
+		let num_nominators = clamp(self.num_nominators, Self::MIN_NOMINATORS, Self::MAX_NOMINATORS);
+		let edge_per_voter = clamp(self.edge_per_voter, Self::MIN_EDGES, Self::MAX_EDGES);
+		let to_elect = clamp(self.to_elect, Self::MIN_TO_ELECT, num_validators);
+		Self { num_validators, num_nominators, edge_per_voter, to_elect, ..self }
+	}
+}
+
+pub fn new_test_ext(seed: u32) -> sp_io::TestExternalities {
 	let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::default().build_storage::<mock::Test>().map(Into::into)
 		.expect("Failed to create test externalities.");
 
 	let (offchain, offchain_state) = TestOffchainExt::new();
 
-	let mut seed = [0u8; 32];
-	seed[0..4].copy_from_slice(&iterations.to_le_bytes());
-	offchain_state.write().seed = seed;
+	let mut raw_seed = [0u8; 32];
+	raw_seed[0..4].copy_from_slice(&seed.to_le_bytes());
+	offchain_state.write().seed = raw_seed;
 
 	ext.register_extension(OffchainExt::new(offchain));
 
 	ext
 }
 
-fn main() {
-	let to_range = |x: u32, a: u32, b: u32| {
-		let collapsed = x % b;
-		if collapsed >= a {
-			collapsed
-		} else {
-			collapsed + a
-		}
-	};
-	loop {
-		fuzz!(|data: (u32, u32, u32, u32, u32)| {
-			let (mut num_validators, mut num_nominators, mut edge_per_voter, mut to_elect, mode_u32) = data;
-			let mut ext = new_test_ext(5);
-			let mode: Mode = unsafe { std::mem::transmute(mode_u32) };
-			num_validators = to_range(num_validators, 50, 1000);
-			num_nominators = to_range(num_nominators, 50, 2000);
-			edge_per_voter = to_range(edge_per_voter, 1, 16);
-			to_elect = to_range(to_elect, 20, num_validators);
-			let do_reduce = true;
-
-			println!("+++ instance with params {} / {} / {} / {:?}({}) / {}",
-				num_nominators,
-				num_validators,
-				edge_per_voter,
-				mode,
-				mode_u32,
-				to_elect,
-			);
-
-			ext.execute_with(|| {
-				// initial setup
-				set_validator_count::<Test>(to_elect);
-				pallet_staking::testing_utils::init_active_era();
-				setup_chain_stakers::<Test>(
-					num_validators,
-					num_nominators,
-					edge_per_voter,
-				);
-				<pallet_staking::EraElectionStatus<Test>>::put(pallet_staking::ElectionStatus::Open(1));
-
-				println!("++ Chain setup done.");
-
-				// stuff to submit
-				let (winners, compact, score) = match mode {
-					Mode::InitialSubmission => {
-						/* No need to setup anything */
-						get_seq_phragmen_solution::<Test>(do_reduce)
-					},
-					Mode::StrongerSubmission => {
-						let (winners, compact, score) = get_weak_solution::<Test>(false);
-						println!("Weak on chain score = {:?}", score);
-						assert_ok!(
-							<pallet_staking::Module<Test>>::submit_election_solution(
-								signed_account::<Test>(USER),
-								winners,
-								compact,
-								score,
-								pallet_staking::testing_utils::active_era::<Test>(),
-							)
-						);
-						get_seq_phragmen_solution::<Test>(do_reduce)
-					},
-					Mode::WeakerSubmission => {
-						let (winners, compact, score) = get_seq_phragmen_solution::<Test>(do_reduce);
-						println!("Strong on chain score = {:?}", score);
-						assert_ok!(
-							<pallet_staking::Module<Test>>::submit_election_solution(
-								signed_account::<Test>(USER),
-								winners,
-								compact,
-								score,
-								pallet_staking::testing_utils::active_era::<Test>(),
-							)
-						);
-						get_weak_solution::<Test>(false)
-					}
-				};
-
-				println!("++ Submission ready. Score = {:?}", score);
-
-				// must have chosen correct number of winners.
-				assert_eq!(winners.len() as u32, <pallet_staking::Module<Test>>::validator_count());
-
-				// final call and origin
-				let call = pallet_staking::Call::<Test>::submit_election_solution(
+/// Run a single decoded scenario against `new_test_ext`. Shared by the honggfuzz loop in
+/// [`main`] and the deterministic reproducer in [`reproduce`].
+fn run_scenario(scenario: ElectionScenario) {
+	let ElectionScenario { num_validators, num_nominators, edge_per_voter, to_elect, mode, seed } =
+		scenario.bounded();
+	let mut ext = new_test_ext(seed);
+	let do_reduce = true;
+
+	println!(
+		"+++ instance with params {} / {} / {} / {:?} / {}",
+		num_nominators, num_validators, edge_per_voter, mode, to_elect,
+	);
+
+	ext.execute_with(|| {
+		// initial setup
+		set_validator_count::<Test>(to_elect);
+		pallet_staking::testing_utils::init_active_era();
+		setup_chain_stakers::<Test>(num_validators, num_nominators, edge_per_voter);
+		<pallet_staking::EraElectionStatus<Test>>::put(pallet_staking::ElectionStatus::Open(1));
+
+		println!("++ Chain setup done.");
+
+		// stuff to submit
+		let (winners, compact, score) = match mode {
+			Mode::InitialSubmission => {
+				/* No need to setup anything */
+				get_seq_phragmen_solution::<Test>(do_reduce)
+			},
+			Mode::StrongerSubmission => {
+				let (winners, compact, score) = get_weak_solution::<Test>(false);
+				println!("Weak on chain score = {:?}", score);
+				assert_ok!(<pallet_staking::Module<Test>>::submit_election_solution(
+					signed_account::<Test>(USER),
 					winners,
 					compact,
 					score,
 					pallet_staking::testing_utils::active_era::<Test>(),
-				);
-				let caller = signed_account::<Test>(USER);
-
-				// actually submit
-				match mode {
-					Mode::WeakerSubmission => {
-						assert_eq!(
-							call.dispatch(caller.into()).unwrap_err().error,
-							DispatchError::Module { index: 0, error: 16, message: Some("PhragmenWeakSubmission") },
-						);
+				));
+				get_seq_phragmen_solution::<Test>(do_reduce)
+			},
+			Mode::WeakerSubmission => {
+				let (winners, compact, score) = get_seq_phragmen_solution::<Test>(do_reduce);
+				println!("Strong on chain score = {:?}", score);
+				assert_ok!(<pallet_staking::Module<Test>>::submit_election_solution(
+					signed_account::<Test>(USER),
+					winners,
+					compact,
+					score,
+					pallet_staking::testing_utils::active_era::<Test>(),
+				));
+				get_weak_solution::<Test>(false)
+			},
+		};
+
+		println!("++ Submission ready. Score = {:?}", score);
+
+		// must have chosen correct number of winners.
+		assert_eq!(winners.len() as u32, <pallet_staking::Module<Test>>::validator_count());
+
+		// final call and origin
+		let call = pallet_staking::Call::<Test>::submit_election_solution(
+			winners,
+			compact,
+			score,
+			pallet_staking::testing_utils::active_era::<Test>(),
+		);
+		let caller = signed_account::<Test>(USER);
+
+		// actually submit
+		match mode {
+			Mode::WeakerSubmission => {
+				assert_eq!(
+					call.dispatch(caller.into()).unwrap_err().error,
+					DispatchError::Module {
+						index: 0,
+						error: 16,
+						message: Some("PhragmenWeakSubmission")
 					},
-					// NOTE: so exhaustive pattern doesn't work here.. maybe some rust issue? or due to `#[repr(u32)]`?
-					Mode::InitialSubmission | Mode::StrongerSubmission => assert!(call.dispatch(caller.into()).is_ok()),
-				};
-			})
+				);
+			},
+			Mode::InitialSubmission | Mode::StrongerSubmission =>
+				assert!(call.dispatch(caller.into()).is_ok()),
+		};
+	})
+}
+
+/// Replay a saved honggfuzz corpus input deterministically, for debugging a crash outside of
+/// the fuzzer's own loop.
+///
+/// Usage: `submit_solution repro <path-to-corpus-file>`.
+fn reproduce(path: &str) {
+	let data = std::fs::read(path).expect("failed to read corpus file");
+	let mut unstructured = Unstructured::new(&data);
+	let scenario =
+		ElectionScenario::arbitrary(&mut unstructured).expect("failed to decode corpus input");
+	println!("+++ replaying {:?}", scenario);
+	run_scenario(scenario);
+}
+
+fn main() {
+	let mut args = std::env::args().skip(1);
+	if let (Some(mode), Some(path)) = (args.next(), args.next()) {
+		if mode == "repro" {
+			return reproduce(&path);
+		}
+	}
+
+	loop {
+		fuzz!(|data: &[u8]| {
+			let mut unstructured = Unstructured::new(data);
+			let scenario = match ElectionScenario::arbitrary(&mut unstructured) {
+				Ok(scenario) => scenario,
+				Err(_) => return,
+			};
+			run_scenario(scenario);
 		});
 	}
 }
@@ -126,23 +126,49 @@ impl ToTokens for DynamicParamModAttr {
 
 /// Ensure there is a `#[codec(index = ..)]` attribute.
 fn ensure_codec_index(attrs: &Vec<syn::Attribute>, span: Span) -> Result<()> {
-	let mut found = false;
+	codec_index(attrs, span).map(|_| ())
+}
 
+/// Find the `#[codec(index = ..)]` attribute and parse its value.
+fn codec_index(attrs: &Vec<syn::Attribute>, span: Span) -> Result<u8> {
 	for attr in attrs.iter() {
 		if attr.path().is_ident("codec") {
 			let meta: syn::ExprAssign = attr.parse_args()?;
 			if meta.left.to_token_stream().to_string() == "index" {
-				found = true;
-				break
+				return match &*meta.right {
+					syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(lit), .. }) =>
+						lit.base10_parse::<u8>(),
+					_ => Err(syn::Error::new(span, "`codec(index = ..)` must be an integer literal")),
+				}
 			}
 		}
 	}
 
-	if !found {
-		Err(syn::Error::new(span, "Missing explicit `#[codec(index = ..)]` attribute"))
-	} else {
-		Ok(())
+	Err(syn::Error::new(span, "Missing explicit `#[codec(index = ..)]` attribute"))
+}
+
+/// Find an optional `#[validate(<closure>)]` attribute and parse its closure expression.
+///
+/// The closure takes `&Self::Value` and returns whether the value is legal for this key.
+fn validator_expr(attrs: &[syn::Attribute]) -> Result<Option<syn::ExprClosure>> {
+	for attr in attrs.iter() {
+		if attr.path().is_ident("validate") {
+			return Ok(Some(attr.parse_args()?))
+		}
+	}
+
+	Ok(None)
+}
+
+/// Find an optional `#[migrate(<old type>)]` attribute and parse the prior type it names.
+fn migrate_from(attrs: &[syn::Attribute]) -> Result<Option<syn::Type>> {
+	for attr in attrs.iter() {
+		if attr.path().is_ident("migrate") {
+			return Ok(Some(attr.parse_args()?))
+		}
 	}
+
+	Ok(None)
 }
 
 /// Used to inject arguments into the inner `#[dynamic_pallet_params(..)]` attribute.
@@ -227,7 +253,7 @@ impl ToTokens for DynamicPalletParamAttr {
 			params_mod.ident.span(),
 		);
 		let (mod_name, vis) = (&params_mod.ident, &params_mod.vis);
-		let statics = self.statics();
+		let mut statics = self.statics();
 
 		let (mut key_names, mut key_values, mut defaults, mut attrs, mut value_types): (
 			Vec<_>,
@@ -236,13 +262,46 @@ impl ToTokens for DynamicPalletParamAttr {
 			Vec<_>,
 			Vec<_>,
 		) = Default::default();
+		let mut indices: Vec<u8> = Vec::new();
+		let mut validators: Vec<TokenStream> = Vec::new();
+		let mut old_types: Vec<Option<syn::Type>> = Vec::new();
+
+		for s in statics.iter_mut() {
+			let index = match codec_index(&s.attrs, s.span()) {
+				Ok(index) => index,
+				Err(err) => {
+					tokens.extend(err.into_compile_error());
+					return
+				},
+			};
+			let validator = match validator_expr(&s.attrs) {
+				Ok(validator) => validator,
+				Err(err) => {
+					tokens.extend(err.into_compile_error());
+					return
+				},
+			};
+			let old_type = match migrate_from(&s.attrs) {
+				Ok(old_type) => old_type,
+				Err(err) => {
+					tokens.extend(err.into_compile_error());
+					return
+				},
+			};
+			// The `#[validate(..)]` and `#[migrate(..)]` attributes are our own and must not be
+			// re-emitted onto the generated enum variant, unlike `#[codec(index = ..)]` which
+			// SCALE needs to see.
+			s.attrs.retain(|attr| !attr.path().is_ident("validate") && !attr.path().is_ident("migrate"));
+
+			indices.push(index);
+			validators.push(match validator {
+				Some(validator) => quote! { (#validator)(value) },
+				None => quote! { true },
+			});
+			old_types.push(old_type);
+		}
 
 		for s in statics.iter() {
-			if let Err(err) = ensure_codec_index(&s.attrs, s.span()) {
-				tokens.extend(err.into_compile_error());
-				return
-			}
-
 			key_names.push(&s.ident);
 			key_values.push(format_ident!("{}Value", &s.ident));
 			defaults.push(&s.expr);
@@ -250,6 +309,94 @@ impl ToTokens for DynamicPalletParamAttr {
 			value_types.push(&s.ty);
 		}
 
+		// The type actually stored on the wire for each key: either the key's own value type, or
+		// a `VersionedValue`-style wrapper when the key declared `#[migrate(<old type>)]`. Keeping
+		// this separate from `value_types` lets every other generated item (`Key::Value`,
+		// defaults, `Get::get`'s return type) keep referring to the current, logical type.
+		let versioned_idents: Vec<Option<syn::Ident>> = key_names
+			.iter()
+			.zip(old_types.iter())
+			.map(|(name, old)| old.as_ref().map(|_| format_ident!("{}VersionedValue", name)))
+			.collect();
+		let wire_types: Vec<TokenStream> = versioned_idents
+			.iter()
+			.zip(value_types.iter())
+			.map(|(versioned, value_type)| match versioned {
+				Some(versioned) => quote! { #versioned },
+				None => quote! { #value_type },
+			})
+			.collect();
+		// Expression upgrading a wire-typed `value` binding to the current, logical value type.
+		let from_wire: Vec<TokenStream> = versioned_idents
+			.iter()
+			.map(|versioned| match versioned {
+				Some(_) => quote! { value.migrate() },
+				None => quote! { value },
+			})
+			.collect();
+		// Expression upgrading a `&wire type` binding (as bound by a `match &Self::Value`) to a
+		// `&current value type`, for `validate_value`'s dispatch into `Key::validate`.
+		let from_wire_ref: Vec<TokenStream> = versioned_idents
+			.iter()
+			.map(|versioned| match versioned {
+				Some(_) => quote! { &value.clone().migrate() },
+				None => quote! { value },
+			})
+			.collect();
+		// Expression wrapping a current, logical `value.0` binding into its wire type.
+		let into_wire: Vec<TokenStream> = versioned_idents
+			.iter()
+			.map(|versioned| match versioned {
+				Some(versioned) => quote! { #versioned::Current(value.0) },
+				None => quote! { value.0 },
+			})
+			.collect();
+		// The `VersionedValue` wrapper enum and its `migrate` method, for keys that opted in.
+		let versioned_defs: Vec<TokenStream> = versioned_idents
+			.iter()
+			.zip(old_types.iter())
+			.zip(value_types.iter())
+			.map(|((versioned, old_type), value_type)| match (versioned, old_type) {
+				(Some(versioned), Some(old_type)) => quote! {
+					/// The on-chain encodings this key's value has ever been stored under.
+					///
+					/// Decoding always succeeds for a value written by a prior runtime; `migrate`
+					/// upgrades it to the current representation.
+					#[doc(hidden)]
+					#[derive(
+						Clone,
+						PartialEq,
+						Eq,
+						#scrate::__private::codec::Encode,
+						#scrate::__private::codec::Decode,
+						#scrate::__private::codec::MaxEncodedLen,
+						#scrate::__private::RuntimeDebug,
+						#scrate::__private::scale_info::TypeInfo
+					)]
+					#vis enum #versioned {
+						/// The value as it was encoded before this key's last type change.
+						#[codec(index = 0)]
+						V0(#old_type),
+						/// The value as encoded by the current runtime.
+						#[codec(index = 1)]
+						Current(#value_type),
+					}
+
+					impl #versioned {
+						/// Upgrade a stored value of any known prior version to the current
+						/// representation.
+						fn migrate(self) -> #value_type {
+							match self {
+								#versioned::V0(old) => old.into(),
+								#versioned::Current(value) => value,
+							}
+						}
+					}
+				},
+				_ => quote! {},
+			})
+			.collect();
+
 		let key_ident = syn::Ident::new("ParametersKey", params_mod.ident.span());
 		let value_ident = syn::Ident::new("ParametersValue", params_mod.ident.span());
 		let runtime_key_ident = format_ident!("{}Key", runtime_params);
@@ -309,7 +456,7 @@ impl ToTokens for DynamicPalletParamAttr {
 				#vis enum #value_ident {
 					#(
 						#(#attrs)*
-						#key_names(#value_types),
+						#key_names(#wire_types),
 					)*
 				}
 
@@ -326,9 +473,42 @@ impl ToTokens for DynamicPalletParamAttr {
 							)*
 						}
 					}
+
+					fn key_variants() -> #scrate::__private::Vec<#scrate::traits::dynamic_params::ParameterMeta> {
+						#scrate::__private::vec![
+							#(
+								#scrate::traits::dynamic_params::ParameterMeta {
+									index: #indices,
+									pallet: "",
+									name: stringify!(#key_names),
+									key_type: #scrate::__private::scale_info::meta_type::<#key_names>(),
+									value_type: #scrate::__private::scale_info::meta_type::<#value_types>(),
+								},
+							)*
+						]
+					}
+
+					fn validate_value(value: &Self::Value) -> bool {
+						match value {
+							#(
+								#value_ident::#key_names(value) =>
+									<#key_names as #scrate::traits::dynamic_params::Key>::validate(
+										#from_wire_ref,
+									),
+							)*
+						}
+					}
+
+					fn all_keys() -> #scrate::__private::Vec<Self::Key> {
+						#scrate::__private::vec![
+							#( #key_ident::#key_names(#key_names), )*
+						]
+					}
 				}
 
 				#(
+					#versioned_defs
+
 					#[doc(hidden)]
 					#[derive(
 						Clone,
@@ -350,7 +530,7 @@ impl ToTokens for DynamicPalletParamAttr {
 								>::get(#runtime_key_ident::#aggregate_name(#key_ident::#key_names(#key_names)))
 							{
 								Some(#runtime_value_ident::#aggregate_name(
-									#value_ident::#key_names(inner))) => inner,
+									#value_ident::#key_names(value))) => #from_wire,
 								Some(_) => {
 									#scrate::defensive!("Unexpected value type at key - returning default");
 									#defaults
@@ -363,6 +543,14 @@ impl ToTokens for DynamicPalletParamAttr {
 					impl #scrate::traits::dynamic_params::Key for #key_names {
 						type Value = #value_types;
 						type WrappedValue = #key_values;
+
+						fn default() -> Option<Self::Value> {
+							Some(#defaults)
+						}
+
+						fn validate(value: &Self::Value) -> bool {
+							#validators
+						}
 					}
 
 					impl From<#key_names> for #key_ident {
@@ -393,7 +581,7 @@ impl ToTokens for DynamicPalletParamAttr {
 
 					impl From<#key_values> for #value_ident {
 						fn from(value: #key_values) -> Self {
-							#value_ident::#key_names(value.0)
+							#value_ident::#key_names(#into_wire)
 						}
 					}
 
@@ -414,7 +602,7 @@ impl ToTokens for DynamicPalletParamAttr {
 
 						fn try_from(value: #value_ident) -> Result<Self, Self::Error> {
 							match value {
-								#value_ident::#key_names(value) => Ok(#key_values(value)),
+								#value_ident::#key_names(value) => Ok(#key_values(#from_wire)),
 								_ => Err(()),
 							}
 						}
@@ -535,6 +723,42 @@ impl ToTokens for DynamicParamAggregatedEnum {
 						)*
 					}
 				}
+
+				fn key_variants() -> #scrate::__private::Vec<#scrate::traits::dynamic_params::ParameterMeta> {
+					let mut variants = #scrate::__private::Vec::new();
+					#(
+						variants.extend(
+							<#param_types as #scrate::traits::dynamic_params::AggregatedKeyValue>::key_variants()
+								.into_iter()
+								.map(|meta| #scrate::traits::dynamic_params::ParameterMeta {
+									pallet: stringify!(#param_names),
+									..meta
+								}),
+						);
+					)*
+					variants
+				}
+
+				fn all_keys() -> #scrate::__private::Vec<Self::Key> {
+					let mut keys = #scrate::__private::Vec::new();
+					#(
+						keys.extend(
+							<#param_types as #scrate::traits::dynamic_params::AggregatedKeyValue>::all_keys()
+								.into_iter()
+								.map(#params_key_ident::#param_names),
+						);
+					)*
+					keys
+				}
+
+				fn validate_value(value: &Self::Value) -> bool {
+					match value {
+						#(
+							#params_value_ident::#param_names(value) =>
+								<#param_types as #scrate::traits::dynamic_params::AggregatedKeyValue>::validate_value(value),
+						)*
+					}
+				}
 			}
 
 			#(
@@ -897,8 +897,10 @@ pub mod pallet_prelude {
 			bounded_btree_set::BoundedBTreeSet,
 			bounded_vec::BoundedVec,
 			types::{
-				CountedStorageMap, CountedStorageNMap, Key as NMapKey, OptionQuery, ResultQuery,
-				StorageDoubleMap, StorageMap, StorageNMap, StorageValue, ValueQuery,
+				CheckedStorageDoubleMapInstance, CountedStorageDoubleMap, CountedStorageMap,
+				CountedStorageNMap, Key as NMapKey, OptionQuery, ResultQuery, StorageCursor,
+				StorageDoubleMap, StorageMap, StorageNMap, StorageValue, TranslateError,
+				TranslateStage, TypeHash, TypeMismatch, ValueQuery,
 			},
 			weak_bounded_vec::WeakBoundedVec,
 			StorageList,
@@ -2303,6 +2305,7 @@ pub mod pallet_macros {
 	/// * [`StorageMap`](crate::storage::types::StorageMap)
 	/// * [`CountedStorageMap`](crate::storage::types::CountedStorageMap)
 	/// * [`StorageDoubleMap`](crate::storage::types::StorageDoubleMap)
+	/// * [`CountedStorageDoubleMap`](crate::storage::types::CountedStorageDoubleMap)
 	/// * [`StorageNMap`](crate::storage::types::StorageNMap)
 	/// * [`CountedStorageNMap`](crate::storage::types::CountedStorageNMap)
 	///
@@ -0,0 +1,466 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage double map type that adds a contained `u32` counter to the `StorageDoubleMap`, making
+//! the `count()` operation O(1), mirroring the companion `CountedStorageMap`.
+
+use crate::{
+	storage::{
+		types::{
+			OptionQuery, QueryKindTrait, StorageDoubleMap, StorageEntryMetadataBuilder, ValueQuery,
+		},
+		StorageAppend, StorageDecodeLength, StorageTryAppend,
+	},
+	traits::{Get, GetDefault, StorageInfo, StorageInstance},
+	Never,
+};
+use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen};
+use sp_metadata_ir::StorageEntryMetadataIR;
+
+use alloc::vec::Vec;
+
+/// The prefix used to generate the key for the counter storage value.
+pub trait CountedStorageDoubleMapInstance: StorageInstance {
+	/// The prefix to use for the counter storage value.
+	type CounterPrefix: StorageInstance;
+}
+
+/// A wrapper around a `StorageDoubleMap` and a `u32` counter value, which can be incremented or
+/// decremented as entries are added or removed, in order to maintain an on-chain record of the
+/// number of entries in the map, so that the size of the map can be queried in O(1) without
+/// iterating over it.
+///
+/// For general information regarding the `#[pallet::storage]` attribute, refer to
+/// [`crate::pallet_macros::storage`].
+pub struct CountedStorageDoubleMap<
+	Prefix,
+	Hasher1,
+	Key1,
+	Hasher2,
+	Key2,
+	Value,
+	QueryKind = OptionQuery,
+	OnEmpty = GetDefault,
+	MaxValues = GetDefault,
+>(core::marker::PhantomData<(Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues)>);
+
+/// The requirement for the counter value. It is the same storage value type as used by
+/// `CountedStorageMap`: a simple `u32` `ValueQuery` storage value.
+type CounterFor<Prefix> = crate::storage::types::StorageValue<
+	<Prefix as CountedStorageDoubleMapInstance>::CounterPrefix,
+	u32,
+	ValueQuery,
+>;
+
+/// Inner (uncounted) storage double map backing a `CountedStorageDoubleMap`.
+type InnerMap<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues> =
+	StorageDoubleMap<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>;
+
+impl<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>
+	CountedStorageDoubleMap<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>
+where
+	Prefix: CountedStorageDoubleMapInstance,
+	Hasher1: crate::hash::StorageHasher,
+	Hasher2: crate::hash::StorageHasher,
+	Key1: FullCodec,
+	Key2: FullCodec,
+	Value: FullCodec,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	/// The number of entries currently stored in the map, read directly from the counter. O(1).
+	pub fn count() -> u32 {
+		CounterFor::<Prefix>::get()
+	}
+
+	/// Does the value (explicitly) exist in storage?
+	pub fn contains_key<KArg1, KArg2>(k1: KArg1, k2: KArg2) -> bool
+	where
+		KArg1: EncodeLike<Key1>,
+		KArg2: EncodeLike<Key2>,
+	{
+		InnerMap::<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>::contains_key(
+			k1, k2,
+		)
+	}
+
+	/// Load the value associated with the given key from the double map.
+	pub fn get<KArg1, KArg2>(k1: KArg1, k2: KArg2) -> QueryKind::Query
+	where
+		KArg1: EncodeLike<Key1>,
+		KArg2: EncodeLike<Key2>,
+	{
+		InnerMap::<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>::get(k1, k2)
+	}
+
+	/// Store a value and increment the counter if this is a new entry.
+	pub fn insert<KArg1, KArg2, VArg>(k1: KArg1, k2: KArg2, val: VArg)
+	where
+		KArg1: EncodeLike<Key1> + Clone,
+		KArg2: EncodeLike<Key2> + Clone,
+		VArg: EncodeLike<Value>,
+	{
+		if !Self::contains_key(k1.clone(), k2.clone()) {
+			CounterFor::<Prefix>::mutate(|c| *c = c.saturating_add(1));
+		}
+		InnerMap::<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>::insert(
+			k1, k2, val,
+		)
+	}
+
+	/// Remove the value under the given keys, decrementing the counter if a value was actually
+	/// removed.
+	pub fn remove<KArg1, KArg2>(k1: KArg1, k2: KArg2)
+	where
+		KArg1: EncodeLike<Key1> + Clone,
+		KArg2: EncodeLike<Key2> + Clone,
+	{
+		if Self::contains_key(k1.clone(), k2.clone()) {
+			CounterFor::<Prefix>::mutate(|c| *c = c.saturating_sub(1));
+		}
+		InnerMap::<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>::remove(
+			k1, k2,
+		)
+	}
+
+	/// Take a value from storage, removing it and decrementing the counter if it existed.
+	pub fn take<KArg1, KArg2>(k1: KArg1, k2: KArg2) -> QueryKind::Query
+	where
+		KArg1: EncodeLike<Key1> + Clone,
+		KArg2: EncodeLike<Key2> + Clone,
+	{
+		let existed = Self::contains_key(k1.clone(), k2.clone());
+		let result =
+			InnerMap::<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>::take(
+				k1, k2,
+			);
+		if existed {
+			CounterFor::<Prefix>::mutate(|c| *c = c.saturating_sub(1));
+		}
+		result
+	}
+
+	/// Swap the values of two key-pairs, adjusting the counter only if one side's existence
+	/// changes relative to the other.
+	pub fn swap<XKArg1, XKArg2, YKArg1, YKArg2>(
+		x_k1: XKArg1,
+		x_k2: XKArg2,
+		y_k1: YKArg1,
+		y_k2: YKArg2,
+	) where
+		XKArg1: EncodeLike<Key1> + Clone,
+		XKArg2: EncodeLike<Key2> + Clone,
+		YKArg1: EncodeLike<Key1> + Clone,
+		YKArg2: EncodeLike<Key2> + Clone,
+	{
+		let x_existed = Self::contains_key(x_k1.clone(), x_k2.clone());
+		let y_existed = Self::contains_key(y_k1.clone(), y_k2.clone());
+		InnerMap::<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>::swap(
+			x_k1, x_k2, y_k1, y_k2,
+		);
+		// A swap only changes the *set* of occupied keys if exactly one side existed beforehand.
+		if x_existed && !y_existed {
+			CounterFor::<Prefix>::mutate(|c| *c = c.saturating_sub(1));
+		} else if !x_existed && y_existed {
+			CounterFor::<Prefix>::mutate(|c| *c = c.saturating_add(1));
+		}
+	}
+
+	/// Mutate the value under the given keys. Existence transitions are detected by comparing
+	/// the query before and after the closure runs.
+	pub fn mutate<KArg1, KArg2, R, F>(k1: KArg1, k2: KArg2, f: F) -> R
+	where
+		KArg1: EncodeLike<Key1> + Clone,
+		KArg2: EncodeLike<Key2> + Clone,
+		F: FnOnce(&mut QueryKind::Query) -> R,
+	{
+		Self::try_mutate(k1, k2, |v| Ok::<R, Never>(f(v)))
+			.expect("`Never` can not be constructed; qed")
+	}
+
+	/// Mutate the value under the given keys when the closure returns `Ok`.
+	pub fn try_mutate<KArg1, KArg2, R, E, F>(k1: KArg1, k2: KArg2, f: F) -> Result<R, E>
+	where
+		KArg1: EncodeLike<Key1> + Clone,
+		KArg2: EncodeLike<Key2> + Clone,
+		F: FnOnce(&mut QueryKind::Query) -> Result<R, E>,
+	{
+		let existed = Self::contains_key(k1.clone(), k2.clone());
+		let result =
+			InnerMap::<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>::try_mutate(
+				k1.clone(),
+				k2.clone(),
+				f,
+			);
+		if result.is_ok() {
+			let exists = Self::contains_key(k1, k2);
+			if existed && !exists {
+				CounterFor::<Prefix>::mutate(|c| *c = c.saturating_sub(1));
+			} else if !existed && exists {
+				CounterFor::<Prefix>::mutate(|c| *c = c.saturating_add(1));
+			}
+		}
+		result
+	}
+
+	/// Mutate the value under the given keys. Deletes the item (and decrements the counter) if
+	/// mutated to `None`; increments it if the item transitions from absent to present.
+	pub fn mutate_exists<KArg1, KArg2, R, F>(k1: KArg1, k2: KArg2, f: F) -> R
+	where
+		KArg1: EncodeLike<Key1> + Clone,
+		KArg2: EncodeLike<Key2> + Clone,
+		F: FnOnce(&mut Option<Value>) -> R,
+	{
+		Self::try_mutate_exists(k1, k2, |v| Ok::<R, Never>(f(v)))
+			.expect("`Never` can not be constructed; qed")
+	}
+
+	/// Mutate the item, only if an `Ok` value is returned. Deletes the item if mutated to `None`.
+	/// The counter is updated only when the before/after existence of the value differs - this is
+	/// the key invariant for keeping `count()` accurate.
+	pub fn try_mutate_exists<KArg1, KArg2, R, E, F>(k1: KArg1, k2: KArg2, f: F) -> Result<R, E>
+	where
+		KArg1: EncodeLike<Key1> + Clone,
+		KArg2: EncodeLike<Key2> + Clone,
+		F: FnOnce(&mut Option<Value>) -> Result<R, E>,
+	{
+		let existed = Self::contains_key(k1.clone(), k2.clone());
+		let result = InnerMap::<
+			Prefix,
+			Hasher1,
+			Key1,
+			Hasher2,
+			Key2,
+			Value,
+			QueryKind,
+			OnEmpty,
+			MaxValues,
+		>::try_mutate_exists(k1.clone(), k2.clone(), f);
+		if result.is_ok() {
+			let exists = Self::contains_key(k1, k2);
+			if existed && !exists {
+				CounterFor::<Prefix>::mutate(|c| *c = c.saturating_sub(1));
+			} else if !existed && exists {
+				CounterFor::<Prefix>::mutate(|c| *c = c.saturating_add(1));
+			}
+		}
+		result
+	}
+
+	/// Append the given item to the value in storage (`Value` must implement [`StorageAppend`]).
+	/// Appending to a missing entry creates it and increments the counter.
+	pub fn append<Item, EncodeLikeItem, KArg1, KArg2>(k1: KArg1, k2: KArg2, item: EncodeLikeItem)
+	where
+		KArg1: EncodeLike<Key1> + Clone,
+		KArg2: EncodeLike<Key2> + Clone,
+		Item: Encode,
+		EncodeLikeItem: EncodeLike<Item>,
+		Value: StorageAppend<Item>,
+	{
+		if !Self::contains_key(k1.clone(), k2.clone()) {
+			CounterFor::<Prefix>::mutate(|c| *c = c.saturating_add(1));
+		}
+		InnerMap::<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>::append(
+			k1, k2, item,
+		)
+	}
+
+	/// Read the length of the storage value without decoding the entire value.
+	pub fn decode_len<KArg1, KArg2>(key1: KArg1, key2: KArg2) -> Option<usize>
+	where
+		KArg1: EncodeLike<Key1>,
+		KArg2: EncodeLike<Key2>,
+		Value: StorageDecodeLength,
+	{
+		InnerMap::<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>::decode_len(
+			key1, key2,
+		)
+	}
+
+	/// Try and append the given item to the value in storage. Appending to a missing entry creates
+	/// it and increments the counter.
+	pub fn try_append<KArg1, KArg2, Item, EncodeLikeItem>(
+		key1: KArg1,
+		key2: KArg2,
+		item: EncodeLikeItem,
+	) -> Result<(), ()>
+	where
+		KArg1: EncodeLike<Key1> + Clone,
+		KArg2: EncodeLike<Key2> + Clone,
+		Item: Encode,
+		EncodeLikeItem: EncodeLike<Item>,
+		Value: StorageTryAppend<Item>,
+	{
+		let existed = Self::contains_key(key1.clone(), key2.clone());
+		let result = InnerMap::<
+			Prefix,
+			Hasher1,
+			Key1,
+			Hasher2,
+			Key2,
+			Value,
+			QueryKind,
+			OnEmpty,
+			MaxValues,
+		>::try_append(key1, key2, item);
+		if result.is_ok() && !existed {
+			CounterFor::<Prefix>::mutate(|c| *c = c.saturating_add(1));
+		}
+		result
+	}
+
+	/// Remove all values under `k1` in the overlay and up to `limit` in the backend, decrementing
+	/// the counter by the number of unique keys actually removed.
+	pub fn clear_prefix<KArg1>(
+		k1: KArg1,
+		limit: u32,
+		maybe_cursor: Option<&[u8]>,
+	) -> sp_io::MultiRemovalResults
+	where
+		KArg1: EncodeLike<Key1>,
+	{
+		let result = InnerMap::<
+			Prefix,
+			Hasher1,
+			Key1,
+			Hasher2,
+			Key2,
+			Value,
+			QueryKind,
+			OnEmpty,
+			MaxValues,
+		>::clear_prefix(k1, limit, maybe_cursor);
+		CounterFor::<Prefix>::mutate(|c| *c = c.saturating_sub(result.unique as u32));
+		result
+	}
+
+	/// Remove all values from the map and iterate through them in no particular order,
+	/// decrementing the counter for every item yielded.
+	pub fn drain() -> impl Iterator<Item = (Key1, Key2, Value)>
+	where
+		Hasher1: crate::ReversibleStorageHasher,
+		Hasher2: crate::ReversibleStorageHasher,
+	{
+		InnerMap::<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>::drain()
+			.inspect(|_| CounterFor::<Prefix>::mutate(|c| *c = c.saturating_sub(1)))
+	}
+
+	/// Remove all values in the overlay and up to `limit` in the backend. Once `maybe_cursor`
+	/// is `None` the whole map - and thus the counter - has been cleared, so the counter is reset
+	/// to zero at that point rather than decremented entry-by-entry.
+	pub fn clear(limit: u32, maybe_cursor: Option<&[u8]>) -> sp_io::MultiRemovalResults {
+		let result = InnerMap::<
+			Prefix,
+			Hasher1,
+			Key1,
+			Hasher2,
+			Key2,
+			Value,
+			QueryKind,
+			OnEmpty,
+			MaxValues,
+		>::clear(limit, maybe_cursor);
+		if result.maybe_cursor.is_none() {
+			CounterFor::<Prefix>::kill();
+		}
+		result
+	}
+
+	/// Enumerate all elements in the map in no particular order.
+	pub fn iter() -> impl Iterator<Item = (Key1, Key2, Value)>
+	where
+		Hasher1: crate::ReversibleStorageHasher,
+		Hasher2: crate::ReversibleStorageHasher,
+	{
+		InnerMap::<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>::iter()
+	}
+}
+
+impl<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>
+	StorageEntryMetadataBuilder
+	for CountedStorageDoubleMap<
+		Prefix,
+		Hasher1,
+		Key1,
+		Hasher2,
+		Key2,
+		Value,
+		QueryKind,
+		OnEmpty,
+		MaxValues,
+	> where
+	Prefix: CountedStorageDoubleMapInstance,
+	Hasher1: crate::hash::StorageHasher,
+	Hasher2: crate::hash::StorageHasher,
+	Key1: FullCodec + scale_info::StaticTypeInfo,
+	Key2: FullCodec + scale_info::StaticTypeInfo,
+	Value: FullCodec + scale_info::StaticTypeInfo,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	fn build_metadata(docs: Vec<&'static str>, entries: &mut Vec<StorageEntryMetadataIR>) {
+		InnerMap::<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>::build_metadata(
+			docs, entries,
+		);
+		CounterFor::<Prefix>::build_metadata(
+			alloc::vec!["Counter for the related counted storage map"],
+			entries,
+		);
+	}
+}
+
+impl<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>
+	crate::traits::StorageInfoTrait
+	for CountedStorageDoubleMap<
+		Prefix,
+		Hasher1,
+		Key1,
+		Hasher2,
+		Key2,
+		Value,
+		QueryKind,
+		OnEmpty,
+		MaxValues,
+	> where
+	Prefix: CountedStorageDoubleMapInstance,
+	Hasher1: crate::hash::StorageHasher,
+	Hasher2: crate::hash::StorageHasher,
+	Key1: FullCodec + MaxEncodedLen,
+	Key2: FullCodec + MaxEncodedLen,
+	Value: FullCodec + MaxEncodedLen,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	fn storage_info() -> Vec<StorageInfo> {
+		let mut info = InnerMap::<
+			Prefix,
+			Hasher1,
+			Key1,
+			Hasher2,
+			Key2,
+			Value,
+			QueryKind,
+			OnEmpty,
+			MaxValues,
+		>::storage_info();
+		info.extend(CounterFor::<Prefix>::storage_info());
+		info
+	}
+}
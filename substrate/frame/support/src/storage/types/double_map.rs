@@ -35,6 +35,21 @@ use frame_support::storage::StorageDecodeNonDedupLength;
 use sp_arithmetic::traits::SaturatedConversion;
 use sp_metadata_ir::{StorageEntryMetadataIR, StorageEntryTypeIR};
 
+/// Extends a double map's [`StorageInstance`] with a prefix under which a "last written value
+/// type" fingerprint can be recorded, enabling [`StorageDoubleMap::translate_checked`].
+pub trait CheckedStorageDoubleMapInstance: StorageInstance {
+	/// The prefix to use for the recorded type-hash storage value.
+	type TypeHashPrefix: StorageInstance;
+}
+
+/// The storage value backing [`StorageDoubleMap::record_type_hash`]/
+/// [`StorageDoubleMap::translate_checked`].
+type TypeHashFor<Prefix> = crate::storage::types::StorageValue<
+	<Prefix as CheckedStorageDoubleMapInstance>::TypeHashPrefix,
+	super::TypeHash,
+	OptionQuery,
+>;
+
 /// A type representing a *double map* in storage. This structure associates a pair of keys with a
 /// value of a specified type stored on-chain.
 ///
@@ -1208,6 +1223,392 @@ where
 	pub fn translate<O: Decode, F: FnMut(Key1, Key2, O) -> Option<Value>>(f: F) {
 		<Self as crate::storage::IterableStorageDoubleMap<Key1, Key2, Value>>::translate(f)
 	}
+
+	/// Collect up to `max_items` entries of the whole map, resuming strictly after `cursor`'s key
+	/// if given, and return them alongside a [`super::StorageCursor`] to resume a later call - or
+	/// `None` once the map is exhausted.
+	///
+	/// See [`StorageMap::paged_iter`](super::StorageMap::paged_iter) for the rationale; a cursor
+	/// produced against a different map or a different `paged_iter_prefix` scope is rejected.
+	pub fn paged_iter(
+		max_items: u32,
+		cursor: Option<&super::StorageCursor>,
+	) -> Result<(Vec<(Key1, Key2, Value)>, Option<super::StorageCursor>), ()> {
+		let prefix = Self::prefix_hash().to_vec();
+		let mut iter = match cursor {
+			Some(cursor) => Self::iter_from(cursor.raw_key_for(&prefix)?.to_vec()),
+			None => Self::iter(),
+		};
+
+		let items: Vec<_> = (&mut iter).take(max_items as usize).collect();
+		let has_more = sp_io::storage::next_key(&iter.previous_key)
+			.map_or(false, |next| next.starts_with(&prefix));
+		let maybe_cursor = if items.len() == max_items as usize && has_more {
+			Some(super::StorageCursor::new(prefix, iter.previous_key.clone()))
+		} else {
+			None
+		};
+		Ok((items, maybe_cursor))
+	}
+
+	/// Record the [`super::TypeHash`] fingerprint of `Hashes` as "the type this storage item was last
+	/// written with", under `Prefix::TypeHashPrefix`.
+	///
+	/// Call this from the writing side whenever the value type changes (typically right after a
+	/// storage migration finishes translating every entry to the new type), so a later migration
+	/// guarded by [`Self::translate_checked`] can detect a wrong `OldV` assumption instead of
+	/// silently mis-decoding.
+	pub fn record_type_hash<Hashes>()
+	where
+		Prefix: CheckedStorageDoubleMapInstance,
+		Hashes: scale_info::TypeInfo + 'static,
+	{
+		TypeHashFor::<Prefix>::put(super::type_hash::<Hashes>())
+	}
+
+	/// Same as [`Self::translate`], but first compares the `TypeHash` fingerprint recorded by
+	/// [`Self::record_type_hash`] (if any) against `super::type_hash::<OldV>()`, refusing to run
+	/// `f` at all when they differ.
+	///
+	/// If no fingerprint has ever been recorded, this proceeds as `translate` would - there is
+	/// nothing to contradict the caller's `OldV` assumption yet.
+	pub fn translate_checked<OldV: Decode + scale_info::TypeInfo + 'static, F>(
+		f: F,
+	) -> Result<(), super::TypeMismatch>
+	where
+		Prefix: CheckedStorageDoubleMapInstance,
+		F: FnMut(Key1, Key2, OldV) -> Option<Value>,
+	{
+		let expected = super::type_hash::<OldV>();
+		if let Some(recorded) = TypeHashFor::<Prefix>::get() {
+			if recorded != expected {
+				return Err(super::TypeMismatch { expected, recorded: Some(recorded) })
+			}
+		}
+		Self::translate(f);
+		Ok(())
+	}
+
+	/// Enumerate all elements in the map with first key `k1`, in reverse raw-hashed-key order.
+	///
+	/// Useful for "show the N most recent entries" or bounded tail-cleanup without loading the
+	/// whole prefix into memory, e.g. `Map::rev_iter_prefix(k1).take(n)`.
+	///
+	/// # Warning
+	///
+	/// Because the backend only exposes a forward `next_key` primitive, finding the predecessor
+	/// of the current position requires re-walking forward from the start of the prefix each
+	/// time, i.e. this performs one extra full forward seek per yielded item. It is intended for
+	/// bounded (`.take(n)`) consumption, not for reversing an entire large prefix.
+	pub fn rev_iter_prefix(k1: impl EncodeLike<Key1>) -> RevPrefixDoubleMapIterator<Key2, Value> {
+		let prefix = Self::storage_double_map_final_key1(k1);
+		RevPrefixDoubleMapIterator {
+			upper_bound: exclusive_successor(&prefix),
+			prefix,
+			closure: |raw_key_without_prefix, mut raw_value| {
+				let mut key_material = Hasher2::reverse(raw_key_without_prefix);
+				Ok((Key2::decode(&mut key_material)?, Value::decode(&mut raw_value)?))
+			},
+			phantom: Default::default(),
+		}
+	}
+
+	/// Same as [`Self::paged_iter`], but scoped to entries sharing the first key `k1`.
+	pub fn paged_iter_prefix(
+		k1: impl EncodeLike<Key1> + Clone,
+		max_items: u32,
+		cursor: Option<&super::StorageCursor>,
+	) -> Result<(Vec<(Key2, Value)>, Option<super::StorageCursor>), ()> {
+		let prefix = Self::storage_double_map_final_key1(k1.clone());
+		let mut iter = match cursor {
+			Some(cursor) => Self::iter_prefix_from(k1, cursor.raw_key_for(&prefix)?.to_vec()),
+			None => Self::iter_prefix(k1),
+		};
+
+		let items: Vec<_> = (&mut iter).take(max_items as usize).collect();
+		let has_more = sp_io::storage::next_key(&iter.previous_key)
+			.map_or(false, |next| next.starts_with(&prefix));
+		let maybe_cursor = if items.len() == max_items as usize && has_more {
+			Some(super::StorageCursor::new(prefix, iter.previous_key.clone()))
+		} else {
+			None
+		};
+		Ok((items, maybe_cursor))
+	}
+
+	/// Translate at most `limit` entries of the map starting at `maybe_cursor`, applying `f` to
+	/// each decoded `(Key1, Key2, O)` and either writing the returned value back or killing the
+	/// key when `f` returns `None`.
+	///
+	/// Returns a [`MultiRemovalResults`](sp_io::MultiRemovalResults)-style cursor holding the last
+	/// raw key processed, from which the next call resumes, or `None` once the whole map has been
+	/// visited. `maybe_cursor`
+	/// must be `None` on the first call for a given map, and `Some` (equal to the previous call's
+	/// returned cursor) on every subsequent call - the same contract as
+	/// [`Self::clear_prefix`]'s `limit`/`maybe_cursor` pair.
+	///
+	/// # Usage
+	///
+	/// This is meant to be called repeatedly across several blocks from `on_runtime_upgrade`, so
+	/// that rewriting every entry of a large map does not blow the block weight budget in one go.
+	///
+	/// # Warning
+	///
+	/// Because the value is rewritten in place, the raw key touched by this call is always used
+	/// as the resume cursor (rather than re-deriving a position from the new encoding), so a
+	/// value changing length between calls can never cause the next-key walk to skip or revisit
+	/// entries.
+	pub fn translate_bounded<O: Decode, F: FnMut(Key1, Key2, O) -> Option<Value>>(
+		limit: u32,
+		maybe_cursor: Option<&[u8]>,
+		mut f: F,
+	) -> Option<Vec<u8>>
+	where
+		Hasher1: crate::ReversibleStorageHasher,
+		Hasher2: crate::ReversibleStorageHasher,
+	{
+		let prefix = Self::prefix_hash().to_vec();
+		let mut previous_key = maybe_cursor.map(|c| c.to_vec()).unwrap_or_else(|| prefix.clone());
+		let mut processed = 0u32;
+
+		while processed < limit {
+			let next = match sp_io::storage::next_key(&previous_key).filter(|n| n.starts_with(&prefix))
+			{
+				Some(next) => next,
+				None => return None,
+			};
+			previous_key = next;
+			processed += 1;
+
+			let value = match unhashed::get::<O>(&previous_key) {
+				Some(value) => value,
+				None => {
+					log::error!("Invalid translate_bounded: fail to decode old value");
+					continue
+				},
+			};
+			let mut key_material = Hasher1::reverse(&previous_key[prefix.len()..]);
+			let key1 = match Key1::decode(&mut key_material) {
+				Ok(key1) => key1,
+				Err(_) => {
+					log::error!("Invalid translate_bounded: fail to decode key1");
+					continue
+				},
+			};
+			let mut key2_material = Hasher2::reverse(key_material);
+			let key2 = match Key2::decode(&mut key2_material) {
+				Ok(key2) => key2,
+				Err(_) => {
+					log::error!("Invalid translate_bounded: fail to decode key2");
+					continue
+				},
+			};
+
+			match f(key1, key2, value) {
+				Some(new) => unhashed::put::<Value>(&previous_key, &new),
+				None => unhashed::kill(&previous_key),
+			}
+		}
+
+		// Resume from the last key touched, but only if the map has entries after it.
+		sp_io::storage::next_key(&previous_key)
+			.filter(|n| n.starts_with(&prefix))
+			.map(|_| previous_key)
+	}
+
+	/// Translate the values of all elements by `f`, aborting on the first entry whose key or
+	/// value fails to decode rather than silently skipping it.
+	///
+	/// Returns the number of entries successfully translated before any failure, or a
+	/// [`TranslateError`] identifying the raw key and stage (key1/key2/value decode) of the first
+	/// undecodable entry. No further writes happen once an error is hit.
+	pub fn try_translate<O: Decode, F: FnMut(Key1, Key2, O) -> Option<Value>>(
+		mut f: F,
+	) -> Result<u64, super::TranslateError> {
+		let prefix = Self::prefix_hash().to_vec();
+		let mut previous_key = prefix.clone();
+		let mut translated = 0u64;
+
+		while let Some(next) =
+			sp_io::storage::next_key(&previous_key).filter(|n| n.starts_with(&prefix))
+		{
+			previous_key = next;
+
+			let mut key_material = Hasher1::reverse(&previous_key[prefix.len()..]);
+			let key1 = Key1::decode(&mut key_material).map_err(|_| super::TranslateError {
+				raw_key: previous_key.clone(),
+				stage: super::TranslateStage::Key1,
+			})?;
+			let mut key2_material = Hasher2::reverse(key_material);
+			let key2 = Key2::decode(&mut key2_material).map_err(|_| super::TranslateError {
+				raw_key: previous_key.clone(),
+				stage: super::TranslateStage::Key2,
+			})?;
+			let value = unhashed::get::<O>(&previous_key).ok_or_else(|| super::TranslateError {
+				raw_key: previous_key.clone(),
+				stage: super::TranslateStage::Value,
+			})?;
+
+			match f(key1, key2, value) {
+				Some(new) => unhashed::put::<Value>(&previous_key, &new),
+				None => unhashed::kill(&previous_key),
+			}
+			translated += 1;
+		}
+
+		Ok(translated)
+	}
+
+	/// Same as [`Self::try_translate`], but only the value is passed to `f`, leaving the keys
+	/// untouched.
+	pub fn try_translate_values<OldValue: Decode, F: FnMut(OldValue) -> Option<Value>>(
+		mut f: F,
+	) -> Result<u64, super::TranslateError> {
+		Self::try_translate(move |_k1, _k2, old: OldValue| f(old))
+	}
+
+	/// Translate the values of all elements by `f`, like [`Self::translate`], but never aborts:
+	/// any entry whose key or value fails to decode is skipped and its raw key recorded, so a
+	/// migration can log and decide what to do about the loss instead of it passing unnoticed.
+	///
+	/// Returns `(translated, skipped)` where `skipped` lists the raw backend keys that could not
+	/// be decoded.
+	pub fn translate_with_report<O: Decode, F: FnMut(Key1, Key2, O) -> Option<Value>>(
+		mut f: F,
+	) -> (u64, Vec<Vec<u8>>) {
+		let prefix = Self::prefix_hash().to_vec();
+		let mut previous_key = prefix.clone();
+		let mut translated = 0u64;
+		let mut skipped = Vec::new();
+
+		while let Some(next) =
+			sp_io::storage::next_key(&previous_key).filter(|n| n.starts_with(&prefix))
+		{
+			previous_key = next;
+
+			let decoded = (|| {
+				let mut key_material = Hasher1::reverse(&previous_key[prefix.len()..]);
+				let key1 = Key1::decode(&mut key_material).ok()?;
+				let mut key2_material = Hasher2::reverse(key_material);
+				let key2 = Key2::decode(&mut key2_material).ok()?;
+				let value = unhashed::get::<O>(&previous_key)?;
+				Some((key1, key2, value))
+			})();
+
+			match decoded {
+				Some((key1, key2, value)) => {
+					match f(key1, key2, value) {
+						Some(new) => unhashed::put::<Value>(&previous_key, &new),
+						None => unhashed::kill(&previous_key),
+					}
+					translated += 1;
+				},
+				None => skipped.push(previous_key.clone()),
+			}
+		}
+
+		(translated, skipped)
+	}
+
+	/// Same as [`Self::translate_bounded`], but operating over `OldValue` rewritten via a
+	/// values-only closure `F: FnMut(OldValue) -> Option<Value>`, keeping the keys untouched.
+	pub fn translate_values_bounded<OldValue: Decode, F: FnMut(OldValue) -> Option<Value>>(
+		limit: u32,
+		maybe_cursor: Option<&[u8]>,
+		mut f: F,
+	) -> Option<Vec<u8>>
+	where
+		Hasher1: crate::ReversibleStorageHasher,
+		Hasher2: crate::ReversibleStorageHasher,
+	{
+		Self::translate_bounded(limit, maybe_cursor, move |_k1, _k2, old: OldValue| f(old))
+	}
+
+	/// Remove and return up to `limit` entries of the whole map, resuming from `maybe_cursor`,
+	/// reporting progress via [`sp_io::MultiRemovalResults`].
+	///
+	/// Has the same `limit`/`maybe_cursor` contract as [`Self::clear_prefix`]: `maybe_cursor` must
+	/// be `None` on the first call and `Some` (the previous call's returned cursor) on every
+	/// subsequent call for the same map.
+	pub fn drain_bounded(
+		limit: u32,
+		maybe_cursor: Option<&[u8]>,
+	) -> (Vec<(Key1, Key2, Value)>, sp_io::MultiRemovalResults) {
+		Self::drain_prefix_range_bounded(Self::prefix_hash().to_vec(), limit, maybe_cursor)
+	}
+
+	/// Remove and return up to `limit` entries sharing the first key `first_key`, resuming from
+	/// `maybe_cursor`, reporting progress via [`sp_io::MultiRemovalResults`].
+	///
+	/// Has the same `limit`/`maybe_cursor` contract as [`Self::clear_prefix`].
+	pub fn drain_prefix_bounded(
+		first_key: impl EncodeLike<Key1>,
+		limit: u32,
+		maybe_cursor: Option<&[u8]>,
+	) -> (Vec<(Key2, Value)>, sp_io::MultiRemovalResults) {
+		let (items, result) = Self::drain_prefix_range_bounded(
+			Self::storage_double_map_final_key1(first_key),
+			limit,
+			maybe_cursor,
+		);
+		(items.into_iter().map(|(_k1, k2, v)| (k2, v)).collect(), result)
+	}
+
+	/// Shared implementation backing [`Self::drain_bounded`] and [`Self::drain_prefix_bounded`]:
+	/// walks up to `limit` backend keys under `prefix`, decoding and removing each one.
+	fn drain_prefix_range_bounded(
+		prefix: Vec<u8>,
+		limit: u32,
+		maybe_cursor: Option<&[u8]>,
+	) -> (Vec<(Key1, Key2, Value)>, sp_io::MultiRemovalResults) {
+		let mut previous_key = maybe_cursor.map(|c| c.to_vec()).unwrap_or_else(|| prefix.clone());
+		let mut items = Vec::new();
+		let mut unique = 0u32;
+		let mut loops = 0u32;
+		let mut maybe_next_cursor = None;
+
+		while loops < limit {
+			let next = match sp_io::storage::next_key(&previous_key).filter(|n| n.starts_with(&prefix))
+			{
+				Some(next) => next,
+				None => break,
+			};
+			previous_key = next;
+			loops += 1;
+
+			if let Some(mut raw_value) = unhashed::get_raw(&previous_key) {
+				let mut key_material = Hasher1::reverse(&previous_key[prefix.len()..]);
+				let decoded = Key1::decode(&mut key_material).ok().and_then(|key1| {
+					let mut key2_material = Hasher2::reverse(key_material);
+					let key2 = Key2::decode(&mut key2_material).ok()?;
+					let value = Value::decode(&mut &raw_value[..]).ok()?;
+					Some((key1, key2, value))
+				});
+				unhashed::kill(&previous_key);
+				raw_value.clear();
+				unique += 1;
+				if let Some(decoded) = decoded {
+					items.push(decoded);
+				} else {
+					log::error!("Invalid drain_bounded: fail to decode key or value");
+				}
+			}
+		}
+
+		if sp_io::storage::next_key(&previous_key).filter(|n| n.starts_with(&prefix)).is_some() {
+			maybe_next_cursor = Some(previous_key);
+		}
+
+		(
+			items,
+			sp_io::MultiRemovalResults {
+				maybe_cursor: maybe_next_cursor,
+				backend: loops,
+				unique,
+				loops,
+			},
+		)
+	}
 }
 
 impl<Prefix, Hasher1, Hasher2, Key1, Key2, Value, QueryKind, OnEmpty, MaxValues>
@@ -1299,6 +1700,64 @@ where
 	}
 }
 
+/// Returns the lexicographically smallest byte string that is strictly greater than every byte
+/// string starting with `prefix`, or `None` if `prefix` consists entirely of `0xff` bytes (in
+/// which case no finite upper bound exists, and the caller should treat "no more keys" as the
+/// only valid termination).
+fn exclusive_successor(prefix: &[u8]) -> Option<Vec<u8>> {
+	let mut successor = prefix.to_vec();
+	while let Some(last) = successor.pop() {
+		if last != 0xff {
+			successor.push(last + 1);
+			return Some(successor)
+		}
+	}
+	None
+}
+
+/// Iterator yielding `(Key2, Value)` pairs with a shared first key, in reverse raw-hashed-key
+/// order. Produced by [`StorageDoubleMap::rev_iter_prefix`].
+pub struct RevPrefixDoubleMapIterator<Key2, Value> {
+	prefix: Vec<u8>,
+	upper_bound: Option<Vec<u8>>,
+	closure: fn(&[u8], &[u8]) -> Result<(Key2, Value), codec::Error>,
+	phantom: core::marker::PhantomData<(Key2, Value)>,
+}
+
+impl<Key2, Value> Iterator for RevPrefixDoubleMapIterator<Key2, Value> {
+	type Item = (Key2, Value);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let upper_bound = self.upper_bound.as_ref()?;
+
+		// Walk forward from the start of the prefix, remembering the last key strictly below
+		// `upper_bound`: that is the predecessor we want to yield next.
+		let mut cursor = self.prefix.clone();
+		let mut predecessor = None;
+		while let Some(next) =
+			sp_io::storage::next_key(&cursor).filter(|n| n.starts_with(&self.prefix))
+		{
+			if next.as_slice() >= upper_bound.as_slice() {
+				break
+			}
+			cursor = next;
+			predecessor = Some(cursor.clone());
+		}
+
+		let predecessor = predecessor?;
+		self.upper_bound = Some(predecessor.clone());
+
+		let raw_value = unhashed::get_raw(&predecessor)?;
+		match (self.closure)(&predecessor[self.prefix.len()..], &raw_value) {
+			Ok(item) => Some(item),
+			Err(e) => {
+				log::error!("Invalid rev_iter_prefix item: {:?}", e);
+				self.next()
+			},
+		}
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -792,6 +792,35 @@ where
 	pub fn translate<O: Decode, F: FnMut(Key, O) -> Option<Value>>(f: F) {
 		<Self as crate::storage::IterableStorageMap<Key, Value>>::translate(f)
 	}
+
+	/// Collect up to `max_items` entries, resuming strictly after `cursor`'s key if given, and
+	/// return them alongside a [`StorageCursor`] to resume a later call - or `None` once the map
+	/// is exhausted.
+	///
+	/// This gives callers such as RPC handlers and offchain workers, which don't persist state
+	/// between calls, a stateless, chunked way to walk a potentially large map. Passing back a
+	/// cursor produced for a different map (or map prefix) is rejected rather than silently
+	/// mis-resumed.
+	pub fn paged_iter(
+		max_items: u32,
+		cursor: Option<&super::StorageCursor>,
+	) -> Result<(Vec<(Key, Value)>, Option<super::StorageCursor>), ()> {
+		let prefix = Self::prefix_hash().to_vec();
+		let mut iter = match cursor {
+			Some(cursor) => Self::iter_from(cursor.raw_key_for(&prefix)?.to_vec()),
+			None => Self::iter(),
+		};
+
+		let items: Vec<_> = (&mut iter).take(max_items as usize).collect();
+		let has_more = sp_io::storage::next_key(&iter.previous_key)
+			.map_or(false, |next| next.starts_with(&prefix));
+		let maybe_cursor = if items.len() == max_items as usize && has_more {
+			Some(super::StorageCursor::new(prefix, iter.previous_key.clone()))
+		} else {
+			None
+		};
+		Ok((items, maybe_cursor))
+	}
 }
 
 impl<Prefix, Hasher, Key, Value, QueryKind, OnEmpty, MaxValues> StorageEntryMetadataBuilder
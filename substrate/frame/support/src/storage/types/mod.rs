@@ -18,9 +18,10 @@
 //! Storage types to build abstraction on storage, they implements storage traits such as
 //! StorageMap and others.
 use alloc::vec::Vec;
-use codec::FullCodec;
+use codec::{Decode, Encode, FullCodec};
 use sp_metadata_ir::{StorageEntryMetadataIR, StorageEntryModifierIR};
 
+mod counted_double_map;
 mod counted_map;
 mod counted_nmap;
 mod double_map;
@@ -29,9 +30,10 @@ mod map;
 mod nmap;
 mod value;
 
+pub use counted_double_map::{CountedStorageDoubleMap, CountedStorageDoubleMapInstance};
 pub use counted_map::{CountedStorageMap, CountedStorageMapInstance, Counter};
 pub use counted_nmap::{CountedStorageNMap, CountedStorageNMapInstance};
-pub use double_map::StorageDoubleMap;
+pub use double_map::{CheckedStorageDoubleMapInstance, RevPrefixDoubleMapIterator, StorageDoubleMap};
 pub use key::{
 	EncodeLikeTuple, HasKeyPrefix, HasReversibleKeyPrefix, Key, KeyGenerator,
 	KeyGeneratorMaxEncodedLen, ReversibleKeyGenerator, TupleToEncodedIter,
@@ -40,6 +42,93 @@ pub use map::StorageMap;
 pub use nmap::StorageNMap;
 pub use value::StorageValue;
 
+/// An opaque, SCALE-encodable cursor into a paginated `StorageMap`/`StorageDoubleMap` iteration.
+///
+/// A `StorageCursor` wraps the raw backend key last visited by a [`Self`]-producing call (e.g.
+/// `paged_iter`), together with the prefix it was produced against. Because it carries the prefix,
+/// a stale cursor obtained from a different map (or a different `iter_prefix` scope of the same
+/// map) is rejected rather than silently mis-resumed. Encoding/decoding it allows callers such as
+/// RPC handlers and offchain workers - which hold no state between calls - to hand the cursor back
+/// across the wire and resume iteration later.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo)]
+pub struct StorageCursor {
+	prefix: Vec<u8>,
+	last_raw_key: Vec<u8>,
+}
+
+impl StorageCursor {
+	/// Construct a cursor for the given `prefix`, having last visited `last_raw_key`.
+	pub fn new(prefix: Vec<u8>, last_raw_key: Vec<u8>) -> Self {
+		Self { prefix, last_raw_key }
+	}
+
+	/// Validate that this cursor belongs to `expected_prefix`, returning the raw key to resume
+	/// from if so.
+	///
+	/// Returns `Err(())` if the cursor was produced against a different prefix (e.g. a different
+	/// map, or a different `k1` scope of the same double map), which would otherwise silently
+	/// resume from an unrelated position.
+	pub fn raw_key_for(&self, expected_prefix: &[u8]) -> Result<&[u8], ()> {
+		if self.prefix == expected_prefix {
+			Ok(&self.last_raw_key)
+		} else {
+			Err(())
+		}
+	}
+}
+
+/// The stage at which a `try_translate`/`try_translate_values` call failed to decode an entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TranslateStage {
+	/// The first storage key failed to decode.
+	Key1,
+	/// The second storage key failed to decode (double maps only).
+	Key2,
+	/// The stored value failed to decode.
+	Value,
+}
+
+/// The error returned by a `try_translate`/`try_translate_values` call when an entry cannot be
+/// decoded: unlike plain `translate`, which silently skips undecodable entries, this surfaces the
+/// raw key and the stage of the first failure so migrations can fail loudly instead of quietly
+/// losing data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TranslateError {
+	/// The raw (hashed) backend key of the entry that could not be decoded.
+	pub raw_key: Vec<u8>,
+	/// Which part of the entry failed to decode.
+	pub stage: TranslateStage,
+}
+
+/// A stable fingerprint of a value type's `scale_info` metadata, used by
+/// [`StorageDoubleMap::translate_checked`](super::double_map::StorageDoubleMap::translate_checked)
+/// to catch a migration's `OldV` parameter silently diverging from what was actually last written.
+pub type TypeHash = [u8; 8];
+
+/// Compute the [`TypeHash`] of `T`, by hashing its fully-qualified Rust type name together with
+/// the identifier (last segment) of its `scale_info` type path.
+///
+/// This is a coarse but dependable notion of "type identity": it catches the common migration
+/// mistake of an `OldV` parameter that names an entirely different (renamed, moved, or
+/// restructured) type, without requiring `scale_info`'s internal portable-registry
+/// representation to be stable across versions.
+pub fn type_hash<T: scale_info::TypeInfo + 'static>() -> TypeHash {
+	let type_info = scale_info::meta_type::<T>().type_info();
+	let ident = type_info.path.ident().unwrap_or_default();
+	let fingerprint = alloc::format!("{}::{}", core::any::type_name::<T>(), ident);
+	sp_core::hashing::twox_64(fingerprint.as_bytes())
+}
+
+/// The error returned by a `translate_checked` call whose recorded "previous value type"
+/// fingerprint does not match the `OldV` the caller asked to decode with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TypeMismatch {
+	/// The fingerprint of the type the caller assumed (`OldV`).
+	pub expected: TypeHash,
+	/// The fingerprint that was actually recorded for the storage item, if any.
+	pub recorded: Option<TypeHash>,
+}
+
 /// Trait implementing how the storage optional value is converted into the queried type.
 ///
 /// It is implemented most notable by:
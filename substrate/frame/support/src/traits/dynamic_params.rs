@@ -22,11 +22,54 @@
 
 use codec::MaxEncodedLen;
 use frame_support::Parameter;
+use scale_info::MetaType;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Metadata describing a single parameter key, for runtime introspection.
+///
+/// Exposed through [`AggregratedKeyValue::key_variants`] so off-chain tooling can discover every
+/// parameter a runtime defines without hardcoding the list.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ParameterMeta {
+	/// The index of this key's variant within its enclosing key/value enum.
+	pub index: u8,
+	/// The name of the pallet this key belongs to, as it appears in the aggregate enum. Empty
+	/// until filled in by the aggregating (runtime-wide) `AggregratedKeyValue` impl.
+	pub pallet: &'static str,
+	/// The human readable name of the key, as declared in its `static` definition.
+	pub name: &'static str,
+	/// The `scale_info` type of the key.
+	pub key_type: MetaType,
+	/// The `scale_info` type of the value.
+	pub value_type: MetaType,
+}
 
 /// A dynamic parameter store across an aggregated KV type.
 pub trait RuntimeParameterStore {
 	type AggregratedKeyValue: AggregratedKeyValue;
 
+	/// Fetch the raw stored value for an aggregated key, without going through a leaf [`Key`]'s
+	/// wrapper type. Used by [`Self::dump`] to walk the full parameter set.
+	fn get_aggregated(
+		key: <Self::AggregratedKeyValue as AggregratedKeyValue>::Key,
+	) -> Option<<Self::AggregratedKeyValue as AggregratedKeyValue>::Value>;
+
+	/// Dump every parameter key this runtime defines alongside its currently stored value, if
+	/// one was set.
+	fn dump() -> Vec<(
+		<Self::AggregratedKeyValue as AggregratedKeyValue>::Key,
+		Option<<Self::AggregratedKeyValue as AggregratedKeyValue>::Value>,
+	)> {
+		Self::AggregratedKeyValue::all_keys()
+			.into_iter()
+			.map(|key| {
+				let value = Self::get_aggregated(key.clone());
+				(key, value)
+			})
+			.collect()
+	}
+
 	/// Get the value of a parametrized key.
 	///
 	/// Should return `None` if no explicit value was set instead of a default.
@@ -40,6 +83,26 @@ pub trait RuntimeParameterStore {
 		<<Self as RuntimeParameterStore>::AggregratedKeyValue as AggregratedKeyValue>::Value:
 			TryIntoKey<<KV as AggregratedKeyValue>::Value>,
 		<KV as AggregratedKeyValue>::Value: TryInto<K::WrappedValue>;
+
+	/// Get the value of a parametrized key, falling back to [`Key::default`] if it was never
+	/// explicitly set.
+	///
+	/// Panics if the key has neither a stored value nor a compiled-in default.
+	fn get_or_default<KV, K>(key: K) -> K::Value
+	where
+		KV: AggregratedKeyValue,
+		K: Key + Into<<KV as AggregratedKeyValue>::Key>,
+		<KV as AggregratedKeyValue>::Key: IntoKey<
+			<<Self as RuntimeParameterStore>::AggregratedKeyValue as AggregratedKeyValue>::Key,
+		>,
+		<<Self as RuntimeParameterStore>::AggregratedKeyValue as AggregratedKeyValue>::Value:
+			TryIntoKey<<KV as AggregratedKeyValue>::Value>,
+		<KV as AggregratedKeyValue>::Value: TryInto<K::WrappedValue>,
+	{
+		Self::get::<KV, K>(key)
+			.or_else(K::default)
+			.expect("key has neither a stored value nor a default; qed")
+	}
 }
 
 /// A dynamic parameter store across a concrete KV type.
@@ -49,6 +112,18 @@ pub trait ParameterStore<KV: AggregratedKeyValue> {
 	where
 		K: Key + Into<<KV as AggregratedKeyValue>::Key>,
 		<KV as AggregratedKeyValue>::Value: TryInto<K::WrappedValue>;
+
+	/// Get the value of a parametrized key, falling back to [`Key::default`] if it was never
+	/// explicitly set.
+	///
+	/// Panics if the key has neither a stored value nor a compiled-in default.
+	fn get_or_default<K>(key: K) -> K::Value
+	where
+		K: Key + Into<<KV as AggregratedKeyValue>::Key>,
+		<KV as AggregratedKeyValue>::Value: TryInto<K::WrappedValue>,
+	{
+		Self::get(key).or_else(K::default).expect("key has neither a stored value nor a default; qed")
+	}
 }
 
 /// Key of a dynamic parameter.
@@ -58,6 +133,20 @@ pub trait Key {
 
 	/// An opaque representation of `Self::Value`.
 	type WrappedValue: Into<Self::Value>;
+
+	/// The compiled-in default for this key, if one was declared.
+	///
+	/// Keys generated by the `#[dynamic_pallet_params]` macro return the default given in their
+	/// `static` declaration; hand-written `Key` impls are not required to override this.
+	fn default() -> Option<Self::Value> {
+		None
+	}
+
+	/// Whether `value` is a legal value for this key, used to reject out-of-range governance
+	/// updates at dispatch time. Keys without a declared validator accept anything.
+	fn validate(_value: &Self::Value) -> bool {
+		true
+	}
 }
 
 /// The aggregated key-value type of a dynamic parameter store.
@@ -70,6 +159,27 @@ pub trait AggregratedKeyValue: Parameter {
 
 	/// Split the aggregated key-value type into its parts.
 	fn into_parts(self) -> (Self::Key, Option<Self::Value>);
+
+	/// Metadata for every parameter key this aggregate defines.
+	///
+	/// The runtime-wide aggregate recurses into each pallet's own `key_variants` and fills in
+	/// [`ParameterMeta::pallet`] with the pallet's name in the aggregate enum.
+	fn key_variants() -> Vec<ParameterMeta> {
+		Vec::new()
+	}
+
+	/// Every concrete key this aggregate defines, for store introspection (see
+	/// [`RuntimeParameterStore::dump`]).
+	fn all_keys() -> Vec<Self::Key> {
+		Vec::new()
+	}
+
+	/// Whether `value` is a legal value for the key it is paired with, dispatching into the
+	/// matching key's [`Key::validate`]. Used to reject out-of-range governance updates before
+	/// they are stored.
+	fn validate_value(_value: &Self::Value) -> bool {
+		true
+	}
 }
 
 impl AggregratedKeyValue for () {